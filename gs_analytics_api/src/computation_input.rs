@@ -1,4 +1,4 @@
-use crate::{DiffCount, VertexId};
+use crate::{DiffCount, EdgePayload, VertexId};
 use derive_new::new;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::operators::arrange::{Arranged, TraceAgent};
@@ -16,6 +16,15 @@ pub type EdgeTrace<T> =
 pub type NodeTrace<T> = Spine<VertexId, (), T, DiffCount, Rc<OrdKeyBatch<VertexId, T, DiffCount>>>;
 pub type NodeArrangement<G> = Arranged<G, TraceAgent<NodeTrace<<G as ScopeParent>::Timestamp>>>;
 pub type EdgeArrangement<G> = Arranged<G, TraceAgent<EdgeTrace<<G as ScopeParent>::Timestamp>>>;
+pub type WeightedEdgeTrace<T> = Spine<
+    VertexId,
+    (VertexId, EdgePayload),
+    T,
+    DiffCount,
+    Rc<OrdValBatch<VertexId, (VertexId, EdgePayload), T, DiffCount>>,
+>;
+pub type WeightedEdgeArrangement<G> =
+    Arranged<G, TraceAgent<WeightedEdgeTrace<<G as ScopeParent>::Timestamp>>>;
 pub type EdgeArrangementEnter<G, T> =
     Arranged<G, TraceEnter<TraceAgent<EdgeTrace<T>>, <G as ScopeParent>::Timestamp>>;
 
@@ -35,6 +44,9 @@ where
     pub edges: EdgeArrangement<G>,
     /// Pre-arranged edges in reverse direction.
     pub reverse_edges: EdgeArrangement<G>,
+    /// Pre-arranged edges in the forward direction, along with the values of the edge properties
+    /// carried by the collection. `None` if the collection carries no edge properties.
+    pub weighted_edges: Option<WeightedEdgeArrangement<G>>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -17,19 +17,69 @@ use timely::progress::Timestamp;
 use timely::ExchangeData;
 
 // Universally used types.
+#[cfg(not(feature = "large-ids"))]
 pub type VertexId = u32;
+#[cfg(not(feature = "large-ids"))]
 pub type EdgeId = u32;
+/// 64-bit ids for graphs with more than 2^32 vertices or edges.
+#[cfg(feature = "large-ids")]
+pub type VertexId = u64;
+#[cfg(feature = "large-ids")]
+pub type EdgeId = u64;
 pub type DiffCount = isize;
 pub type SimpleEdge = (VertexId, VertexId);
+pub type EdgePropertyValue = isize;
+/// The values of the edge properties carried by a collection, in the order they were declared.
+pub type EdgePayload = Vec<EdgePropertyValue>;
 pub type TimelyTimeStamp = u32;
 pub type GsTimestampIndex = usize;
 
 #[derive(Serialize, Deserialize, new, Clone)]
 pub struct FilteredCubeData<T> {
     pub entries: Vec<CubeDataEntries<T>>,
+    /// The names of the edge properties carried by the collection.
+    #[new(default)]
+    pub edge_properties: Vec<String>,
+    /// The payloads of the edges of each entry. Empty if the collection carries no edge properties.
+    #[new(default)]
+    pub payloads: Vec<EntryPayloads>,
+    /// Whether the vertices of each view are given by the vertex diffs of the entries. Otherwise,
+    /// they are the endpoints of the edges of the view.
+    #[new(default)]
+    pub has_vertex_views: bool,
+    /// Whether the entries hold the full view of each timestamp besides its diffs.
+    #[new(default)]
+    pub has_full_views: bool,
 }
-pub type CubeDataEntries<T> = (GsTimestampIndex, T, FilteredCubeEntries, (usize, usize));
+pub type CubeDataEntries<T> =
+    (GsTimestampIndex, T, FilteredCubeEntries, (usize, usize), FilteredCubeVertices);
 pub type FilteredCubeEntries = (Vec<SimpleEdge>, Vec<(SimpleEdge, DiffCount)>);
+/// The full vertices and the vertex diffs of an entry. Empty if the collection has no vertex views.
+pub type FilteredCubeVertices = (Vec<VertexId>, Vec<(VertexId, DiffCount)>);
+/// The payloads of the full and diff edges of an entry, in the same order as the edges.
+pub type EntryPayloads = (Vec<EdgePayload>, Vec<EdgePayload>);
+
+impl<T> FilteredCubeData<T> {
+    pub fn with_payloads(
+        entries: Vec<CubeDataEntries<T>>,
+        edge_properties: Vec<String>,
+        payloads: Vec<EntryPayloads>,
+    ) -> Self {
+        Self { entries, edge_properties, payloads, has_vertex_views: false, has_full_views: false }
+    }
+
+    pub fn has_payloads(&self) -> bool {
+        !self.edge_properties.is_empty()
+    }
+
+    /// Returns the payload of a diff edge of an entry, which is empty if the collection carries no
+    /// edge properties.
+    pub fn diff_payload(&self, entry_index: usize, edge_index: usize) -> EdgePayload {
+        self.payloads
+            .get(entry_index)
+            .map_or_else(Vec::new, |(_, diff_payloads)| diff_payloads[edge_index].clone())
+    }
+}
 
 /// This trait is used to define the output type of a computation.
 ///
@@ -65,6 +115,18 @@ pub trait GraphsurgeComputation: ComputationTypes {
     ) -> Collection<G, Self::Result>
     where
         G::Timestamp: Lattice + Ord + Copy;
+
+    /// The number of edge properties the collection needs to carry for the computation to use
+    /// `ComputationInput::weighted_edges`.
+    fn required_edge_properties(&self) -> usize {
+        0
+    }
+
+    /// Checks the payload of an edge before the computation runs, returning why the computation
+    /// cannot use it otherwise.
+    fn check_edge_payload(&self, _payload: &EdgePayload) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 /// A secondary trait that is used to define computations that want to the use the higher level
@@ -79,6 +141,20 @@ pub trait BasicComputation: ComputationTypes + Send + Sync + Clone + 'static {
     {
         unimplemented!()
     }
+
+    /// Used instead of `basic_computation` when the collection has vertex views, with `nodes`
+    /// holding the vertices of each view. Computations that do not depend on isolated vertices can
+    /// ignore `nodes`.
+    fn basic_computation_with_nodes<G: Scope>(
+        &self,
+        _nodes: &Collection<G, VertexId>,
+        edges: &Collection<G, SimpleEdge>,
+    ) -> Collection<G, Self::Result>
+    where
+        G::Timestamp: Lattice + Ord + Copy,
+    {
+        self.basic_computation(edges)
+    }
 }
 
 /// Timely implementation. Mostly unused for now.
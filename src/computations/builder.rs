@@ -1,9 +1,8 @@
 use crate::computations::pagerank::PageRank;
 use crate::computations::{bfs::Bfs, scc::Scc, spsp::Spsp, sssp::Sssp, wcc::Wcc};
-use crate::computations::{Computation, ComputationProperties};
+use crate::computations::{Computation, ComputationOutput, ComputationProperties};
 use crate::error::GSError;
 use crate::filtered_cubes::FilteredCube;
-use crate::GraphSurgeResult;
 use gs_analytics_api::ComputationRuntimeData;
 use hashbrown::hash_map::DefaultHashBuilder;
 use hashbrown::HashMap;
@@ -14,7 +13,7 @@ pub trait ComputationBuilder {
         properties: &HashMap<String, ComputationProperties>,
         cube: &mut FilteredCube,
         runtime_data: ComputationRuntimeData,
-    ) -> Result<GraphSurgeResult, GSError>;
+    ) -> Result<ComputationOutput, GSError>;
 }
 
 macro_rules! create_builder {
@@ -27,7 +26,7 @@ macro_rules! create_builder {
                 properties: &HashMap<String, ComputationProperties>,
                 cube: &mut FilteredCube,
                 runtime_data: ComputationRuntimeData,
-            ) -> Result<GraphSurgeResult, GSError> {
+            ) -> Result<ComputationOutput, GSError> {
                 $name::instance(properties)?.execute(cube, runtime_data)
             }
         }
@@ -1,16 +1,13 @@
 #![allow(clippy::cast_precision_loss)]
 
+use crate::computations::input_diff::{arrange_input, new_inputs};
 use crate::computations::{Computation, DifferentialRunOutput};
 use crate::error::GSError;
 use crate::filtered_cubes::CubePointer;
-use crate::graph::stream_data::get_worker_indices;
 use crate::util::memory_usage::print_memory_usage;
 use crate::util::timer::{GsDuration, GsTimer};
 use differential_dataflow::lattice::Lattice;
-use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf};
-use differential_dataflow::operators::Threshold;
-use differential_dataflow::AsCollection;
-use gs_analytics_api::{ComputationInput, ComputationRuntimeData, GsTs};
+use gs_analytics_api::{ComputationRuntimeData, GsTs};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 use log::info;
@@ -19,7 +16,6 @@ use std::fmt::Display;
 use timely::dataflow::operators::capture::capture::Capture;
 use timely::dataflow::operators::capture::event::Event::Messages;
 use timely::dataflow::operators::probe::Handle;
-use timely::dataflow::operators::unordered_input::UnorderedInput;
 use timely::progress::timestamp::Refines;
 use timely::Configuration;
 
@@ -30,15 +26,14 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
     cube_data: CubePointer<T>,
     computation: C,
     runtime_data: &ComputationRuntimeData,
-) -> Result<DifferentialRunOutput<C, T>, GSError> {
+) -> Result<(DifferentialRunOutput<C, T>, AdaptiveModel), GSError> {
     print_memory_usage(format_args!("starting differential workers"));
     let materialize_results = runtime_data.should_materialize_results();
     let batch_size = runtime_data.batch_size;
-    let comp_multipler = runtime_data.comp_multipler;
-    let diff_multipler = runtime_data.diff_multipler;
-    let limit = runtime_data.limit;
-    let use_lr = runtime_data.use_lr;
+    let settings = AdaptiveSettings::new(runtime_data);
     let pre_specified_splits = runtime_data.splits.clone();
+    let has_payloads = cube_data.has_payloads();
+    let has_vertex_views = cube_data.has_vertex_views;
 
     // Create channels for worker 0 to send decisions to the other workers.
     let channels =
@@ -50,8 +45,7 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
             let worker_count = worker.peers();
             let mut all_times = HashMap::new();
             let mut actual_splits = HashSet::new();
-            let mut individual_tracker = (Vec::new(), Vec::new());
-            let mut diff_tracker = (Vec::new(), Vec::new());
+            let mut model = AdaptiveModel::default();
             let mut results = HashMap::new();
             let timer = GsTimer::now();
 
@@ -61,56 +55,44 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
 
             'outer: loop {
                 let mut probe = Handle::new();
-                let (mut edge_input, mut edge_cap, result_stream) =
+                let (mut inputs, mut input_cap, result_stream) =
                     worker.dataflow::<T, _, _>(|scope| {
-                        let (edge_session, edge_stream) = scope.new_unordered_input();
-                        let edge_stream = edge_stream.as_collection();
-
-                        let forward_edges = edge_stream.arrange_by_key();
-                        let reverse_edges =
-                            forward_edges.as_collection(|&k, &v| (v, k)).arrange_by_key();
-                        let nodes = forward_edges
-                            .flat_map_ref(|src, dst| Some(*src).into_iter().chain(Some(*dst)))
-                            .distinct()
-                            .arrange_by_self();
-                        let input_stream =
-                            ComputationInput::new(nodes, forward_edges, reverse_edges);
+                        let ((inputs, input_cap), input_collections) =
+                            new_inputs(scope, has_payloads, has_vertex_views);
+                        let input_stream = arrange_input(&input_collections);
 
                         let results =
                             computation.graph_analytics(&input_stream).probe_with(&mut probe);
 
                         (
-                            edge_session.0,
-                            edge_session.1,
+                            inputs,
+                            input_cap,
                             if materialize_results { Some(results.inner.capture()) } else { None },
                         )
                     });
 
                 if let Some(indv_index) = loop_indv {
-                    let (_, view_timestamp, (_, _), _) = &cube_data.entries[indv_index];
-                    edge_cap.downgrade(&view_timestamp);
+                    let (_, view_timestamp, (_, _), _, _) = &cube_data.entries[indv_index];
+                    input_cap.downgrade(view_timestamp);
                     if worker_index == 0 {
                         info!("Running {} as {}", view_timestamp, view_timestamp);
                     }
                     let timer = GsTimer::now();
                     let mut count: isize = 0;
                     let mut diffs = 0;
+                    for (entry_index, (_, _, _, (adds, dels), _)) in
+                        cube_data.entries[..=indv_index].iter().enumerate()
                     {
-                        let mut session = edge_input.session(edge_cap.clone());
-                        for (_, _, (_, diff_data), (adds, dels)) in
-                            &cube_data.entries[..=indv_index]
-                        {
-                            count += isize::try_from(*adds).expect("overflow")
-                                - isize::try_from(*dels).expect("overflow");
-                            let (left_index, right_index) =
-                                get_worker_indices(diff_data.len(), worker_index, worker_count);
-                            diffs += right_index - left_index;
-                            {
-                                for (edge, change) in &diff_data[left_index..right_index] {
-                                    session.give((*edge, *view_timestamp, *change));
-                                }
-                            }
-                        }
+                        count += isize::try_from(*adds).expect("overflow")
+                            - isize::try_from(*dels).expect("overflow");
+                        diffs += inputs.give_entry(
+                            &input_cap,
+                            &cube_data,
+                            entry_index,
+                            *view_timestamp,
+                            worker_index,
+                            worker_count,
+                        );
                     }
                     let loaded = timer.elapsed();
                     if worker_index == 0 {
@@ -125,8 +107,8 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                     }
                     let next_timestamp = view_timestamp.next();
                     let timer2 = GsTimer::now();
-                    edge_cap.downgrade(&next_timestamp);
-                    while probe.less_than(edge_cap.time()) {
+                    input_cap.downgrade(&next_timestamp);
+                    while probe.less_than(input_cap.time()) {
                         worker.step();
                     }
                     let stable = timer2.elapsed();
@@ -144,8 +126,8 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                         .insert(*view_timestamp, (GsDuration::default(), loaded, stable, total));
                     let runtime = total.as_secs_f64();
                     if count > 0 {
-                        individual_tracker.0.push(count as LRDataPoint);
-                        individual_tracker.1.push(runtime);
+                        model.individual_runs.0.push(count as LRDataPoint);
+                        model.individual_runs.1.push(runtime);
                     }
                     if worker_index == 0 {
                         info!("ML result: index = {} : {} -> {:.3}", indv_index, count, runtime);
@@ -166,44 +148,41 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                 if let Some((diff_start, diff_end)) = loop_diffs {
                     let timer = GsTimer::now();
                     let mut count = 0;
-                    let &(_, start_ts, (_, _), _) = &cube_data.entries[diff_start];
-                    let &(_, end_ts, (_, _), _) = &cube_data.entries[diff_end - 1];
-                    edge_cap.downgrade(&start_ts);
+                    let &(_, start_ts, (_, _), _, _) = &cube_data.entries[diff_start];
+                    let &(_, end_ts, (_, _), _, _) = &cube_data.entries[diff_end - 1];
+                    input_cap.downgrade(&start_ts);
+                    for (entry_index, (_, view_timestamp, _, (adds, dels), _)) in
+                        cube_data.entries.iter().enumerate().take(diff_end).skip(diff_start)
                     {
-                        let mut session = edge_input.session(edge_cap.clone());
-                        for (_, view_timestamp, (_, diff_data), (adds, dels)) in
-                            &cube_data.entries[diff_start..diff_end]
-                        {
-                            if worker_index == 0 {
-                                info!("diffs Running {} as {}", view_timestamp, start_ts);
-                            }
-                            all_times.insert(*view_timestamp, Default::default());
-                            count += *adds + *dels;
-                            let (left_index, right_index) =
-                                get_worker_indices(diff_data.len(), worker_index, worker_count);
-                            {
-                                for (edge, change) in &diff_data[left_index..right_index] {
-                                    session.give((*edge, *view_timestamp, *change));
-                                }
-                            }
-                            if worker_index == 0 {
-                                info!(
-                                    "[worker {:>2}] diffs loaded {} diffs at timestamp {} \
-                                (as {}) in {}",
-                                    worker_index,
-                                    right_index - left_index,
-                                    view_timestamp,
-                                    start_ts,
-                                    timer.elapsed().seconds_string()
-                                );
-                            }
+                        if worker_index == 0 {
+                            info!("diffs Running {} as {}", view_timestamp, start_ts);
+                        }
+                        all_times.insert(*view_timestamp, Default::default());
+                        count += *adds + *dels;
+                        let diffs = inputs.give_entry(
+                            &input_cap,
+                            &cube_data,
+                            entry_index,
+                            *view_timestamp,
+                            worker_index,
+                            worker_count,
+                        );
+                        if worker_index == 0 {
+                            info!(
+                                "[worker {:>2}] diffs loaded {} diffs at timestamp {} (as {}) in {}",
+                                worker_index,
+                                diffs,
+                                view_timestamp,
+                                start_ts,
+                                timer.elapsed().seconds_string()
+                            );
                         }
                     }
                     let loaded = timer.elapsed();
                     let next_timestamp = end_ts.next();
                     let timer2 = GsTimer::now();
-                    edge_cap.downgrade(&next_timestamp);
-                    while probe.less_than(edge_cap.time()) {
+                    input_cap.downgrade(&next_timestamp);
+                    while probe.less_than(input_cap.time()) {
                         worker.step();
                     }
                     let stable = timer2.elapsed();
@@ -221,8 +200,8 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                     all_times.insert(start_ts, (GsDuration::default(), loaded, stable, total));
                     let runtime = total.as_secs_f64();
                     if count > 0 {
-                        diff_tracker.0.push(count as LRDataPoint);
-                        diff_tracker.1.push(runtime);
+                        model.diff_runs.0.push(count as LRDataPoint);
+                        model.diff_runs.1.push(runtime);
                     }
                     if worker_index == 0 {
                         info!(
@@ -252,14 +231,15 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                     let decisions = if worker_index == 0 {
                         let mut indvc_total: isize = cube_data.entries.iter().take(start).fold(
                             0,
-                            |acc, &(_, _, (_, _), (additions, deletions))| {
+                            |acc, &(_, _, (_, _), (additions, deletions), _)| {
                                 acc + isize::try_from(additions).expect("Overflow")
                                     - isize::try_from(deletions).expect("Overflow")
                             },
                         );
                         let mut decisions = Vec::new();
                         for index in start..end {
-                            let &(_, _, (_, _), (additions, deletions)) = &cube_data.entries[index];
+                            let &(_, _, (_, _), (additions, deletions), _) =
+                                &cube_data.entries[index];
                             let diffc_total =
                                 isize::try_from(additions + deletions).expect("Overflow");
                             indvc_total += isize::try_from(additions).expect("Overflow")
@@ -273,75 +253,8 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                                 );
                                 decision
                             } else {
-                                let closure =
-                                    |totals: &[LRDataPoint],
-                                     runtimes: &[LRDataPoint],
-                                     new_total: isize| {
-                                        assert_eq!(totals.len(), runtimes.len());
-                                        let end_index = totals.len();
-                                        let start_index = if limit > 0 && limit < end_index {
-                                            end_index - limit
-                                        } else {
-                                            0
-                                        };
-                                        let totals_slice = &totals[start_index..end_index];
-                                        let runtimes_slice = &runtimes[start_index..end_index];
-
-                                        let mut lr = LinearRegression::new();
-                                        lr.fit(totals_slice, runtimes_slice);
-                                        let prediction_lr = lr.predict(new_total as LRDataPoint);
-
-                                        let avg_runtime =
-                                            runtimes_slice.iter().sum::<LRDataPoint>()
-                                                / totals_slice.iter().sum::<LRDataPoint>();
-                                        let prediction_avg =
-                                            avg_runtime * (new_total as LRDataPoint);
-
-                                        let prediction = if !prediction_lr.is_finite() || !use_lr {
-                                            prediction_avg
-                                        } else {
-                                            prediction_lr
-                                        };
-
-                                        (
-                                            prediction,
-                                            prediction_lr,
-                                            prediction_avg,
-                                            start_index,
-                                            end_index,
-                                        )
-                                    };
-
-                                let (
-                                    predicted_diff_time,
-                                    lr_diff_time,
-                                    avg_diff_time,
-                                    start_index_d,
-                                    end_index_d,
-                                ) = closure(&diff_tracker.0, &diff_tracker.1, diffc_total);
-                                let (
-                                    predicted_indv_time,
-                                    lr_indv_time,
-                                    avg_indv_time,
-                                    start_index_i,
-                                    end_index_i,
-                                ) = closure(
-                                    &individual_tracker.0,
-                                    &individual_tracker.1,
-                                    indvc_total,
-                                );
-                                let indv_time_scaled = predicted_indv_time
-                                    * comp_multipler.unwrap_or(DEFAULT_MULTIPLIER);
-
-                                let scaled_indvc_total = indvc_total as f64
-                                    * diff_multipler.unwrap_or(DEFAULT_MULTIPLIER);
-                                let (decision, string) = if diffc_total as f64 > scaled_indvc_total
-                                {
-                                    // No point running differentially.
-                                    (true, "diffs")
-                                } else {
-                                    (indv_time_scaled < predicted_diff_time, "time")
-                                };
+                                let prediction =
+                                    model.predict_split(diffc_total, indvc_total, &settings);
                                 info!(
                                     "ML result: index = {} ; diffc = {} predict = {:.3} \
                                         [lr={:.3};avg={:.3};limit={}..{}] ; \
@@ -350,23 +263,23 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                                         decision_should_split({}) = {}",
                                     index,
                                     diffc_total,
-                                    predicted_diff_time,
-                                    lr_diff_time,
-                                    avg_diff_time,
-                                    start_index_d,
-                                    end_index_d,
+                                    prediction.diff_time.predicted,
+                                    prediction.diff_time.lr,
+                                    prediction.diff_time.avg,
+                                    prediction.diff_time.start_index,
+                                    prediction.diff_time.end_index,
                                     indvc_total,
-                                    scaled_indvc_total,
-                                    predicted_indv_time,
-                                    indv_time_scaled,
-                                    lr_indv_time,
-                                    avg_indv_time,
-                                    start_index_i,
-                                    end_index_i,
-                                    string,
-                                    decision,
+                                    prediction.scaled_indvc_total,
+                                    prediction.indv_time.predicted,
+                                    prediction.indv_time_scaled,
+                                    prediction.indv_time.lr,
+                                    prediction.indv_time.avg,
+                                    prediction.indv_time.start_index,
+                                    prediction.indv_time.end_index,
+                                    prediction.reason,
+                                    prediction.should_split,
                                 );
-                                decision
+                                prediction.should_split
                             };
                             if decision {
                                 actual_splits.insert(index);
@@ -392,44 +305,42 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                         let mut count = 0;
                         let diff_start = index_consider;
                         let diff_end = index_consider + index;
-                        let (_, start_ts, (_, _), _) = &cube_data.entries[diff_start];
-                        let (_, end_ts, (_, _), _) = &cube_data.entries[diff_end - 1];
-                        edge_cap.downgrade(&start_ts);
+                        let (_, start_ts, (_, _), _, _) = &cube_data.entries[diff_start];
+                        let (_, end_ts, (_, _), _, _) = &cube_data.entries[diff_end - 1];
+                        input_cap.downgrade(start_ts);
+                        for (entry_index, (_, view_timestamp, _, (adds, dels), _)) in
+                            cube_data.entries.iter().enumerate().take(diff_end).skip(diff_start)
                         {
-                            let mut session = edge_input.session(edge_cap.clone());
-                            for (_, view_timestamp, (_, diff_data), (adds, dels)) in
-                                &cube_data.entries[diff_start..diff_end]
-                            {
-                                if worker_index == 0 {
-                                    info!("diffs2: Running {} as {}", view_timestamp, start_ts);
-                                }
-                                all_times.insert(*view_timestamp, Default::default());
-                                count += *adds + *dels;
-                                let (left_index, right_index) =
-                                    get_worker_indices(diff_data.len(), worker_index, worker_count);
-                                {
-                                    for (edge, change) in &diff_data[left_index..right_index] {
-                                        session.give((*edge, *view_timestamp, *change));
-                                    }
-                                }
-                                if worker_index == 0 {
-                                    info!(
-                                        "[worker {:>2}] diffs2 loaded {} diffs at timestamp {}\
+                            if worker_index == 0 {
+                                info!("diffs2: Running {} as {}", view_timestamp, start_ts);
+                            }
+                            all_times.insert(*view_timestamp, Default::default());
+                            count += *adds + *dels;
+                            let diffs = inputs.give_entry(
+                                &input_cap,
+                                &cube_data,
+                                entry_index,
+                                *view_timestamp,
+                                worker_index,
+                                worker_count,
+                            );
+                            if worker_index == 0 {
+                                info!(
+                                    "[worker {:>2}] diffs2 loaded {} diffs at timestamp {}\
                                     (as {}) in {}",
-                                        worker_index,
-                                        right_index - left_index,
-                                        view_timestamp,
-                                        start_ts,
-                                        timer.elapsed().seconds_string()
-                                    );
-                                }
+                                    worker_index,
+                                    diffs,
+                                    view_timestamp,
+                                    start_ts,
+                                    timer.elapsed().seconds_string()
+                                );
                             }
                         }
                         let loaded = timer.elapsed();
                         let next_timestamp = end_ts.next();
                         let timer2 = GsTimer::now();
-                        edge_cap.downgrade(&next_timestamp);
-                        while probe.less_than(edge_cap.time()) {
+                        input_cap.downgrade(&next_timestamp);
+                        while probe.less_than(input_cap.time()) {
                             worker.step();
                         }
                         let stable = timer2.elapsed();
@@ -447,8 +358,8 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
                         }
                         all_times.insert(*start_ts, (GsDuration::default(), loaded, stable, total));
                         let runtime = total.as_secs_f64();
-                        diff_tracker.0.push(count as LRDataPoint);
-                        diff_tracker.1.push(runtime);
+                        model.diff_runs.0.push(count as LRDataPoint);
+                        model.diff_runs.1.push(runtime);
                         if worker_index == 0 {
                             info!(
                                 "ML result: index = {}..{} : {} -> {:.3}",
@@ -488,13 +399,155 @@ pub fn differential_run_adaptive<C: Computation, T: GsTs + Refines<()> + Lattice
 
             let worker_time = timer.elapsed();
             info!("Worker {:>2} finished in total {}", worker_index, worker_time.seconds_string());
-            (results, all_times.into_iter().collect_vec(), worker_time, actual_splits)
+            (
+                (results, all_times.into_iter().collect_vec(), worker_time, actual_splits),
+                // Worker 0 makes the decisions, so its model is the one that was used.
+                if worker_index == 0 { Some(model) } else { None },
+            )
         })
         .map_err(GSError::Timely)?
         .join();
     print_memory_usage(format_args!("done with timely"));
 
-    Ok(worker_results)
+    let mut fitted_model = AdaptiveModel::default();
+    let worker_results = worker_results
+        .into_iter()
+        .map(|result| {
+            result.map(|(output, model)| {
+                if let Some(model) = model {
+                    fitted_model = model;
+                }
+                output
+            })
+        })
+        .collect();
+    Ok((worker_results, fitted_model))
+}
+
+/// The runtimes of the views run individually and of the batches of diffs run differentially,
+/// against their sizes, from which the adaptive runner predicts where to split.
+#[derive(Clone, Debug, Default)]
+pub struct AdaptiveModel {
+    /// The edge counts of the views run individually, and their runtimes.
+    pub individual_runs: (Vec<LRDataPoint>, Vec<LRDataPoint>),
+    /// The diff counts of the batches run differentially, and their runtimes.
+    pub diff_runs: (Vec<LRDataPoint>, Vec<LRDataPoint>),
+}
+
+/// The parameters of a `RUN ADAPTIVE` query that affect its split decisions.
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveSettings {
+    pub limit: usize,
+    pub use_lr: bool,
+    pub comp_multipler: f64,
+    pub diff_multipler: f64,
+}
+
+impl AdaptiveSettings {
+    pub fn new(runtime_data: &ComputationRuntimeData) -> Self {
+        Self {
+            limit: runtime_data.limit,
+            use_lr: runtime_data.use_lr,
+            comp_multipler: runtime_data.comp_multipler.unwrap_or(DEFAULT_MULTIPLIER),
+            diff_multipler: runtime_data.diff_multipler.unwrap_or(DEFAULT_MULTIPLIER),
+        }
+    }
+}
+
+pub struct RuntimePrediction {
+    pub predicted: LRDataPoint,
+    pub lr: LRDataPoint,
+    pub avg: LRDataPoint,
+    /// The range of the runs the prediction is fitted on.
+    pub start_index: usize,
+    pub end_index: usize,
+}
+
+pub struct SplitPrediction {
+    pub diff_time: RuntimePrediction,
+    pub indv_time: RuntimePrediction,
+    pub indv_time_scaled: f64,
+    pub scaled_indvc_total: f64,
+    /// Whether the decision was made on the diff counts or on the predicted runtimes.
+    pub reason: &'static str,
+    pub should_split: bool,
+}
+
+impl AdaptiveModel {
+    pub fn is_fitted(&self) -> bool {
+        !self.individual_runs.0.is_empty() && !self.diff_runs.0.is_empty()
+    }
+
+    /// Predicts whether a view should be run individually, i.e., split from the differential run,
+    /// given the diffs to run differentially and the edges to run individually.
+    pub fn predict_split(
+        &self,
+        diffc_total: isize,
+        indvc_total: isize,
+        settings: &AdaptiveSettings,
+    ) -> SplitPrediction {
+        let diff_time =
+            predict_runtime(&self.diff_runs.0, &self.diff_runs.1, diffc_total, settings);
+        let indv_time = predict_runtime(
+            &self.individual_runs.0,
+            &self.individual_runs.1,
+            indvc_total,
+            settings,
+        );
+        let indv_time_scaled = indv_time.predicted * settings.comp_multipler;
+
+        let scaled_indvc_total = indvc_total as f64 * settings.diff_multipler;
+        let (should_split, reason) = if diffc_total as f64 > scaled_indvc_total {
+            // No point running differentially.
+            (true, "diffs")
+        } else {
+            (indv_time_scaled < diff_time.predicted, "time")
+        };
+        SplitPrediction {
+            diff_time,
+            indv_time,
+            indv_time_scaled,
+            scaled_indvc_total,
+            reason,
+            should_split,
+        }
+    }
+}
+
+fn predict_runtime(
+    totals: &[LRDataPoint],
+    runtimes: &[LRDataPoint],
+    new_total: isize,
+    settings: &AdaptiveSettings,
+) -> RuntimePrediction {
+    assert_eq!(totals.len(), runtimes.len());
+    let end_index = totals.len();
+    let start_index = if settings.limit > 0 && settings.limit < end_index {
+        end_index - settings.limit
+    } else {
+        0
+    };
+    let totals_slice = &totals[start_index..end_index];
+    let runtimes_slice = &runtimes[start_index..end_index];
+
+    let mut lr = LinearRegression::new();
+    lr.fit(totals_slice, runtimes_slice);
+    let prediction_lr = lr.predict(new_total as LRDataPoint);
+
+    let avg_runtime =
+        runtimes_slice.iter().sum::<LRDataPoint>() / totals_slice.iter().sum::<LRDataPoint>();
+    let prediction_avg = avg_runtime * (new_total as LRDataPoint);
+
+    let prediction =
+        if !prediction_lr.is_finite() || !settings.use_lr { prediction_avg } else { prediction_lr };
+
+    RuntimePrediction {
+        predicted: prediction,
+        lr: prediction_lr,
+        avg: prediction_avg,
+        start_index,
+        end_index,
+    }
 }
 
 pub type LRDataPoint = f64;
@@ -1,20 +1,16 @@
+use crate::computations::input_diff::{arrange_input, new_inputs};
 use crate::computations::{Computation, DifferentialRunOutput, SplitIndices};
 use crate::error::GSError;
 use crate::filtered_cubes::CubePointer;
-use crate::graph::stream_data::get_worker_indices;
 use crate::util::memory_usage::print_memory_usage;
 use crate::util::timer::{GsDuration, GsTimer};
 use differential_dataflow::lattice::Lattice;
-use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf};
-use differential_dataflow::operators::Threshold;
-use differential_dataflow::AsCollection;
-use gs_analytics_api::{ComputationInput, ComputationRuntimeData, GsTs};
+use gs_analytics_api::{ComputationRuntimeData, GsTs};
 use hashbrown::HashMap;
 use itertools::Itertools;
 use log::info;
 use std::fmt::Display;
 use timely::dataflow::operators::probe::Handle;
-use timely::dataflow::operators::unordered_input::UnorderedInput;
 use timely::progress::timestamp::Refines;
 use timely::progress::Timestamp;
 
@@ -28,27 +24,21 @@ pub fn differential_run_1_stage<
     runtime_data: &ComputationRuntimeData,
 ) -> Result<DifferentialRunOutput<C, T>, GSError> {
     print_memory_usage(format_args!("starting differential workers"));
+    let has_payloads = cube_data.has_payloads();
+    let has_vertex_views = cube_data.has_vertex_views;
     let worker_results = timely::execute(runtime_data.timely_config(), move |worker| {
         let worker_index = worker.index();
         let worker_count = worker.peers();
         let worker_timer = GsTimer::now();
 
         let mut probe = Handle::new();
-        let (mut edge_input, mut edge_cap) = worker.dataflow(|scope| {
-            let (edge_session, edge_stream) = scope.new_unordered_input();
-            let edge_stream = edge_stream.as_collection();
-
-            let forward_edges = edge_stream.arrange_by_key();
-            let reverse_edges = forward_edges.as_collection(|&k, &v| (v, k)).arrange_by_key();
-            let nodes = forward_edges
-                .flat_map_ref(|src, dst| Some(*src).into_iter().chain(Some(*dst)))
-                .distinct()
-                .arrange_by_self();
-
-            let input_stream = ComputationInput::new(nodes, forward_edges, reverse_edges);
+        let (mut inputs, mut input_cap) = worker.dataflow(|scope| {
+            let (input_session, input_collections) =
+                new_inputs(scope, has_payloads, has_vertex_views);
+            let input_stream = arrange_input(&input_collections);
             computation.graph_analytics(&input_stream).probe_with(&mut probe);
 
-            edge_session
+            input_session
         });
 
         if worker_index == 0 {
@@ -56,29 +46,29 @@ pub fn differential_run_1_stage<
         }
 
         let mut all_times = Vec::new();
-        for (_, timestamp, (_, data), _) in &cube_data.entries {
-            let (left_index, right_index) =
-                get_worker_indices(data.len(), worker_index, worker_count);
+        for (entry_index, (_, timestamp, _, _, _)) in cube_data.entries.iter().enumerate() {
             let timer = GsTimer::now();
-            {
-                let mut session = edge_input.session(edge_cap.clone());
-                for (edge, change) in &data[left_index..right_index] {
-                    session.give((*edge, *timestamp, *change));
-                }
-            }
+            let diffs = inputs.give_entry(
+                &input_cap,
+                &cube_data,
+                entry_index,
+                *timestamp,
+                worker_index,
+                worker_count,
+            );
             let loaded = timer.elapsed();
             if worker_index == 0 {
                 info!(
                     "[worker {:>2}] loaded {} diffs at timestamp {} in {}",
                     worker_index,
-                    right_index - left_index,
+                    diffs,
                     *timestamp,
                     loaded.seconds_string()
                 );
             }
             let timer2 = GsTimer::now();
-            edge_cap.downgrade(&timestamp.next());
-            while probe.less_than(edge_cap.time()) {
+            input_cap.downgrade(&timestamp.next());
+            while probe.less_than(input_cap.time()) {
                 worker.step();
             }
             let stable = timer2.elapsed();
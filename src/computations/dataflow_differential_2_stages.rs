@@ -1,13 +1,10 @@
+use crate::computations::input_diff::{arrange_input, new_inputs};
 use crate::computations::{Computation, DifferentialRunOutput, SplitIndices};
 use crate::error::GSError;
 use crate::filtered_cubes::CubePointer;
-use crate::graph::stream_data::get_worker_indices;
 use crate::util::memory_usage::print_memory_usage;
 use crate::util::timer::{GsDuration, GsTimer};
 use differential_dataflow::lattice::Lattice;
-use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf};
-use differential_dataflow::operators::Threshold;
-use differential_dataflow::AsCollection;
 use gs_analytics_api::{ComputationInput, ComputationRuntimeData};
 use hashbrown::HashMap;
 use itertools::Itertools;
@@ -15,7 +12,6 @@ use log::info;
 use std::fmt::Display;
 use timely::dataflow::operators::capture::capture::Capture;
 use timely::dataflow::operators::capture::event::Event::Messages;
-use timely::dataflow::operators::unordered_input::UnorderedInput;
 use timely::dataflow::operators::Exchange;
 use timely::progress::timestamp::Refines;
 use timely::progress::Timestamp;
@@ -32,25 +28,32 @@ pub fn differential_run_2_stage<
     print_memory_usage(format_args!("starting differential workers"));
     let materialize_results = runtime_data.should_materialize_results();
     let threads = runtime_data.threads;
+    let has_payloads = cube_data.has_payloads();
+    let has_vertex_views = cube_data.has_vertex_views;
     let worker_results = timely::execute(runtime_data.timely_config(), move |worker| {
         let worker_index = worker.index();
         let worker_count = worker.peers();
         let worker_timer = GsTimer::now();
 
-        let ((mut edge_input, edge_cap), mut forward_edges, mut reverse_edges, mut nodes) = worker
-            .dataflow(|scope| {
-                let (edge_session, edge_stream) = scope.new_unordered_input();
-                let edge_stream = edge_stream.as_collection();
+        let (
+            (mut inputs, input_cap),
+            mut forward_edges,
+            mut reverse_edges,
+            mut nodes,
+            mut weighted_edges,
+        ) = worker.dataflow(|scope| {
+            let (input_session, input_collections) =
+                new_inputs(scope, has_payloads, has_vertex_views);
+            let input = arrange_input(&input_collections);
 
-                let forward_edges = edge_stream.arrange_by_key();
-                let reverse_edges = forward_edges.as_collection(|&k, &v| (v, k)).arrange_by_key();
-                let nodes = forward_edges
-                    .flat_map_ref(|src, dst| Some(*src).into_iter().chain(Some(*dst)))
-                    .distinct()
-                    .arrange_by_self();
-
-                (edge_session, forward_edges.trace, reverse_edges.trace, nodes.trace)
-            });
+            (
+                input_session,
+                input.edges.trace,
+                input.reverse_edges.trace,
+                input.nodes.trace,
+                input.weighted_edges.map(|weighted_edges| weighted_edges.trace),
+            )
+        });
 
         if worker_index == 0 {
             info!("Inserting data");
@@ -58,26 +61,27 @@ pub fn differential_run_2_stage<
 
         let mut all_times = HashMap::new();
         let timer = GsTimer::now();
-        {
-            let mut session = edge_input.session(edge_cap);
-            for (_, timestamp, (_, data), _) in &cube_data.entries {
-                let (left_index, right_index) =
-                    get_worker_indices(data.len(), worker_index, worker_count);
-                for (edge, change) in &data[left_index..right_index] {
-                    session.give((*edge, *timestamp, *change));
-                }
-                if worker_index == 0 {
-                    info!(
-                        "[worker {:>2}] loaded {} diffs at timestamp {} in {}",
-                        worker_index,
-                        right_index - left_index,
-                        timestamp,
-                        timer.elapsed().seconds_string()
-                    );
-                }
-                all_times.insert(*timestamp, Default::default());
+        for (entry_index, (_, timestamp, _, _, _)) in cube_data.entries.iter().enumerate() {
+            let diffs = inputs.give_entry(
+                &input_cap,
+                &cube_data,
+                entry_index,
+                *timestamp,
+                worker_index,
+                worker_count,
+            );
+            if worker_index == 0 {
+                info!(
+                    "[worker {:>2}] loaded {} diffs at timestamp {} in {}",
+                    worker_index,
+                    diffs,
+                    timestamp,
+                    timer.elapsed().seconds_string()
+                );
             }
+            all_times.insert(*timestamp, Default::default());
         }
+        drop(input_cap);
         while worker.step() {}
         let load_time = timer.elapsed();
         if worker_index == 0 {
@@ -89,7 +93,8 @@ pub fn differential_run_2_stage<
             let nodes = nodes.import(scope);
             let forward = forward_edges.import(scope);
             let reverse = reverse_edges.import(scope);
-            let input_stream = ComputationInput::new(nodes, forward, reverse);
+            let weighted = weighted_edges.as_mut().map(|trace| trace.import(scope));
+            let input_stream = ComputationInput::new(nodes, forward, reverse, weighted);
 
             let results = computation.graph_analytics(&input_stream);
 
@@ -1,13 +1,12 @@
+use crate::computations::input_diff::{new_inputs, InputCapability, InputHandles};
 use crate::computations::{Computation, DifferentialRunOutput, SplitIndices};
 use crate::computations::{DifferentialResults, Times};
 use crate::error::GSError;
 use crate::filtered_cubes::materialise::{CubeDiffIterators, DiffIteratorPointer};
-use crate::graph::stream_data::get_worker_indices;
 use crate::util::memory_usage::print_memory_usage;
 use crate::util::timer::{GsDuration, GsTimer};
 use differential_dataflow::lattice::Lattice;
-use differential_dataflow::AsCollection;
-use gs_analytics_api::{ComputationRuntimeData, DiffCount, SimpleEdge};
+use gs_analytics_api::{ComputationRuntimeData, DiffCount};
 use hashbrown::HashMap;
 use log::info;
 use std::fmt::Display;
@@ -17,9 +16,6 @@ use timely::dataflow::operators::capture::capture::Capture;
 use timely::dataflow::operators::capture::event::Event::Messages;
 use timely::dataflow::operators::capture::Event;
 use timely::dataflow::operators::probe::Handle;
-use timely::dataflow::operators::unordered_input::ActivateCapability;
-use timely::dataflow::operators::unordered_input::UnorderedHandle;
-use timely::dataflow::operators::unordered_input::UnorderedInput;
 use timely::progress::timestamp::Refines;
 use timely::progress::Timestamp;
 use timely::worker::Worker;
@@ -32,6 +28,7 @@ pub fn differential_run_basic<
     T: Timestamp + Refines<()> + Lattice + Display + Copy,
 >(
     diff_iterators: DiffIteratorPointer<T>,
+    has_vertex_views: bool,
     starting_timestamp: T,
     computation: C,
     runtime_data: &ComputationRuntimeData,
@@ -45,27 +42,30 @@ pub fn differential_run_basic<
         let timer = GsTimer::now();
 
         let mut probe = Handle::new();
-        let (mut edge_input, mut edge_cap, result_stream) = worker.dataflow(|scope| {
-            let ((edge_input, edge_cap), edges) = scope.new_unordered_input();
-            let edges = edges.as_collection();
+        let (mut inputs, mut input_cap, result_stream) = worker.dataflow(|scope| {
+            let ((inputs, input_cap), input) = new_inputs(scope, false, has_vertex_views);
 
-            let result = computation.basic_computation(&edges).probe_with(&mut probe);
+            let result = match &input.vertices {
+                Some(nodes) => computation.basic_computation_with_nodes(nodes, &input.edges),
+                None => computation.basic_computation(&input.edges),
+            }
+            .probe_with(&mut probe);
 
             (
-                edge_input,
-                edge_cap,
+                inputs,
+                input_cap,
                 if materialize_results { Some(result.inner.capture()) } else { None },
             )
         });
 
-        edge_cap.downgrade(&starting_timestamp);
+        input_cap.downgrade(&starting_timestamp);
 
         let mut all_times = Vec::new();
         let mut results = HashMap::new();
         insert_edges::<_, C, T>(
             &*diff_iterators,
-            &mut edge_input,
-            edge_cap,
+            &mut inputs,
+            input_cap,
             &mut worker,
             &probe,
             &result_stream,
@@ -90,8 +90,8 @@ pub fn differential_run_basic<
 
 fn insert_edges<A: Allocate, C: Computation, T: Timestamp + Display + Copy>(
     edges: &CubeDiffIterators<T>,
-    edge_input: &mut UnorderedHandle<T, (SimpleEdge, T, DiffCount)>,
-    mut edge_cap: ActivateCapability<T>,
+    inputs: &mut InputHandles<T>,
+    mut input_cap: InputCapability<T>,
     worker: &mut Worker<A>,
     probe: &Handle<T>,
     result_stream: &Option<ResultStream<C::Result, T>>,
@@ -104,14 +104,14 @@ fn insert_edges<A: Allocate, C: Computation, T: Timestamp + Display + Copy>(
     match edges {
         CubeDiffIterators::Outer(outer) => {
             for (next_timestamp, row) in outer.iter() {
-                let edge_cap_next = edge_cap.delayed(next_timestamp);
+                let input_cap_next = input_cap.delayed(next_timestamp);
                 if worker_index == 0 {
                     info!("Preparing for {}", next_timestamp);
                 }
                 insert_edges::<_, C, T>(
                     row,
-                    edge_input,
-                    edge_cap,
+                    inputs,
+                    input_cap,
                     worker,
                     probe,
                     result_stream,
@@ -121,30 +121,36 @@ fn insert_edges<A: Allocate, C: Computation, T: Timestamp + Display + Copy>(
                     materialize_results,
                     all_times,
                 );
-                edge_cap = edge_cap_next;
+                input_cap = input_cap_next;
             }
         }
         CubeDiffIterators::Inner(inner) => {
-            for (current_timestamp, next_timestamp, diff) in inner.iter() {
+            for (current_timestamp, next_timestamp, diff, vertex_diff) in inner.iter() {
                 info!("[worker {:>2}] running {}", worker_index, current_timestamp);
                 if worker_index == 0 {
                     print_memory_usage(format_args!("running {}", current_timestamp));
                 }
-                let (left_index, right_index) =
-                    get_worker_indices(diff.len(), worker_index, worker_count);
                 let timer = GsTimer::now();
-                {
-                    let mut session = edge_input.session(edge_cap.clone());
-                    for (edge, change) in &diff[left_index..right_index] {
-                        session.give((*edge, *current_timestamp, *change));
-                    }
-                }
+                inputs.give_vertices(
+                    &input_cap,
+                    vertex_diff,
+                    *current_timestamp,
+                    worker_index,
+                    worker_count,
+                );
+                let diffs = inputs.give_edges(
+                    &input_cap,
+                    diff,
+                    None,
+                    *current_timestamp,
+                    worker_index,
+                    worker_count,
+                );
                 let loaded = timer.elapsed();
                 info!(
-                    "[worker {:>2}] loaded {} diffs [{:?}] at timestamp {} in {}",
+                    "[worker {:>2}] loaded {} diffs at timestamp {} in {}",
                     worker_index,
-                    right_index - left_index,
-                    diff[left_index],
+                    diffs,
                     current_timestamp,
                     loaded.seconds_string()
                 );
@@ -153,8 +159,8 @@ fn insert_edges<A: Allocate, C: Computation, T: Timestamp + Display + Copy>(
                     info!("Downgrading to {}", next_timestamp);
                 }
                 let timer2 = GsTimer::now();
-                edge_cap.downgrade(&next_timestamp);
-                while probe.less_than(edge_cap.time()) {
+                input_cap.downgrade(next_timestamp);
+                while probe.less_than(input_cap.time()) {
                     worker.step();
                 }
                 let stable = timer2.elapsed();
@@ -53,7 +53,6 @@ mod tests {
     use crate::computations::filtered_cubes::execute::get_results_stash;
     use itertools::Itertools;
 
-    #[cfg(not(feature = "nd-timestamps"))]
     #[test]
     fn test_1d() {
         let edge = (100, vec![vec![0, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1]]);
@@ -74,7 +73,6 @@ mod tests {
         assert_eq!(diffs, expected_diffs);
     }
 
-    #[cfg(feature = "nd-timestamps")]
     #[test]
     fn test_3d() {
         let edge = (
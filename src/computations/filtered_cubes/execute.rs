@@ -1,9 +1,13 @@
-use crate::computations::filtered_cubes::filter_matrix::FilteredMatrix;
+use crate::computations::filtered_cubes::filter_matrix::{
+    get_dimension_filters, DimensionFilter, FilteredMatrix,
+};
 use crate::computations::filtered_cubes::matrix_operation::MatrixOperation;
-use crate::computations::filtered_cubes::optimal_orders::OptimalOrder;
+use crate::computations::filtered_cubes::optimal_orders::{get_optimal_order, OptimalOrder};
 use crate::computations::filtered_cubes::process_edge_diff::EdgeDiff;
 use crate::computations::filtered_cubes::reduce_matrices::ReduceMatrices;
-use crate::computations::filtered_cubes::{DiffProcessingData, DimensionOrder};
+use crate::computations::filtered_cubes::{
+    DiffProcessingData, DimensionOrder, DimensionOrders, FilteredMatrixStream, Matrices,
+};
 use crate::computations::views::monitor::MonitorStream;
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::{
@@ -11,17 +15,24 @@ use crate::filtered_cubes::timestamp::timestamp_mappings::{
 };
 use crate::filtered_cubes::timestamp::GSTimestamp;
 use crate::filtered_cubes::{DimensionLength, DimensionLengths};
-use crate::graph::stream_data::get_timely_edgeid_stream;
+use crate::graph::indexes::IndexStore;
+use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::PropertyKeyId;
+use crate::graph::stream_data::{get_timely_edgeid_stream, get_worker_indices};
 use crate::graph::Graph;
 use crate::graph::GraphPointer;
 use crate::query_handler::create_filtered_cube::Dimension;
+use crate::query_handler::create_view::PropertyName;
 use crate::util::memory_usage::print_memory_usage;
 use crossbeam_utils::thread;
 use hashbrown::HashMap;
 use itertools::Itertools;
 use log::info;
 
-use gs_analytics_api::CubeDataEntries;
+use gs_analytics_api::{
+    CubeDataEntries, EdgeId, EdgePayload, EntryPayloads, TimelyTimeStamp, VertexId,
+};
+use std::convert::TryFrom;
 use timely::dataflow::operators::broadcast::Broadcast;
 use timely::dataflow::operators::capture::capture::Capture;
 use timely::dataflow::operators::capture::event::Event::Messages;
@@ -32,35 +43,45 @@ use timely::dataflow::operators::inspect::Inspect;
 use timely::dataflow::operators::map::Map;
 use timely::dataflow::operators::probe::Probe;
 use timely::dataflow::operators::to_stream::ToStream;
-use timely::dataflow::{InputHandle, ProbeHandle};
+use timely::dataflow::{InputHandle, ProbeHandle, Scope, Stream};
 use timely::Configuration;
 
+/// The entries of the collection, the payloads of their edges, and the order of its views.
+pub type ExecuteResults = (Vec<CubeDataEntries<GSTimestamp>>, Vec<EntryPayloads>, DimensionOrders);
+
 pub fn execute(
     dimensions: Vec<Dimension>,
+    vertex_dimensions: Vec<Dimension>,
     dimension_lengths: DimensionLengths,
     graph: &Graph,
+    index_store: &IndexStore,
+    edge_properties: &[(PropertyName, PropertyKeyId)],
     manual_order: bool,
     store_total_data: bool,
     timestamp_mappings: &TimestampMappings,
     threads_per_process: usize,
     process_id: usize,
     hosts: &[String],
-) -> Result<Vec<CubeDataEntries<GSTimestamp>>, GSError> {
+) -> Result<ExecuteResults, GSError> {
     let graph_pointer = GraphPointer::new(&graph);
+    let config = get_timely_config(threads_per_process, process_id, hosts);
 
-    let config = if hosts.len() > 1 {
-        let c_hosts = hosts.to_vec();
-        info!("Process {} w/ {} threads, hosts = {:?}", process_id, threads_per_process, c_hosts);
-        Configuration::Cluster {
-            threads: threads_per_process,
-            process: process_id,
-            addresses: c_hosts,
-            report: false,
-            log_fn: Box::new(|_| None),
+    let views_count: usize = dimensions.iter().map(Vec::len).sum();
+    let dimension_filters = get_dimension_filters(dimensions, graph, index_store);
+    let mut threshold_dimensions_count = 0;
+    let mut indexed_views_count = 0;
+    for dimension_filter in &dimension_filters {
+        match dimension_filter {
+            DimensionFilter::Views(views) => {
+                indexed_views_count += views.iter().filter(|view| view.uses_indexes()).count();
+            }
+            DimensionFilter::Thresholds(_) => threshold_dimensions_count += 1,
         }
-    } else {
-        Configuration::Process(threads_per_process)
-    };
+    }
+    info!(
+        "{} of {} views use indexes, {} dimensions use shared thresholds",
+        indexed_views_count, views_count, threshold_dimensions_count
+    );
 
     info!("Starting execution for new filtered cube...");
     print_memory_usage(format_args!("starting differential workers"));
@@ -68,44 +89,32 @@ pub fn execute(
         let timer = std::time::Instant::now();
 
         let mut edge_input = InputHandle::new();
+        let mut vertex_input = InputHandle::new();
         let mut probe = ProbeHandle::new();
         let worker_index = worker.index();
         let worker_count = worker.peers();
 
-        let (filtered_edge_stream, order_stream) = worker.dataflow(|scope| {
-            let edge_stream = edge_input.to_stream(scope);
+        let (filtered_edge_stream, filtered_vertex_stream, order_stream) =
+            worker.dataflow(|scope| {
+                let edge_stream = edge_input.to_stream(scope);
+                let filtered_vertex_stream = vertex_input
+                    .to_stream(scope)
+                    .vertex_filtered_matrix(vertex_dimensions.clone(), graph_pointer);
 
-            let filtered_matrix_stream = edge_stream
-                .filtered_matrix(dimensions.clone(), graph_pointer)
-                .monitor(500_000, "filtered_edge_stream", worker_index);
+                let filtered_matrix_stream = edge_stream
+                    .filtered_matrix(dimension_filters.clone(), graph_pointer)
+                    .monitor(500_000, "filtered_edge_stream", worker_index);
 
-            let order_stream =
-                if manual_order || dimension_lengths.iter().all(|&length| length < 3) {
-                    // If requested or all lengths are less than 3, order can be simply
-                    // specified in order instead of a costly computation.
-                    //
-                    // Only do this for worker 0 to mirror the reduce operation in the else
-                    // case that generates the final orders.
-                    if worker_index == 0 {
-                        vec![dimension_lengths
-                            .iter()
-                            .map(|&length| (1..=length).collect_vec())
-                            .collect()]
-                    } else {
-                        vec![]
-                    }
+                let order_stream = if is_manual_order(manual_order, &dimension_lengths) {
+                // Only do this for worker 0 to mirror the reduce operation in the else
+                // case that generates the final orders.
+                if worker_index == 0 { vec![get_manual_orders(&dimension_lengths)] } else { vec![] }
                     .into_iter()
                     .to_stream(scope)
-                } else {
-                    // Add 1 to all dimension lengths to account for the extra 0 added to all edges.
-                    let dimension_lengths_plus_one =
-                        dimension_lengths.iter().map(|length| length + 1).collect_vec();
-                    filtered_matrix_stream
-                            .create_ordering_matrices(dimension_lengths_plus_one.clone(),worker_index)
-                            .exchange(|_| 0_u64) // Send all matrices to worker 0.
-                            .reduce_matrices(dimension_lengths_plus_one,worker_index)
-                            .generate_optimal_orders()
-                }
+            } else {
+                reduce_ordering_matrices(&filtered_matrix_stream, &dimension_lengths, worker_index)
+                    .generate_optimal_orders()
+            }
                 .flat_map(|orders| orders.into_iter().enumerate())
                 .inspect(move |(index, order)| {
                     if worker_index == 0 {
@@ -114,16 +123,27 @@ pub fn execute(
                 })
                 // Send all matrices to worker 0.
                 .broadcast();
-            filtered_matrix_stream.probe_with(&mut probe);
-            order_stream.probe_with(&mut probe);
+                filtered_matrix_stream.probe_with(&mut probe);
+                filtered_vertex_stream.probe_with(&mut probe);
+                order_stream.probe_with(&mut probe);
 
-            (filtered_matrix_stream.capture(), order_stream.capture())
-        });
+                (
+                    filtered_matrix_stream.capture(),
+                    filtered_vertex_stream.capture(),
+                    order_stream.capture(),
+                )
+            });
 
-        for edgeid in get_timely_edgeid_stream(&*graph_pointer, worker_index, worker_count) {
-            edge_input.send(edgeid);
+        send_edges(edge_input, &graph_pointer, worker_index, worker_count);
+        // Vertices are only filtered if the collection has vertex views.
+        if !vertex_dimensions.is_empty() {
+            let (left_index, right_index) =
+                get_worker_indices(graph_pointer.vertex_count(), worker_index, worker_count);
+            for vertex_id in left_index..right_index {
+                vertex_input.send(VertexId::try_from(vertex_id).expect("Overflow"));
+            }
         }
-        edge_input.close();
+        vertex_input.close();
         if worker_index == 0 {
             print_memory_usage(format_args!("loaded edges"));
         }
@@ -133,11 +153,15 @@ pub fn execute(
         }
 
         if worker_index == 0 {
-            info!("Done on worker {:>2} in {:?}. Processing diffs...", worker_index, timer.elapsed());
+            info!(
+                "Done on worker {:>2} in {:?}. Processing diffs...",
+                worker_index,
+                timer.elapsed()
+            );
             print_memory_usage(format_args!("processed edges"));
         }
 
-        let mut orders = (0..dimensions.len()).map(|_| Vec::new()).collect_vec();
+        let mut orders = (0..dimension_filters.len()).map(|_| Vec::new()).collect_vec();
         for (index, mut order) in order_stream.into_iter().flat_map(fnn) {
             std::mem::swap(&mut orders[index], &mut order);
         }
@@ -147,19 +171,26 @@ pub fn execute(
 
         let mut probe = ProbeHandle::new();
 
-        let output_stream = worker.dataflow(|scope| {
+        let (output_stream, vertex_output_stream) = worker.dataflow(|scope| {
+            let exchange = move |_: &_| {
+                if threads_per_process == 1 {
+                    0
+                } else {
+                    (worker_index % threads_per_process) as u64
+                }
+            };
             let edge_stream = filtered_edge_stream.into_iter().flat_map(fnn).to_stream(scope);
             let output_stream = edge_stream
-                .process_edge(orders, dimension_lengths.clone(), store_total_data)
-                .exchange(move |_| {
-                    if threads_per_process == 1 {
-                        0
-                    } else {
-                        (worker_index % threads_per_process) as u64
-                    }
-                })
+                .process_edge(orders.clone(), dimension_lengths.clone(), store_total_data)
+                .exchange(exchange)
                 .probe_with(&mut probe);
-            output_stream.capture()
+            // Vertex diffs follow the same orders as the edge diffs.
+            let vertex_stream = filtered_vertex_stream.into_iter().flat_map(fnn).to_stream(scope);
+            let vertex_output_stream = vertex_stream
+                .process_edge(orders.clone(), dimension_lengths.clone(), store_total_data)
+                .exchange(exchange)
+                .probe_with(&mut probe);
+            (output_stream.capture(), vertex_output_stream.capture())
         });
         if worker_index == 0 {
             print_memory_usage(format_args!("processing diffs"));
@@ -172,26 +203,37 @@ pub fn execute(
             print_memory_usage(format_args!("done with diffs"));
         }
 
-        output_stream.into_iter().flat_map(fnn).collect_vec()
+        (
+            output_stream.into_iter().flat_map(fnn).collect_vec(),
+            vertex_output_stream.into_iter().flat_map(fnn).collect_vec(),
+            orders,
+        )
     })
-        .map_err(GSError::Timely)?;
+    .map_err(GSError::Timely)?;
     let worker_results = worker_threads.join();
     print_memory_usage(format_args!("done with timely"));
 
     let mut full_results = HashMap::new();
+    let mut vertex_results = HashMap::new();
+    // The orders are broadcast, so every worker has all of them.
+    let mut final_orders = Vec::new();
     let edges = &graph.edges();
     for result in worker_results {
-        let maps = result.map_err(GSError::TimelyResults)?;
-        for map in maps {
-            for (key, values) in map {
-                let entry = full_results.entry(key).or_insert_with(|| (Vec::new(), Vec::new()));
-                entry.0.extend(values.0);
-                entry.1.extend(values.1);
+        let (maps, vertex_maps, orders) = result.map_err(GSError::TimelyResults)?;
+        final_orders = orders;
+        for (results, maps) in vec![(&mut full_results, maps), (&mut vertex_results, vertex_maps)] {
+            for map in maps {
+                for (key, values) in map {
+                    let entry = results.entry(key).or_insert_with(|| (Vec::new(), Vec::new()));
+                    entry.0.extend(values.0);
+                    entry.1.extend(values.1);
+                }
             }
         }
     }
 
     let mut final_results = Vec::new();
+    let mut final_payloads = Vec::new();
     // The sorted ordering from `timestamp_mappings` should be maintained in `final_results` as well.
     for timestamps in &timestamp_mappings.0.iter().enumerate().chunks(threads_per_process) {
         thread::scope(|s| {
@@ -199,7 +241,25 @@ pub fn execute(
             for (timestamp_index, (_, timestamp)) in timestamps {
                 let data =
                     full_results.remove(&timestamp_index).unwrap_or((Vec::new(), Vec::new()));
+                let vertex_data =
+                    vertex_results.remove(&timestamp_index).unwrap_or((Vec::new(), Vec::new()));
                 let thread = s.spawn(move |_| {
+                    let payloads = if edge_properties.is_empty() {
+                        (Vec::new(), Vec::new())
+                    } else {
+                        (
+                            data.0
+                                .iter()
+                                .map(|&edge_id| get_edge_payload(graph, edge_id, edge_properties))
+                                .collect::<Result<_, _>>()?,
+                            data.1
+                                .iter()
+                                .map(|&(edge_id, _)| {
+                                    get_edge_payload(graph, edge_id, edge_properties)
+                                })
+                                .collect::<Result<_, _>>()?,
+                        )
+                    };
                     let full_data = data
                         .0
                         .into_iter()
@@ -223,19 +283,179 @@ pub fn execute(
                             ((edge.src_vertex_id, edge.dst_vertex_id), diff)
                         })
                         .collect_vec();
-                    (timestamp_index, *timestamp, (full_data, diff_data), (additions, deletions))
+                    Ok((
+                        (
+                            timestamp_index,
+                            *timestamp,
+                            (full_data, diff_data),
+                            (additions, deletions),
+                            vertex_data,
+                        ),
+                        payloads,
+                    ))
                 });
                 threads.push(thread);
             }
             for thread in threads.drain(..) {
-                final_results
-                    .push(thread.join().unwrap_or_else(|_| panic!("Error joining thread")));
+                let (entry, payloads) =
+                    thread.join().unwrap_or_else(|_| panic!("Error joining thread"))?;
+                final_results.push(entry);
+                final_payloads.push(payloads);
             }
+            Ok::<_, GSError>(())
         })
-        .expect("Error mapping results");
+        .expect("Error mapping results")?;
     }
+    if edge_properties.is_empty() {
+        final_payloads.clear();
+    }
+
+    Ok((final_results, final_payloads, final_orders))
+}
 
-    Ok(final_results)
+/// Returns the values of `edge_properties` for the edge, which must all be integers.
+fn get_edge_payload(
+    graph: &Graph,
+    edge_id: EdgeId,
+    edge_properties: &[(PropertyName, PropertyKeyId)],
+) -> Result<EdgePayload, GSError> {
+    let edge_id_pv = PropertyValue::get_id(edge_id);
+    edge_properties
+        .iter()
+        .map(|(property, key_id)| {
+            match graph.get_edge_id_property_value(edge_id, &edge_id_pv, *key_id) {
+                Some(value) => value.as_isize(),
+                None => None,
+            }
+            .ok_or_else(|| {
+                GSError::Collection(format!(
+                    "Edge {} does not have an integer value for property '{}'",
+                    edge_id, property
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Runs only the filter matrix and ordering stages of `execute`, returning the order of the views
+/// of each dimension and the matrices the order was computed from.
+pub fn estimate_orders(
+    dimensions: Vec<Dimension>,
+    dimension_lengths: &[DimensionLength],
+    graph: &Graph,
+    index_store: &IndexStore,
+    manual_order: bool,
+    threads_per_process: usize,
+    process_id: usize,
+    hosts: &[String],
+) -> Result<(DimensionOrders, Matrices), GSError> {
+    let graph_pointer = GraphPointer::new(graph);
+    let config = get_timely_config(threads_per_process, process_id, hosts);
+    let dimension_filters = get_dimension_filters(dimensions, graph, index_store);
+    let worker_dimension_lengths = dimension_lengths.to_vec();
+
+    let worker_threads = timely::execute(config, move |worker| {
+        let worker_index = worker.index();
+        let worker_count = worker.peers();
+        let mut edge_input = InputHandle::new();
+        let mut probe = ProbeHandle::new();
+
+        let matrices_stream = worker.dataflow(|scope| {
+            let filtered_matrix_stream = edge_input
+                .to_stream(scope)
+                .filtered_matrix(dimension_filters.clone(), graph_pointer);
+            reduce_ordering_matrices(
+                &filtered_matrix_stream,
+                &worker_dimension_lengths,
+                worker_index,
+            )
+            // Send the reduced matrices to all workers, so that every process has them.
+            .broadcast()
+            .probe_with(&mut probe)
+            .capture()
+        });
+
+        send_edges(edge_input, &graph_pointer, worker_index, worker_count);
+        while !probe.done() {
+            worker.step();
+        }
+
+        matrices_stream.into_iter().flat_map(fnn).collect_vec()
+    })
+    .map_err(GSError::Timely)?;
+
+    let mut matrices = Vec::new();
+    for result in worker_threads.join() {
+        if let Some(worker_matrices) = result.map_err(GSError::TimelyResults)?.pop() {
+            matrices = worker_matrices;
+        }
+    }
+    let orders = if is_manual_order(manual_order, dimension_lengths) {
+        get_manual_orders(dimension_lengths)
+    } else {
+        get_optimal_order(&matrices)
+    };
+    Ok((orders, matrices))
+}
+
+/// Returns the configuration of the workers of this process, which are part of a cluster if more
+/// than one host is given.
+fn get_timely_config(
+    threads_per_process: usize,
+    process_id: usize,
+    hosts: &[String],
+) -> Configuration {
+    if hosts.len() > 1 {
+        let c_hosts = hosts.to_vec();
+        info!("Process {} w/ {} threads, hosts = {:?}", process_id, threads_per_process, c_hosts);
+        Configuration::Cluster {
+            threads: threads_per_process,
+            process: process_id,
+            addresses: c_hosts,
+            report: false,
+            log_fn: Box::new(|_| None),
+        }
+    } else {
+        Configuration::Process(threads_per_process)
+    }
+}
+
+fn send_edges(
+    mut edge_input: InputHandle<TimelyTimeStamp, EdgeId>,
+    graph: &Graph,
+    worker_index: usize,
+    worker_count: usize,
+) {
+    for edgeid in get_timely_edgeid_stream(graph, worker_index, worker_count) {
+        edge_input.send(edgeid);
+    }
+    edge_input.close();
+}
+
+/// If requested or all lengths are less than 3, the views can be simply ordered as they are
+/// specified instead of with a costly computation.
+fn is_manual_order(manual_order: bool, dimension_lengths: &[DimensionLength]) -> bool {
+    manual_order || dimension_lengths.iter().all(|&length| length < 3)
+}
+
+fn get_manual_orders(dimension_lengths: &[DimensionLength]) -> DimensionOrders {
+    dimension_lengths.iter().map(|&length| (1..=length).collect_vec()).collect()
+}
+
+/// Creates the ordering matrices of the views of each dimension from the filtered edges, and
+/// reduces them on worker 0.
+fn reduce_ordering_matrices<S: Scope<Timestamp = TimelyTimeStamp>>(
+    filtered_matrix_stream: &Stream<S, FilteredMatrixStream>,
+    dimension_lengths: &[DimensionLength],
+    worker_index: usize,
+) -> Stream<S, Matrices> {
+    // Add 1 to all dimension lengths to account for the extra 0 added to all edges.
+    let dimension_lengths_plus_one =
+        dimension_lengths.iter().map(|length| length + 1).collect_vec();
+    filtered_matrix_stream
+        .create_ordering_matrices(dimension_lengths_plus_one.clone(), worker_index)
+        .exchange(|_| 0_u64) // Send all matrices to worker 0.
+        .reduce_matrices(dimension_lengths_plus_one, worker_index)
 }
 
 fn fnn<T, D>(r: Event<T, D>) -> impl Iterator<Item = D> {
@@ -1,10 +1,18 @@
 use crate::computations::filtered_cubes::{FilteredMatrixRow, FilteredMatrixStream};
+use crate::graph::indexes::IndexStore;
+use crate::graph::properties::operations::{
+    LeftOperand, Operand, Operator as PropertyOperator, RightOperand,
+};
+use crate::graph::properties::property_value::PropertyValue;
 use crate::graph::stream_data::filter::test_where_conditions;
-use crate::graph::GraphPointer;
+use crate::graph::{Graph, GraphPointer, VertexOrEdge};
 use crate::query_handler::create_filtered_cube::Dimension;
+use crate::query_handler::create_view::{WhereCondition, WhereConditions};
+use crate::util::bitmap::Bitmap;
 use gs_analytics_api::{EdgeId, TimelyTimeStamp};
 use itertools::Itertools;
 use std::iter;
+use std::sync::Arc;
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::generic::operator::Operator;
 use timely::dataflow::{Scope, Stream};
@@ -12,7 +20,14 @@ use timely::dataflow::{Scope, Stream};
 pub trait FilteredMatrix<S: Scope<Timestamp = TimelyTimeStamp>> {
     fn filtered_matrix(
         &self,
-        dimensions: Vec<Dimension>,
+        dimensions: Vec<DimensionFilter>,
+        graph_pointer: GraphPointer,
+    ) -> Stream<S, FilteredMatrixStream>;
+
+    /// Same as `filtered_matrix`, for a stream of vertex ids and the vertex views of a collection.
+    fn vertex_filtered_matrix(
+        &self,
+        vertex_dimensions: Vec<Dimension>,
         graph_pointer: GraphPointer,
     ) -> Stream<S, FilteredMatrixStream>;
 }
@@ -20,7 +35,7 @@ pub trait FilteredMatrix<S: Scope<Timestamp = TimelyTimeStamp>> {
 impl<S: Scope<Timestamp = TimelyTimeStamp>> FilteredMatrix<S> for Stream<S, EdgeId> {
     fn filtered_matrix(
         &self,
-        dimensions: Vec<Dimension>,
+        dimensions: Vec<DimensionFilter>,
         graph_pointer: GraphPointer,
     ) -> Stream<S, FilteredMatrixStream> {
         self.unary(Pipeline, "FilterMatrix", move |_, _| {
@@ -37,25 +52,297 @@ impl<S: Scope<Timestamp = TimelyTimeStamp>> FilteredMatrix<S> for Stream<S, Edge
             }
         })
     }
+
+    fn vertex_filtered_matrix(
+        &self,
+        vertex_dimensions: Vec<Dimension>,
+        graph_pointer: GraphPointer,
+    ) -> Stream<S, FilteredMatrixStream> {
+        self.unary(Pipeline, "VertexFilterMatrix", move |_, _| {
+            move |input, output| {
+                input.for_each(|time, vertices| {
+                    let mut session = output.session(&time);
+                    vertices.iter().for_each(|&vertex_id| {
+                        let matrix = vertex_dimensions
+                            .iter()
+                            .map(|dimension| {
+                                iter::once(0)
+                                    .chain(dimension.iter().map(|where_conditions| {
+                                        if test_where_conditions(
+                                            vertex_id,
+                                            where_conditions,
+                                            graph_pointer,
+                                        ) {
+                                            1
+                                        } else {
+                                            0
+                                        }
+                                    }))
+                                    .collect_vec()
+                            })
+                            .collect();
+                        session.give((vertex_id, matrix));
+                    });
+                });
+            }
+        })
+    }
 }
 
 fn create_filtered_matrix(
     edge_id: EdgeId,
-    dimensions: &[Dimension],
+    dimensions: &[DimensionFilter],
     graph_pointer: GraphPointer,
 ) -> Vec<FilteredMatrixRow> {
     dimensions
         .iter()
+        .map(|dimension| match dimension {
+            DimensionFilter::Views(views) => iter::once(0)
+                .chain(views.iter().map(
+                    |view| {
+                        if view.test(edge_id, graph_pointer) {
+                            1
+                        } else {
+                            0
+                        }
+                    },
+                ))
+                .collect_vec(),
+            DimensionFilter::Thresholds(thresholds) => {
+                let mut row = vec![0; thresholds.view_indices.len() + 1];
+                for &view_index in thresholds.matching_views(edge_id, graph_pointer) {
+                    row[view_index + 1] = 1;
+                }
+                row
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// How the views of a dimension are evaluated for each edge.
+#[derive(Clone)]
+pub enum DimensionFilter {
+    Views(Vec<IndexedView>),
+    Thresholds(ThresholdDimension),
+}
+
+/// A dimension whose views each compare the same operand to a different threshold, using the same
+/// ordering operator, e.g., `[year <= 2000], [year <= 2005], [year <= 2010]`. The views matching an
+/// edge are then a prefix or a suffix of the views ordered by threshold, so the operand is read
+/// once per edge and the thresholds are binary searched.
+#[derive(Clone)]
+pub struct ThresholdDimension {
+    operand: Operand,
+    operator: PropertyOperator,
+    /// Sorted in ascending order.
+    thresholds: Vec<PropertyValue>,
+    /// The index of the view of each threshold.
+    view_indices: Vec<usize>,
+}
+
+impl ThresholdDimension {
+    /// Returns the dimension if every view is a single comparison of the same property with a
+    /// value of the same type, using the same ordering operator.
+    fn new(dimension: &[WhereConditions]) -> Option<Self> {
+        let mut operand_and_operator = None;
+        let mut thresholds = Vec::new();
+        for where_conditions in dimension {
+            let (operand, operator, threshold) = match where_conditions.as_slice() {
+                [WhereCondition::Predicate((
+                    (
+                        LeftOperand::Operand(
+                            operand @ (Operand::Property(_)
+                            | Operand::SourceVertex(_)
+                            | Operand::DestinationVertex(_)),
+                        ),
+                        operator @ (PropertyOperator::Less
+                        | PropertyOperator::LessEqual
+                        | PropertyOperator::Greater
+                        | PropertyOperator::GreaterEqual),
+                        RightOperand::Value(threshold),
+                    ),
+                    _,
+                ))] => (*operand, *operator, threshold),
+                _ => return None,
+            };
+            match operand_and_operator {
+                None => operand_and_operator = Some((operand, operator)),
+                Some(first) if first != (operand, operator) => return None,
+                Some(_) => {}
+            }
+            thresholds.push(threshold.clone());
+        }
+        let (operand, operator) = operand_and_operator?;
+        // Values of one type are totally ordered in the same way `PropertyValue::compare`
+        // compares them with any other value.
+        if thresholds.iter().map(PropertyValue::value_type).unique().count() != 1 {
+            return None;
+        }
+        let (view_indices, thresholds) = thresholds
+            .into_iter()
+            .enumerate()
+            .sorted_by(|(_, threshold1), (_, threshold2)| threshold1.cmp(threshold2))
+            .unzip();
+        Some(Self { operand, operator, thresholds, view_indices })
+    }
+
+    fn matching_views(&self, edge_id: EdgeId, graph_pointer: GraphPointer) -> &[usize] {
+        let graph = &*graph_pointer;
+        let edge = &graph.edges()[edge_id as usize];
+        let pv = match self.operand {
+            Operand::SourceVertex(_) => PropertyValue::get_id(edge.src_vertex_id),
+            Operand::DestinationVertex(_) => PropertyValue::get_id(edge.dst_vertex_id),
+            _ => PropertyValue::get_id(edge_id),
+        };
+        let value = match self.operand {
+            Operand::SourceVertex(key_id) => {
+                graph.get_vertex_id_property_value(edge.src_vertex_id, &pv, key_id)
+            }
+            Operand::DestinationVertex(key_id) => {
+                graph.get_vertex_id_property_value(edge.dst_vertex_id, &pv, key_id)
+            }
+            Operand::Property(key_id) => graph.get_edge_id_property_value(edge_id, &pv, key_id),
+            Operand::Edge => unreachable!("Threshold dimensions compare properties"),
+        };
+        let value = match value {
+            Some(value) => value,
+            // Comparisons with missing properties are unknown, so no view matches.
+            None => return &[],
+        };
+        match self.operator {
+            // Holds for the thresholds above the value.
+            PropertyOperator::Less | PropertyOperator::LessEqual => {
+                let start = self
+                    .thresholds
+                    .partition_point(|threshold| !value.compare(threshold, self.operator));
+                &self.view_indices[start..]
+            }
+            // Holds for the thresholds below the value.
+            _ => {
+                let end = self
+                    .thresholds
+                    .partition_point(|threshold| value.compare(threshold, self.operator));
+                &self.view_indices[..end]
+            }
+        }
+    }
+}
+
+/// The where conditions of a view, split into the edges matching the conditions that property
+/// indexes can answer, and the remaining conditions that are tested for each edge.
+#[derive(Clone)]
+pub struct IndexedView {
+    /// `None` if no condition could be answered by an index.
+    matching_edges: Option<Arc<Bitmap>>,
+    where_conditions: WhereConditions,
+}
+
+impl IndexedView {
+    pub fn uses_indexes(&self) -> bool {
+        self.matching_edges.is_some()
+    }
+
+    #[inline(always)]
+    fn test(&self, edge_id: EdgeId, graph_pointer: GraphPointer) -> bool {
+        self.matching_edges.as_ref().map_or(true, |edges| edges.get(edge_id as usize))
+            && test_where_conditions(edge_id, &self.where_conditions, graph_pointer)
+    }
+}
+
+/// Uses a `ThresholdDimension` for the dimensions with more than one view that allow it. For the
+/// other dimensions, answers the top-level predicates of each view using the indexes in
+/// `index_store`, where possible. A conjunction only holds if each of its conditions does, so the
+/// answered conditions can be replaced by the set of edges they hold for.
+pub fn get_dimension_filters(
+    dimensions: Vec<Dimension>,
+    graph: &Graph,
+    index_store: &IndexStore,
+) -> Vec<DimensionFilter> {
+    dimensions
+        .into_iter()
         .map(|dimension| {
-            iter::once(0)
-                .chain(dimension.iter().map(|conditions| {
-                    if test_where_conditions(edge_id, &conditions, graph_pointer) {
-                        1
+            if dimension.len() > 1 {
+                if let Some(thresholds) = ThresholdDimension::new(&dimension) {
+                    return DimensionFilter::Thresholds(thresholds);
+                }
+            }
+            DimensionFilter::Views(
+                dimension
+                    .into_iter()
+                    .map(|where_conditions| get_indexed_view(where_conditions, graph, index_store))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+fn get_indexed_view(
+    where_conditions: WhereConditions,
+    graph: &Graph,
+    index_store: &IndexStore,
+) -> IndexedView {
+    let mut matching_edges: Option<Bitmap> = None;
+    let mut remaining_conditions = Vec::new();
+    for where_condition in where_conditions {
+        match get_matching_edges(&where_condition, graph, index_store) {
+            Some(edges) => match &mut matching_edges {
+                Some(matching_edges) => matching_edges.intersect_with(&edges),
+                None => matching_edges = Some(edges),
+            },
+            None => remaining_conditions.push(where_condition),
+        }
+    }
+    IndexedView {
+        matching_edges: matching_edges.map(Arc::new),
+        where_conditions: remaining_conditions,
+    }
+}
+
+/// Returns the edges for which the condition holds, or `None` if no index can answer it.
+fn get_matching_edges(
+    where_condition: &WhereCondition,
+    graph: &Graph,
+    index_store: &IndexStore,
+) -> Option<Bitmap> {
+    let ((left_operand, operator, right_operand), _) = match where_condition {
+        WhereCondition::Predicate(predicate) => predicate,
+        _ => return None,
+    };
+    if !matches!(right_operand, RightOperand::Value(_) | RightOperand::List(_)) {
+        return None;
+    }
+    let edges = graph.edges();
+    match left_operand {
+        LeftOperand::Operand(Operand::Property(key_id)) => {
+            let edge_ids =
+                index_store.get(VertexOrEdge::Edge, *key_id)?.lookup(*operator, right_operand)?;
+            Some(Bitmap::from_indices(
+                edges.len(),
+                edge_ids.into_iter().map(|edge_id| edge_id as usize),
+            ))
+        }
+        LeftOperand::Operand(
+            Operand::SourceVertex(key_id) | Operand::DestinationVertex(key_id),
+        ) => {
+            let vertex_ids =
+                index_store.get(VertexOrEdge::Vertex, *key_id)?.lookup(*operator, right_operand)?;
+            let vertices = Bitmap::from_indices(
+                graph.vertex_count(),
+                vertex_ids.into_iter().map(|vertex_id| vertex_id as usize),
+            );
+            let is_source = matches!(left_operand, LeftOperand::Operand(Operand::SourceVertex(_)));
+            Some(Bitmap::from_indices(
+                edges.len(),
+                edges.iter().enumerate().filter_map(|(edge_id, edge)| {
+                    let vertex_id = if is_source { edge.src_vertex_id } else { edge.dst_vertex_id };
+                    if vertices.get(vertex_id as usize) {
+                        Some(edge_id)
                     } else {
-                        0
+                        None
                     }
-                }))
-                .collect_vec()
-        })
-        .collect::<Vec<_>>()
+                }),
+            ))
+        }
+        _ => None,
+    }
 }
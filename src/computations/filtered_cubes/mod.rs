@@ -7,7 +7,7 @@ pub mod edge_diff;
 pub mod execute;
 mod filter_matrix;
 mod matrix_operation;
-mod optimal_orders;
+pub mod optimal_orders;
 mod process_edge_diff;
 mod reduce_matrices;
 mod tsp;
@@ -16,10 +16,13 @@ type Bit = u8;
 type FilteredMatrixRow = Vec<Bit>;
 type FilteredMatrixStream = (EdgeId, Vec<FilteredMatrixRow>);
 type MatrixRow = Vec<usize>;
-type Matrix = Vec<MatrixRow>;
-type Matrices = Vec<Matrix>;
-type DimensionOrders = Vec<DimensionOrder>;
-type DimensionOrder = Vec<DimensionId>;
+/// The number of edges whose membership differs between each pair of views of a dimension, with
+/// the empty view at index 0.
+pub type Matrix = Vec<MatrixRow>;
+pub type Matrices = Vec<Matrix>;
+/// The order of the views of each dimension, given by the 1-based indices of the views.
+pub type DimensionOrders = Vec<DimensionOrder>;
+pub type DimensionOrder = Vec<DimensionId>;
 pub struct DiffProcessingData {
     pub timestamp_index: usize,
     pub diff_neighborhood: DiffNeighborhood,
@@ -1,7 +1,11 @@
 use crate::computations::filtered_cubes::tsp::tsp;
-use crate::computations::filtered_cubes::{DimensionOrders, Matrices, Matrix};
+use crate::computations::filtered_cubes::{
+    DimensionOrder, DimensionOrders, Matrices, Matrix, MatrixRow,
+};
+use crate::filtered_cubes::timestamp::DimensionId;
 use gs_analytics_api::TimelyTimeStamp;
 use itertools::Itertools;
+use std::convert::TryFrom;
 use timely::dataflow::operators::map::Map;
 use timely::dataflow::{Scope, Stream};
 
@@ -15,7 +19,7 @@ impl<S: Scope<Timestamp = TimelyTimeStamp>> OptimalOrder<S> for Stream<S, Matric
     }
 }
 
-fn get_optimal_order(matrices: &[Matrix]) -> DimensionOrders {
+pub fn get_optimal_order(matrices: &[Matrix]) -> DimensionOrders {
     matrices
         .iter()
         .map(|matrix| {
@@ -25,3 +29,32 @@ fn get_optimal_order(matrices: &[Matrix]) -> DimensionOrders {
         })
         .collect_vec()
 }
+
+/// Orders the views by repeatedly picking the view with the fewest diffs from the previous one,
+/// starting from the empty view.
+pub fn get_greedy_order(matrix: &[MatrixRow]) -> DimensionOrder {
+    let mut remaining = (1..matrix.len()).collect_vec();
+    let mut previous = 0;
+    let mut order = Vec::with_capacity(remaining.len());
+    while let Some((position, _)) =
+        remaining.iter().enumerate().min_by_key(|(_, &view)| (matrix[previous][view], view))
+    {
+        previous = remaining.remove(position);
+        order.push(DimensionId::try_from(previous).expect("View index to DimensionId overflow"));
+    }
+    order
+}
+
+/// Returns the diffs of each view of `order` from the view before it, starting from the empty view.
+pub fn get_order_diffs(matrix: &[MatrixRow], order: &[DimensionId]) -> Vec<usize> {
+    let mut previous = 0;
+    order
+        .iter()
+        .map(|&view| {
+            let view = view as usize;
+            let diffs = matrix[previous][view];
+            previous = view;
+            diffs
+        })
+        .collect_vec()
+}
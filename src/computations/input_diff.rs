@@ -0,0 +1,217 @@
+use crate::graph::stream_data::get_worker_indices;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf};
+use differential_dataflow::operators::{Join, Threshold};
+use differential_dataflow::{AsCollection, Collection};
+use gs_analytics_api::{
+    ComputationInput, DiffCount, EdgePayload, FilteredCubeData, SimpleEdge, VertexId,
+};
+use timely::dataflow::operators::unordered_input::{
+    ActivateCapability, UnorderedHandle, UnorderedInput,
+};
+use timely::dataflow::Scope;
+use timely::progress::Timestamp;
+
+/// An edge keyed by its source vertex, along with its payload.
+pub type WeightedEdge = (VertexId, (VertexId, EdgePayload));
+
+/// The input handles of a runner, along with the capability to give diffs to them.
+pub type Inputs<T> = (InputHandles<T>, InputCapability<T>);
+
+/// The inputs of a runner. Each kind of diff has an input of its own, so that the edges of
+/// collections without edge properties are arranged as they are given, without payloads.
+pub struct InputHandles<T: Timestamp> {
+    edges: UnorderedHandle<T, (SimpleEdge, T, DiffCount)>,
+    weighted_edges: UnorderedHandle<T, (WeightedEdge, T, DiffCount)>,
+    vertices: UnorderedHandle<T, (VertexId, T, DiffCount)>,
+}
+
+/// The capabilities of the inputs a collection uses. The capabilities of the other inputs are
+/// dropped, so that they do not hold back the frontier.
+#[derive(Clone)]
+pub struct InputCapability<T: Timestamp> {
+    edges: Option<ActivateCapability<T>>,
+    weighted_edges: Option<ActivateCapability<T>>,
+    vertices: Option<ActivateCapability<T>>,
+}
+
+/// The collections of the inputs of a runner.
+pub struct InputCollections<G: Scope> {
+    /// The edges, without their payloads.
+    pub edges: Collection<G, SimpleEdge>,
+    /// The edges with their payloads, if the collection carries edge properties.
+    pub weighted_edges: Option<Collection<G, WeightedEdge>>,
+    /// The diff vertices, if the collection has vertex views.
+    pub vertices: Option<Collection<G, VertexId>>,
+}
+
+/// Creates the inputs of a runner for a collection. If the collection has vertex views, only the
+/// edges with both endpoints in the vertices of a view are part of that view.
+pub fn new_inputs<G: Scope>(
+    scope: &mut G,
+    has_payloads: bool,
+    has_vertex_views: bool,
+) -> (Inputs<G::Timestamp>, InputCollections<G>)
+where
+    G::Timestamp: Lattice + Ord,
+{
+    let ((edges, edges_cap), edge_stream) = scope.new_unordered_input();
+    let ((weighted_edges, weighted_edges_cap), weighted_edge_stream) = scope.new_unordered_input();
+    let ((vertices, vertices_cap), vertex_stream) = scope.new_unordered_input();
+
+    let vertex_collection =
+        if has_vertex_views { Some(vertex_stream.as_collection()) } else { None };
+    let (edge_collection, weighted_edge_collection) = if has_payloads {
+        let mut weighted_edge_collection = weighted_edge_stream.as_collection();
+        if let Some(vertices) = &vertex_collection {
+            weighted_edge_collection = weighted_edge_collection
+                .semijoin(vertices)
+                .map(|(src, (dst, payload))| (dst, (src, payload)))
+                .semijoin(vertices)
+                .map(|(dst, (src, payload))| (src, (dst, payload)));
+        }
+        let edge_collection =
+            weighted_edge_collection.map(|(src, (dst, _)): WeightedEdge| (src, dst));
+        (edge_collection, Some(weighted_edge_collection))
+    } else {
+        let mut edge_collection = edge_stream.as_collection();
+        if let Some(vertices) = &vertex_collection {
+            edge_collection = edge_collection
+                .semijoin(vertices)
+                .map(|(src, dst)| (dst, src))
+                .semijoin(vertices)
+                .map(|(dst, src)| (src, dst));
+        }
+        (edge_collection, None)
+    };
+    let capability = InputCapability {
+        edges: if has_payloads { None } else { Some(edges_cap) },
+        weighted_edges: if has_payloads { Some(weighted_edges_cap) } else { None },
+        vertices: if has_vertex_views { Some(vertices_cap) } else { None },
+    };
+    (
+        (InputHandles { edges, weighted_edges, vertices }, capability),
+        InputCollections {
+            edges: edge_collection,
+            weighted_edges: weighted_edge_collection,
+            vertices: vertex_collection,
+        },
+    )
+}
+
+/// Arranges the inputs for the computations. The nodes are the diff vertices if the collection
+/// has vertex views, and the endpoints of the edges otherwise.
+pub fn arrange_input<G: Scope>(input: &InputCollections<G>) -> ComputationInput<G>
+where
+    G::Timestamp: Lattice + Ord,
+{
+    let forward_edges = input.edges.arrange_by_key();
+    let weighted_edges = input.weighted_edges.as_ref().map(ArrangeByKey::arrange_by_key);
+    let reverse_edges = forward_edges.as_collection(|&k, &v| (v, k)).arrange_by_key();
+    let nodes = match &input.vertices {
+        Some(vertices) => vertices.arrange_by_self(),
+        None => forward_edges
+            .flat_map_ref(|src, dst| Some(*src).into_iter().chain(Some(*dst)))
+            .distinct()
+            .arrange_by_self(),
+    };
+    ComputationInput::new(nodes, forward_edges, reverse_edges, weighted_edges)
+}
+
+impl<T: Timestamp> InputCapability<T> {
+    /// The time of the capabilities, which are all at the same time.
+    pub fn time(&self) -> &T {
+        self.edges
+            .as_ref()
+            .or(self.weighted_edges.as_ref())
+            .expect("One of the edge inputs should be used")
+            .time()
+    }
+
+    pub fn downgrade(&mut self, time: &T) {
+        for capability in self.capabilities_mut() {
+            capability.downgrade(time);
+        }
+    }
+
+    pub fn delayed(&self, time: &T) -> Self {
+        Self {
+            edges: self.edges.as_ref().map(|capability| capability.delayed(time)),
+            weighted_edges: self.weighted_edges.as_ref().map(|capability| capability.delayed(time)),
+            vertices: self.vertices.as_ref().map(|capability| capability.delayed(time)),
+        }
+    }
+
+    fn capabilities_mut(&mut self) -> impl Iterator<Item = &mut ActivateCapability<T>> {
+        self.edges.iter_mut().chain(self.weighted_edges.iter_mut()).chain(self.vertices.iter_mut())
+    }
+}
+
+impl<T: Timestamp + Copy> InputHandles<T> {
+    /// Gives the share of the worker of the diffs of an entry of the cube at `timestamp`, and
+    /// returns the number of edge diffs given.
+    pub fn give_entry<S: Copy>(
+        &mut self,
+        capability: &InputCapability<T>,
+        cube_data: &FilteredCubeData<S>,
+        entry_index: usize,
+        timestamp: T,
+        worker_index: usize,
+        worker_count: usize,
+    ) -> usize {
+        let (_, _, (_, diff_edges), _, (_, vertex_diffs)) = &cube_data.entries[entry_index];
+        let payloads = cube_data.payloads.get(entry_index).map(|(_, payloads)| payloads.as_slice());
+        self.give_vertices(capability, vertex_diffs, timestamp, worker_index, worker_count);
+        self.give_edges(capability, diff_edges, payloads, timestamp, worker_index, worker_count)
+    }
+
+    /// Gives the share of the worker of `diffs`, along with their `payloads` if the collection
+    /// carries edge properties, at `timestamp`, and returns the number of diffs given.
+    pub fn give_edges(
+        &mut self,
+        capability: &InputCapability<T>,
+        diffs: &[(SimpleEdge, DiffCount)],
+        payloads: Option<&[EdgePayload]>,
+        timestamp: T,
+        worker_index: usize,
+        worker_count: usize,
+    ) -> usize {
+        let (left_index, right_index) = get_worker_indices(diffs.len(), worker_index, worker_count);
+        let worker_diffs = &diffs[left_index..right_index];
+        if let Some(edges_cap) = &capability.edges {
+            let mut session = self.edges.session(edges_cap.clone());
+            for &(edge, change) in worker_diffs {
+                session.give((edge, timestamp, change));
+            }
+        }
+        if let Some(weighted_edges_cap) = &capability.weighted_edges {
+            let mut session = self.weighted_edges.session(weighted_edges_cap.clone());
+            for (index, &((src, dst), change)) in worker_diffs.iter().enumerate() {
+                let payload =
+                    payloads.map_or_else(Vec::new, |payloads| payloads[left_index + index].clone());
+                session.give(((src, (dst, payload)), timestamp, change));
+            }
+        }
+        worker_diffs.len()
+    }
+
+    /// Gives the share of the worker of `vertex_diffs` at `timestamp`, if the collection has
+    /// vertex views.
+    pub fn give_vertices(
+        &mut self,
+        capability: &InputCapability<T>,
+        vertex_diffs: &[(VertexId, DiffCount)],
+        timestamp: T,
+        worker_index: usize,
+        worker_count: usize,
+    ) {
+        if let Some(vertices_cap) = &capability.vertices {
+            let (left_index, right_index) =
+                get_worker_indices(vertex_diffs.len(), worker_index, worker_count);
+            let mut session = self.vertices.session(vertices_cap.clone());
+            for &(vertex_id, change) in &vertex_diffs[left_index..right_index] {
+                session.give((vertex_id, timestamp, change));
+            }
+        }
+    }
+}
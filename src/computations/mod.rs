@@ -1,7 +1,9 @@
-use crate::computations::dataflow_arranged_adaptive::differential_run_adaptive;
+use crate::computations::dataflow_arranged_adaptive::{differential_run_adaptive, AdaptiveModel};
 use crate::computations::dataflow_differential_1_stage::differential_run_1_stage;
 use crate::computations::dataflow_differential_2_stages::differential_run_2_stage;
-use crate::filtered_cubes::materialise::{DiffEdgesPointer, DiffIteratorPointer};
+use crate::filtered_cubes::materialise::{
+    DiffEdgesPointer, DiffIteratorPointer, DiffVerticesPointer,
+};
 use crate::filtered_cubes::timestamp::GSTimestamp;
 use crate::filtered_cubes::CubePointer;
 use crate::graph::properties::property_value::PropertyValue;
@@ -31,6 +33,7 @@ pub mod dataflow_differential_1_stage;
 pub mod dataflow_differential_2_stages;
 pub mod dataflow_differential_basic;
 pub mod filtered_cubes;
+pub mod input_diff;
 pub mod pagerank;
 pub mod scc;
 pub mod spsp;
@@ -51,6 +54,8 @@ pub type DifferentialResults<C, T> = HashMap<T, DifferentialResult<C>>;
 pub type DifferentialResult<C> = Vec<(<C as ComputationTypes>::Result, DiffCount)>;
 pub type ComputationResultSet<C> = HashSet<(<C as ComputationTypes>::Result, DiffCount)>;
 pub type ComputationResults<C> = HashMap<GsTimestampIndex, (ComputationResultSet<C>, GSTimestamp)>;
+/// The result of a computation, along with the model fitted by the run if it was adaptive.
+pub type ComputationOutput = (GraphSurgeResult, Option<AdaptiveModel>);
 
 pub enum ComputationProperties {
     Value(PropertyValue),
@@ -84,7 +89,31 @@ pub trait Computation: BasicComputation + GraphsurgeComputation + TimelyComputat
         &self,
         cube: &mut FilteredCube,
         runtime_data: ComputationRuntimeData,
-    ) -> Result<GraphSurgeResult, GSError> {
+    ) -> Result<ComputationOutput, GSError> {
+        let required_edge_properties = self.required_edge_properties();
+        if required_edge_properties > 0 {
+            if cube.data.edge_properties.len() < required_edge_properties {
+                return Err(GSError::Computation(format!(
+                    "Computation needs {} edge properties but the collection carries {}",
+                    required_edge_properties,
+                    cube.data.edge_properties.len()
+                )));
+            }
+            if let ComputationType::Basic
+            | ComputationType::IndividualBasic
+            | ComputationType::Timely = runtime_data.c_type
+            {
+                return Err(GSError::Computation(format!(
+                    "Edge properties are not available to {} computations",
+                    runtime_data.c_type.description()
+                )));
+            }
+            for payload in
+                cube.data.payloads.iter().flat_map(|(full, diff)| full.iter().chain(diff))
+            {
+                self.check_edge_payload(payload).map_err(GSError::Computation)?;
+            }
+        }
         match runtime_data.c_type {
             ComputationType::IndividualBasic
             | ComputationType::OneStageDifferential
@@ -102,17 +131,21 @@ pub trait Computation: BasicComputation + GraphsurgeComputation + TimelyComputat
             | ComputationType::OneStageDifferential
             | ComputationType::TwoStageDifferential
             | ComputationType::Adaptive => {
-                differential_diff_execute(cube, self, &runtime_data)?;
+                let (_, adaptive_model) = differential_diff_execute(cube, self, &runtime_data)?;
+                return Ok((
+                    GraphSurgeResult::new(format!("{} done", runtime_data.c_type.description())),
+                    adaptive_model,
+                ));
             }
             ComputationType::IndividualBasic | ComputationType::Individual => {
                 differential_individual_execute(cube, self, &runtime_data)?;
             }
             ComputationType::CompareDifferential => {
-                return execute_compare_differential(&cube, self, &runtime_data)
+                return Ok((execute_compare_differential(cube, self, &runtime_data)?, None))
             }
             ComputationType::Timely => timely_execute(&cube, self, &runtime_data)?,
         }
-        Ok(GraphSurgeResult::new(format!("{} done", runtime_data.c_type.description())))
+        Ok((GraphSurgeResult::new(format!("{} done", runtime_data.c_type.description())), None))
     }
 }
 
@@ -123,7 +156,7 @@ pub fn execute_compare_differential<T: Computation>(
     computation: &T,
     runtime_data: &ComputationRuntimeData,
 ) -> Result<GraphSurgeResult, GSError> {
-    let r1 = differential_diff_execute(cube, computation, runtime_data)?;
+    let (r1, _) = differential_diff_execute(cube, computation, runtime_data)?;
     let r2 = differential_individual_execute(cube, computation, runtime_data)?;
     compare::<T>(r1, r2)
 }
@@ -203,13 +236,28 @@ where
     let fixed_timestamp = GSTimestamp::default();
     let next_timestamp = GSTimestamp::new(&[1]);
     let materialize_results = runtime_data.materialize_results != MaterializeResults::None;
-    for (timestamp_index, timestamp, (data, _), _) in &cube.data.entries {
+    for (entry_index, (timestamp_index, timestamp, (data, _), _, (vertices, _))) in
+        cube.data.entries.iter().enumerate()
+    {
         info!("Starting computation for {}", timestamp);
         let data = data.iter().map(|&x| (x, 1)).collect_vec();
+        let vertex_data = vertices.iter().map(|&x| (x, 1)).collect_vec();
         let results = if is_arranged {
             let len = data.len();
-            let cube_data =
-                FilteredCubeData::new(vec![(0, fixed_timestamp, (Vec::new(), data), (len, 0))]);
+            // The full edges of the view are inserted as diffs.
+            let payloads = cube
+                .data
+                .payloads
+                .get(entry_index)
+                .map(|(full_payloads, _)| (Vec::new(), full_payloads.clone()))
+                .into_iter()
+                .collect();
+            let mut cube_data = FilteredCubeData::with_payloads(
+                vec![(0, fixed_timestamp, (Vec::new(), data), (len, 0), (Vec::new(), vertex_data))],
+                cube.data.edge_properties.clone(),
+                payloads,
+            );
+            cube_data.has_vertex_views = cube.data.has_vertex_views;
             differential_run_2_stage(
                 CubePointer::new(&cube_data),
                 computation.clone(),
@@ -221,9 +269,11 @@ where
                 fixed_timestamp,
                 next_timestamp,
                 DiffEdgesPointer::new(&data),
+                DiffVerticesPointer::new(&vertex_data),
             )]);
             differential_run_basic(
                 DiffIteratorPointer::new(&diff_iterators),
+                cube.data.has_vertex_views,
                 fixed_timestamp,
                 computation.clone(),
                 &runtime_data,
@@ -262,13 +312,14 @@ fn differential_diff_execute<C: Computation>(
     cube: &FilteredCube,
     computation: &C,
     runtime_data: &ComputationRuntimeData,
-) -> Result<ComputationResults<C>, GSError>
+) -> Result<(ComputationResults<C>, Option<AdaptiveModel>), GSError>
 where
     C::Result: Hash,
 {
     info!("Running {}:", runtime_data.c_type.description());
 
     let timer = GsTimer::now();
+    let mut adaptive_model = None;
     let zeroth_ts = GSTimestamp::get_zeroth_timestamp();
     let materialize_results = runtime_data.materialize_results != MaterializeResults::None;
     #[allow(clippy::wildcard_enum_match_arm)]
@@ -278,6 +329,7 @@ where
             let diff_data = cube.differential_data.as_ref().expect("Should have been materialized");
             differential_run_basic(
                 DiffIteratorPointer::new(&diff_data.cube_diff_iterators),
+                cube.data.has_vertex_views,
                 zeroth_ts,
                 computation.clone(),
                 &runtime_data,
@@ -303,11 +355,13 @@ where
         }
         ComputationType::Adaptive => {
             // arranged operators + insert/computation stages + no timely timestamps.
-            differential_run_adaptive(
+            let (results, model) = differential_run_adaptive(
                 CubePointer::new(&cube.data),
                 computation.clone(),
                 &runtime_data,
-            )?
+            )?;
+            adaptive_model = Some(model);
+            results
         }
         c => return Err(GSError::UnknownComputation(c.to_string())),
     };
@@ -397,7 +451,7 @@ where
         print_save_results::<C>(&full_results, &runtime_data.save_to)?;
     }
 
-    Ok(full_results.into_iter().collect())
+    Ok((full_results.into_iter().collect(), adaptive_model))
 }
 
 fn timely_execute<C: Computation>(
@@ -17,25 +17,36 @@ impl BasicComputation for PageRank {
     where
         G::Timestamp: Lattice + Ord + Copy,
     {
-        pagerank(self.iterations, &edges).consolidate()
+        // initialize many surfers at each node.
+        let nodes = edges.flat_map(|(x, y)| Some(x).into_iter().chain(Some(y))).distinct();
+        pagerank(self.iterations, &nodes, &edges).consolidate()
+    }
+
+    fn basic_computation_with_nodes<G: Scope>(
+        &self,
+        nodes: &Collection<G, VertexId>,
+        edges: &Collection<G, SimpleEdge>,
+    ) -> Collection<G, Self::Result>
+    where
+        G::Timestamp: Lattice + Ord + Copy,
+    {
+        pagerank(self.iterations, nodes, &edges).consolidate()
     }
 }
 
-/// Returns a weighted collection in which the weight of each node is proportional
+/// Returns a weighted collection in which the weight of each node in `nodes` is proportional
 /// to its pagerank in the input graph `edges`.
 ///
 /// Code adapted from [Differential Dataflow](https://github.com/TimelyDataflow/differential-dataflow/blob/master/examples/pagerank.rs).
 fn pagerank<G>(
     iterations: TimelyTimeStamp,
+    nodes: &Collection<G, VertexId, DiffCount>,
     edges: &Collection<G, SimpleEdge, DiffCount>,
 ) -> Collection<G, VertexId, DiffCount>
 where
     G: Scope,
     G::Timestamp: Lattice,
 {
-    // initialize many surfers at each node.
-    let nodes = edges.flat_map(|(x, y)| Some(x).into_iter().chain(Some(y))).distinct();
-
     // snag out-degrees for each node.
     #[allow(clippy::suspicious_map)]
     let degrs = edges.map(|(src, _dst)| src).count();
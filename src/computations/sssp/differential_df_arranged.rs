@@ -4,7 +4,8 @@ use differential_dataflow::operators::iterate::Iterate;
 use differential_dataflow::operators::reduce::Reduce;
 use differential_dataflow::operators::JoinCore;
 use differential_dataflow::Collection;
-use gs_analytics_api::{ComputationInput, GraphsurgeComputation, PropertyInput};
+use gs_analytics_api::{ComputationInput, EdgePayload, GraphsurgeComputation, PropertyInput};
+use std::convert::TryFrom;
 use timely::dataflow::Scope;
 
 impl GraphsurgeComputation for Sssp {
@@ -16,27 +17,55 @@ impl GraphsurgeComputation for Sssp {
     where
         G::Timestamp: Lattice + Ord + Copy,
     {
-        let weight_cap = self.weight_cap;
         let (collection_handle, roots_collection) =
             PropertyInput::new(input_stream.nodes.stream.scope());
 
         // initialize roots as reaching themselves at distance 0
         let nodes = roots_collection.map(|x| (x, 0));
 
-        let result = nodes.iterate(|inner| {
-            let edges = input_stream.edges.enter(&inner.scope());
-            let nodes = nodes.enter(&inner.scope());
+        let result = match (self.weight_cap, &input_stream.weighted_edges) {
+            (Some(weight_cap), _) => nodes.iterate(|inner| {
+                let edges = input_stream.edges.enter(&inner.scope());
+                let nodes = nodes.enter(&inner.scope());
 
-            inner
-                .join_core(&edges, move |k, l, d| {
-                    Some((*d, l + ((k + d) as usize % weight_cap) + 1))
-                })
-                .concat(&nodes)
-                .reduce(|_, s, t| t.push((*s[0].0, 1)))
-        });
+                inner
+                    .join_core(&edges, move |k, l, d| {
+                        Some((*d, l + ((k + d) as usize % weight_cap) + 1))
+                    })
+                    .concat(&nodes)
+                    .reduce(|_, s, t| t.push((*s[0].0, 1)))
+            }),
+            (None, Some(weighted_edges)) => nodes.iterate(|inner| {
+                let edges = weighted_edges.enter(&inner.scope());
+                let nodes = nodes.enter(&inner.scope());
+
+                inner
+                    .join_core(&edges, |_, l, (d, payload)| {
+                        let weight = usize::try_from(payload[0]).expect("Negative edge weight");
+                        Some((*d, l + weight))
+                    })
+                    .concat(&nodes)
+                    .reduce(|_, s, t| t.push((*s[0].0, 1)))
+            }),
+            (None, None) => unreachable!("The collection should carry the edge weights"),
+        };
 
         collection_handle.send(self.root);
 
         result
     }
+
+    fn required_edge_properties(&self) -> usize {
+        // The weighted mode reads the weight from the first edge property.
+        usize::from(self.weight_cap.is_none())
+    }
+
+    fn check_edge_payload(&self, payload: &EdgePayload) -> Result<(), String> {
+        match payload.first() {
+            Some(&weight) if self.weight_cap.is_none() && weight < 0 => {
+                Err(format!("Edge weights cannot be negative, found {}", weight))
+            }
+            _ => Ok(()),
+        }
+    }
 }
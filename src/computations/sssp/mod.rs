@@ -11,17 +11,25 @@ mod differential_df_arranged;
 const NAME: &str = "SSSP";
 const PROPERTY: &str = "root";
 const PROPERTY2: &str = "weight_cap";
+const PROPERTY2_WEIGHTED: &str = "weighted";
 type BFSLength = usize;
 
 #[derive(Clone)]
 pub struct Sssp {
     root: VertexId,
-    weight_cap: usize,
+    /// Edge weights are derived from the vertex ids and capped at `weight_cap` if set. Otherwise,
+    /// the first edge property carried by the collection is used as the weight.
+    weight_cap: Option<usize>,
 }
 
 impl Sssp {
     pub fn new(root: VertexId, weight_cap: usize) -> Self {
-        Self { root, weight_cap }
+        Self { root, weight_cap: Some(weight_cap) }
+    }
+
+    /// Uses the first edge property carried by the collection as the edge weights.
+    pub fn weighted(root: VertexId) -> Self {
+        Self { root, weight_cap: None }
     }
 
     pub fn instance(properties: &HashMap<String, ComputationProperties>) -> Result<Self, GSError> {
@@ -37,18 +45,16 @@ impl Sssp {
         let value = properties
             .get(PROPERTY)
             .ok_or_else(|| GSError::Property(NAME, PROPERTY, keys.clone()))?;
-        let value2 = properties.get(PROPERTY2).ok_or(GSError::Property(NAME, PROPERTY2, keys))?;
-        let root;
-        if let ComputationProperties::Value(PropertyValue::Isize(r)) = value {
-            root = VertexId::try_from(*r).expect("Root value cannot be negative");
-        } else {
-            return Err(GSError::PropertyType(
-                NAME,
-                PROPERTY,
-                "usize(vertex id)",
-                value.get_type(),
-            ));
+        if let Some(weighted) = properties.get(PROPERTY2_WEIGHTED) {
+            let root = Self::get_root(value)?;
+            return if let ComputationProperties::Value(PropertyValue::Bool(true)) = weighted {
+                Ok(Self::weighted(root))
+            } else {
+                Err(GSError::PropertyType(NAME, PROPERTY2_WEIGHTED, "true", weighted.get_type()))
+            };
         }
+        let value2 = properties.get(PROPERTY2).ok_or(GSError::Property(NAME, PROPERTY2, keys))?;
+        let root = Self::get_root(value)?;
         let weight_cap;
         if let ComputationProperties::Value(PropertyValue::Isize(w)) = value2 {
             weight_cap = usize::try_from(*w).expect("Root value cannot be negative");
@@ -61,7 +67,15 @@ impl Sssp {
             ));
         }
 
-        Ok(Self { root, weight_cap })
+        Ok(Self::new(root, weight_cap))
+    }
+
+    fn get_root(value: &ComputationProperties) -> Result<VertexId, GSError> {
+        if let ComputationProperties::Value(PropertyValue::Isize(r)) = value {
+            Ok(VertexId::try_from(*r).expect("Root value cannot be negative"))
+        } else {
+            Err(GSError::PropertyType(NAME, PROPERTY, "usize(vertex id)", value.get_type()))
+        }
     }
 }
 
@@ -3,6 +3,7 @@ use crate::computations::dataflow_arranged_adaptive::differential_run_adaptive;
 use crate::computations::dataflow_differential_2_stages::differential_run_2_stage;
 use crate::computations::dataflow_differential_basic::differential_run_basic;
 use crate::computations::scc::Scc;
+use crate::computations::sssp::Sssp;
 use crate::computations::wcc::Wcc;
 use crate::computations::{
     differential_diff_execute, process_results, Computation, DifferentialResults, SplitIndices,
@@ -17,7 +18,6 @@ use gs_analytics_api::{
 };
 use hashbrown::{HashMap, HashSet};
 
-#[cfg(feature = "nd-timestamps")]
 #[test]
 fn test_bfs_2d() {
     let expected_diff_results = vec![
@@ -185,7 +185,6 @@ fn test_bfs_1d() {
     );
 }
 
-#[cfg(feature = "nd-timestamps")]
 #[test]
 fn test_wcc_2d() {
     let expected_diff_results = vec![
@@ -647,6 +646,117 @@ fn test_scc_adaptive() {
     }
 }
 
+#[test]
+fn test_sssp_negative_weights() {
+    let mut global_store = GlobalStore::default();
+
+    let mut graph_query = "
+        load graph with vertices from 'data/small_negative_weights/vertices.txt'
+        and edges from 'data/small_negative_weights/edges.txt'
+        comment '#';"
+        .to_owned();
+    process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+    let mut cube_query = "
+        create view collection weights
+        where [include = true],[include = true or include = false]
+        with edge properties (weight)
+        manually_ordered;"
+        .to_owned();
+    process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+
+    let cube = global_store.filtered_cube_store.cubes.get_mut("weights").expect("Expected cube");
+    let runtime_data = ComputationRuntimeData {
+        c_type: ComputationType::TwoStageDifferential,
+        ..ComputationRuntimeData::default()
+    };
+    match Sssp::weighted(0).execute(cube, runtime_data) {
+        Err(error) => {
+            assert!(error.to_string().contains("Edge weights cannot be negative, found -2"));
+        }
+        Ok(_) => panic!("Negative weights should be rejected"),
+    }
+}
+
+#[test]
+fn test_wcc_vertex_views() {
+    let mut global_store = GlobalStore::default();
+
+    let mut graph_query = "
+        load graph with vertices from 'data/small_properties/vertices.txt'
+        and edges from 'data/small_properties/edges.txt'
+        comment '#';"
+        .to_owned();
+    process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+    let mut cube_query = "
+        create view collection people
+        where [year <= 2005],[year <= 2018]
+        vertices where [include = true],[include2 = true]
+        manually_ordered
+        materialize_full_view;"
+        .to_owned();
+    process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+
+    // Only the edges with both endpoints in the vertices of a view are part of that view.
+    let expected_diff_results = vec![
+        (
+            GSTimestamp::new(&[0]),
+            vec![((0, 0), 1), ((2, 2), 1), ((4, 4), 1), ((5, 4), 1), ((6, 6), 1)],
+        ),
+        (
+            GSTimestamp::new(&[1]),
+            vec![((2, 2), -1), ((4, 4), -1), ((5, 4), -1), ((7, 6), 1), ((8, 8), 1)],
+        ),
+    ]
+    .into_iter()
+    .map(|(ts, values)| (ts, values.into_iter().collect::<HashSet<_>>()))
+    .collect::<HashMap<_, _>>();
+
+    let cube = global_store.filtered_cube_store.cubes.get_mut("people").expect("Expected cube");
+    cube.prepare_differential_data();
+    let runtime_data = ComputationRuntimeData {
+        total_vertices: global_store.graph.vertex_count(),
+        c_type: ComputationType::Basic,
+        materialize_results: MaterializeResults::Full,
+        threads: 1,
+        ..ComputationRuntimeData::default()
+    };
+
+    let results = differential_run_basic(
+        DiffIteratorPointer::new(
+            &cube.differential_data.as_ref().expect("Data expected").cube_diff_iterators,
+        ),
+        cube.data.has_vertex_views,
+        GSTimestamp::get_zeroth_timestamp(),
+        Wcc,
+        &runtime_data,
+    )
+    .expect("Computation failed");
+    let (computed_results, _, _) =
+        process_results::<Wcc>(results, true).expect("Processing failed");
+    assert_results_match::<Wcc>(
+        computed_results,
+        &expected_diff_results,
+        "Failed to match results of computation with vertex views",
+    );
+
+    let results = differential_run_2_stage(
+        CubePointer::new(&cube.data),
+        Wcc,
+        GSTimestamp::get_zeroth_timestamp(),
+        &runtime_data,
+    )
+    .expect("Computation failed");
+    let (computed_results_arranged, _, _) =
+        process_results::<Wcc>(results, true).expect("Processing failed");
+    assert_results_match::<Wcc>(
+        computed_results_arranged,
+        &expected_diff_results,
+        "Failed to match results of arranged computation with vertex views",
+    );
+}
+
 type ExpectedResults<C> = (GSTimestamp, Vec<(<C as ComputationTypes>::Result, DiffCount)>);
 
 fn create_cube_and_assert<C: Computation>(
@@ -697,6 +807,7 @@ fn create_cube_and_assert<C: Computation>(
             DiffIteratorPointer::new(
                 &cube.differential_data.as_ref().expect("Data expected").cube_diff_iterators,
             ),
+            cube.data.has_vertex_views,
             GSTimestamp::get_zeroth_timestamp(),
             computation.clone(),
             &runtime_data,
@@ -767,7 +878,7 @@ fn create_cube_and_assert<C: Computation>(
 
     runtime_data.hosts.clear();
     runtime_data.threads = 1;
-    let full_results =
+    let (full_results, _) =
         differential_diff_execute(cube, computation, &runtime_data).expect("Computation failed");
     assert_eq!(
         full_results
@@ -818,7 +929,7 @@ fn create_cube_and_assert_adaptive<C: Computation>(
     for &threads in &[1, 4] {
         runtime_data.threads = threads;
         runtime_data.batch_size = Some(2);
-        let results = differential_run_adaptive(
+        let (results, _) = differential_run_adaptive(
             CubePointer::new(&cube.data),
             computation.clone(),
             &runtime_data,
@@ -4,7 +4,7 @@ use crate::graph::stream_data::filter::test_where_conditions;
 use crate::graph::GraphPointer;
 use crate::query_handler::create_view::{
     fmt_condition_clauses, FlattenedGroupCondition, GroupClause, GroupCondition, SectionDetails,
-    VertexSections,
+    VertexSections, WhereCondition,
 };
 use gs_analytics_api::{TimelyTimeStamp, VertexId};
 use itertools::Itertools;
@@ -99,7 +99,7 @@ fn group<'a, T: Eq + Clone, P: Push<Bundle<T, VertexGroupMapOutput>> + 'a>(
                         let pv = PropertyValue::get_id(vertex_id);
                         let value = graph_pointer
                             .get_vertex_id_property_value(vertex_id, &pv, key_id)
-                            .map_or_else(|| "''".to_owned(), ToString::to_string);
+                            .map_or_else(|| "''".to_owned(), |value| value.to_string());
                         result.push(format!("{}={}", key_id, value));
                     }
                     FlattenedGroupCondition::WhereConditions(where_conditions) => {
@@ -131,10 +131,9 @@ fn get_flattened_group_clauses(
                         vec![FlattenedGroupCondition::Variable(key)].into_iter()
                     }
                     GroupCondition::WherePredicate(where_predicate) => {
-                        vec![FlattenedGroupCondition::WhereConditions(vec![(
-                            false,
-                            vec![where_predicate],
-                        )])]
+                        vec![FlattenedGroupCondition::WhereConditions(vec![
+                            WhereCondition::Predicate(where_predicate),
+                        ])]
                         .into_iter()
                     }
                     GroupCondition::List(group_list) => group_list
@@ -1,7 +1,7 @@
 use crate::computations::views::{
     QueryProperties, QueryProperty, TreeAggregatedVertexOutput, TreeVertexReduceOutput,
 };
-use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::property_value::{Float, PropertyValue};
 use gs_analytics_api::TimelyTimeStamp;
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::generic::operator::Operator;
@@ -48,12 +48,28 @@ pub(super) fn merge_properties(
                     *num += rnum;
                 }
             }
-            PropertyValue::Pair(sum, count) => {
-                if let PropertyValue::Pair(rsum, rcount) = rvalue {
+            PropertyValue::Pair(sum, count) => match rvalue {
+                PropertyValue::Pair(rsum, rcount) => {
                     *sum += rsum;
                     *count += rcount;
                 }
-            }
+                PropertyValue::FloatPair(rsum, rcount) => {
+                    *lvalue =
+                        PropertyValue::FloatPair(Float(*sum as f64 + rsum.0), *count + rcount);
+                }
+                _ => {}
+            },
+            PropertyValue::FloatPair(sum, count) => match rvalue {
+                PropertyValue::Pair(rsum, rcount) => {
+                    sum.0 += *rsum as f64;
+                    *count += rcount;
+                }
+                PropertyValue::FloatPair(rsum, rcount) => {
+                    sum.0 += rsum.0;
+                    *count += rcount;
+                }
+                _ => {}
+            },
             _ => unreachable!(),
         },
     );
@@ -5,7 +5,7 @@ use differential_dataflow::operators::iterate::Iterate;
 use differential_dataflow::operators::join::Join;
 use differential_dataflow::operators::reduce::Reduce;
 use differential_dataflow::Collection;
-use gs_analytics_api::{BasicComputation, SimpleEdge};
+use gs_analytics_api::{BasicComputation, SimpleEdge, VertexId};
 use timely::dataflow::Scope;
 
 impl BasicComputation for Wcc {
@@ -22,19 +22,39 @@ impl BasicComputation for Wcc {
                 *pair = (min, min);
             })
             .consolidate();
+        wcc(&nodes, edges)
+    }
+
+    fn basic_computation_with_nodes<G: Scope>(
+        &self,
+        nodes: &Collection<G, VertexId>,
+        edges: &Collection<G, SimpleEdge>,
+    ) -> Collection<G, Self::Result>
+    where
+        G::Timestamp: Lattice + Ord + Copy,
+    {
+        wcc(&nodes.map(|node| (node, node)), edges)
+    }
+}
 
-        // each edge should exist in both directions.
-        let edges = edges.map_in_place(|x| std::mem::swap(&mut x.0, &mut x.1)).concat(&edges);
+/// Labels each node with the smallest label reachable from it, starting from the labels in `nodes`.
+fn wcc<G: Scope>(
+    nodes: &Collection<G, (VertexId, VertexId)>,
+    edges: &Collection<G, SimpleEdge>,
+) -> Collection<G, (VertexId, VertexId)>
+where
+    G::Timestamp: Lattice + Ord + Copy,
+{
+    // each edge should exist in both directions.
+    let edges = edges.map_in_place(|x| std::mem::swap(&mut x.0, &mut x.1)).concat(&edges);
 
-        // don't actually use these labels, just grab the type
-        nodes.filter(|_| false).iterate(|inner| {
-            let edges = edges.enter(&inner.scope());
-            let nodes =
-                nodes.enter_at(&inner.scope(), |r| 256 * (64 - u64::from(r.1.leading_zeros())));
+    // don't actually use these labels, just grab the type
+    nodes.filter(|_| false).iterate(|inner| {
+        let edges = edges.enter(&inner.scope());
+        let nodes = nodes.enter_at(&inner.scope(), |r| 256 * (64 - u64::from(r.1.leading_zeros())));
 
-            inner.join_map(&edges, |_k, l, d| (*d, *l)).concat(&nodes).reduce(|_, s, t| {
-                t.push((*s[0].0, 1));
-            })
+        inner.join_map(&edges, |_k, l, d| (*d, *l)).concat(&nodes).reduce(|_, s, t| {
+            t.push((*s[0].0, 1));
         })
-    }
+    })
 }
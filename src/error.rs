@@ -6,7 +6,8 @@ pub enum GSError {
     WriteFile(String, String),
     Parsing(String),
     LoadGraph(String),
-    GraphParse(String, &'static str, String, String),
+    ModifyGraph(String),
+    Index(String),
     CollectionAlreadyExists(String),
     CollectionMissing(String),
     Collection(String),
@@ -16,6 +17,7 @@ pub enum GSError {
     PropertyType(&'static str, &'static str, &'static str, String),
     Serialize(String, String),
     Deserialize(String, String),
+    UnsupportedFormatVersion(String, u32, u32),
     NotDirectory(String),
     Timely(String),
     TimelyResults(String),
@@ -39,12 +41,8 @@ impl std::fmt::Display for GSError {
             }
             GSError::Parsing(message) => write!(f, "[ParsingError] {}", message)?,
             GSError::LoadGraph(message) => write!(f, "[LoadGraphError] {}", message,)?,
-            GSError::GraphParse(property, ptype, vertex_id, file) => write!(
-                f,
-                "[LoadGraphError] Could not parse property value '{}' as {} \
-                            for vertex id '{}' in file '{}'",
-                property, ptype, vertex_id, file
-            )?,
+            GSError::ModifyGraph(message) => write!(f, "[ModifyGraphError] {}", message)?,
+            GSError::Index(message) => write!(f, "[IndexError] {}", message)?,
             GSError::Collection(message) => write!(f, "[CollectionError] {}", message,)?,
             GSError::CollectionAlreadyExists(name) => {
                 write!(f, "[CollectionError] Collection '{}' already exists in store", name)?;
@@ -73,6 +71,12 @@ impl std::fmt::Display for GSError {
             GSError::Deserialize(name, e) => {
                 write!(f, "[SerdeError] Could not deserialize '{}': {}", name, e)?;
             }
+            GSError::UnsupportedFormatVersion(bin_dir, version, expected_version) => write!(
+                f,
+                "[SerdeError] Unsupported format version {} of the data in '{}', this build only \
+                 reads version {}",
+                version, bin_dir, expected_version
+            )?,
             GSError::PropertyCount(computation, required, properties, found) => write!(
                 f,
                 "[InitError] {} needs {} {} {:?}, but found {} properties",
@@ -2,7 +2,7 @@ use crate::filtered_cubes::timestamp::GSTimestamp;
 use crate::filtered_cubes::DimensionLength;
 use crate::filtered_cubes::FilteredCube;
 use crate::{create_generic_pointer_with_bounds, create_pointer};
-use gs_analytics_api::{DiffCount, SimpleEdge};
+use gs_analytics_api::{DiffCount, SimpleEdge, VertexId};
 use hashbrown::HashMap;
 use log::info;
 use std::fmt::Display;
@@ -10,13 +10,15 @@ use timely::progress::Timestamp;
 
 pub type DiffEdges = Vec<(SimpleEdge, DiffCount)>;
 create_pointer!(DiffEdgesPointer, DiffEdges);
+pub type DiffVertices = Vec<(VertexId, DiffCount)>;
+create_pointer!(DiffVerticesPointer, DiffVertices);
 
-pub type DiffStore = HashMap<GSTimestamp, DiffEdgesPointer>;
+pub type DiffStore = HashMap<GSTimestamp, (DiffEdgesPointer, DiffVerticesPointer)>;
 
 #[derive(Clone, Debug)]
 pub enum CubeDiffIterators<T: Timestamp> {
     Outer(Vec<(T, Box<CubeDiffIterators<T>>)>),
-    Inner(Vec<(T, T, DiffEdgesPointer)>),
+    Inner(Vec<(T, T, DiffEdgesPointer, DiffVerticesPointer)>),
 }
 create_generic_pointer_with_bounds!(DiffIteratorPointer, CubeDiffIterators, Timestamp);
 
@@ -42,8 +44,9 @@ impl CubeDiffIterators<GSTimestamp> {
                             total_dimensions,
                             value.checked_add(1).expect("Dimension overflow"),
                         );
-                        let diffs = diff_store.remove(&current_timestamp).expect("Value not found");
-                        (current_timestamp, next_timestamp, diffs)
+                        let (diffs, vertex_diffs) =
+                            diff_store.remove(&current_timestamp).expect("Value not found");
+                        (current_timestamp, next_timestamp, diffs, vertex_diffs)
                     })
                     .collect(),
             )
@@ -80,7 +83,7 @@ impl<T: Timestamp + Display> std::fmt::Display for CubeDiffIterators<T> {
                     .join("\n"),
                 CubeDiffIterators::Inner(inner) => inner
                     .iter()
-                    .map(|(timestamp, _, data)| format!(
+                    .map(|(timestamp, _, data, _)| format!(
                         "{}:\n\ttotal: {}, sample: {}",
                         timestamp,
                         data.len(),
@@ -104,9 +107,12 @@ pub struct DifferentialData {
 
 pub fn get_differential_data(cube: &FilteredCube) -> DifferentialData {
     let mut diff_data: DiffStore = HashMap::new();
-    for (_, timestamp, (_, diff_edges), _) in &cube.data.entries {
+    for (_, timestamp, (_, diff_edges), _, (_, diff_vertices)) in &cube.data.entries {
         info!("{}: total {}", timestamp, diff_edges.len());
-        diff_data.insert(*timestamp, DiffEdgesPointer::new(&diff_edges));
+        diff_data.insert(
+            *timestamp,
+            (DiffEdgesPointer::new(&diff_edges), DiffVerticesPointer::new(&diff_vertices)),
+        );
     }
     let dimensions_count = cube.dimension_lengths.len();
     let cube_diff_iterators = CubeDiffIterators::new(
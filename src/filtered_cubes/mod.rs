@@ -1,13 +1,16 @@
+use crate::computations::dataflow_arranged_adaptive::AdaptiveModel;
 use crate::create_generic_pointer;
+use crate::error::GSError;
 use crate::filtered_cubes::materialise::get_differential_data;
 use crate::filtered_cubes::materialise::DifferentialData;
-use crate::filtered_cubes::timestamp::timestamp_mappings::TimestampMappings;
+use crate::filtered_cubes::timestamp::timestamp_mappings::{accumulate_diffs, TimestampMappings};
 use crate::filtered_cubes::timestamp::{DimensionId, GSTimestamp};
 use crate::util::timer::GsTimer;
 use gs_analytics_api::{DiffCount, EdgeId, FilteredCubeData, SimpleEdge};
 use hashbrown::HashMap;
 use itertools::Itertools;
 use log::info;
+use std::convert::TryFrom;
 use std::fmt::Display;
 use std::fmt::Error;
 use std::fmt::Formatter;
@@ -26,9 +29,55 @@ impl FilteredCubeStore {
         self.cubes.clear();
     }
 
+    /// Removes the cube `name`, freeing its data and its materialized differential data.
+    pub fn drop_cube(&mut self, name: &str) -> Result<(), GSError> {
+        self.cubes.remove(name).ok_or_else(|| GSError::CollectionMissing(name.to_owned()))?;
+        self.cubes.shrink_to_fit();
+        Ok(())
+    }
+
+    /// Moves the cube `from` to `to`, keeping its materialized differential data.
+    pub fn rename_cube(&mut self, from: &str, to: &str) -> Result<(), GSError> {
+        if self.cubes.contains_key(to) {
+            return Err(GSError::CollectionAlreadyExists(to.to_owned()));
+        }
+        let cube =
+            self.cubes.remove(from).ok_or_else(|| GSError::CollectionMissing(from.to_owned()))?;
+        self.cubes.insert(to.to_owned(), cube);
+        Ok(())
+    }
+
+    /// Copies the cube `from` to `to`. The differential data of the copy is materialized again
+    /// when it is first used.
+    pub fn copy_cube(&mut self, from: &str, to: &str) -> Result<(), GSError> {
+        if self.cubes.contains_key(to) {
+            return Err(GSError::CollectionAlreadyExists(to.to_owned()));
+        }
+        let cube =
+            self.cubes.get(from).ok_or_else(|| GSError::CollectionMissing(from.to_owned()))?;
+        let copy = FilteredCube {
+            timestamp_mappings: cube.timestamp_mappings.clone(),
+            dimension_lengths: cube.dimension_lengths.clone(),
+            differential_data: None,
+            data: cube.data.clone(),
+            stale: cube.stale,
+            definition: cube.definition.clone(),
+            adaptive_models: cube.adaptive_models.clone(),
+        };
+        self.cubes.insert(to.to_owned(), copy);
+        Ok(())
+    }
+
     pub fn reset(&mut self) {
         self.cubes.clear();
     }
+
+    /// Marks every cube as built from an older version of the graph.
+    pub fn mark_all_stale(&mut self) {
+        for cube in self.cubes.values_mut() {
+            cube.stale = true;
+        }
+    }
 }
 
 impl Display for FilteredCubeStore {
@@ -42,7 +91,14 @@ impl Display for FilteredCubeStore {
                 self.cubes
                     .iter()
                     .enumerate()
-                    .map(|(index, (name, _))| format!("({}) cube {}", (index + 1), name,))
+                    .map(|(index, (name, cube))| {
+                        format!(
+                            "({}) cube {}{}",
+                            (index + 1),
+                            name,
+                            if cube.stale { " (stale)" } else { "" }
+                        )
+                    })
                     .collect::<Vec<String>>()
                     .join("\n")
             }
@@ -57,9 +113,92 @@ pub struct FilteredCube {
     #[serde(skip)]
     pub differential_data: Option<DifferentialData>,
     pub data: FilteredCubeData<GSTimestamp>,
+    /// Set when the graph is modified after the cube was created.
+    pub stale: bool,
+    /// The views the cube was created from. `None` if it was loaded or generated.
+    pub definition: Option<CollectionDefinition>,
+    /// The models fitted by the last adaptive run of each computation on the cube.
+    #[serde(skip)]
+    pub adaptive_models: HashMap<String, AdaptiveModel>,
+}
+
+/// The predicates and order of the views of a collection.
+#[derive(Clone, Debug, Serialize, Deserialize, new)]
+pub struct CollectionDefinition {
+    /// The predicates of the views of each dimension, as written in the query.
+    pub dimensions: Vec<Vec<String>>,
+    /// The predicates of the vertex views, with the same shape as `dimensions`. Empty if the
+    /// collection has no vertex views.
+    pub vertex_dimensions: Vec<Vec<String>>,
+    /// The order of the views of each dimension, i.e., the index of the view at each timestamp id.
+    pub orders: Vec<Vec<usize>>,
+    pub manual_order: bool,
+}
+
+impl CollectionDefinition {
+    /// Returns the predicates of the views at `timestamp`, joined across the dimensions.
+    pub fn get_view_string(&self, timestamp: GSTimestamp) -> String {
+        let total_dimensions = self.dimensions.len();
+        (0..total_dimensions)
+            .map(|dimension| {
+                let timestamp_id = timestamp.get_value_at(dimension, total_dimensions) as usize;
+                let view = self.orders[dimension][timestamp_id];
+                match self.vertex_dimensions.get(dimension) {
+                    Some(vertex_dimension) => format!(
+                        "[{}] vertices [{}]",
+                        self.dimensions[dimension][view], vertex_dimension[view]
+                    ),
+                    None => format!("[{}]", self.dimensions[dimension][view]),
+                }
+            })
+            .join(" by ")
+    }
+}
+
+/// The edge and diff counts over all the entries of a cube.
+#[derive(Default)]
+pub struct CubeTotals {
+    pub data: usize,
+    pub diffs: usize,
+    pub adds: usize,
+    pub dels: usize,
+    /// The diffs of all entries except the first, which holds the full first view.
+    pub partial_diffs: usize,
 }
 pub type DimensionLengths = Vec<DimensionLength>;
 pub type DimensionLength = DimensionId;
+
+/// Converts the number of views in a dimension to a `DimensionLength`.
+pub fn get_dimension_length(length: usize) -> Result<DimensionLength, GSError> {
+    DimensionLength::try_from(length).map_err(|_| {
+        GSError::Collection(format!(
+            "A dimension can have at most {} views, found {}",
+            DimensionLength::max_value(),
+            length
+        ))
+    })
+}
+
+/// Converts the number of views in each dimension to `DimensionLengths`, checking that the
+/// timestamps of the collection can hold them.
+pub fn get_dimension_lengths<I: ExactSizeIterator<Item = usize>>(
+    lengths: I,
+) -> Result<DimensionLengths, GSError> {
+    let max_length = GSTimestamp::max_dimension_length(lengths.len());
+    let total_dimensions = lengths.len();
+    lengths
+        .map(|length| {
+            if length > max_length {
+                return Err(GSError::Collection(format!(
+                    "A dimension of a collection with {} dimensions can have at most {} views, \
+                     found {}",
+                    total_dimensions, max_length, length
+                )));
+            }
+            get_dimension_length(length)
+        })
+        .collect()
+}
 pub type CubeDataEntries2<T> = (T, T, Vec<(SimpleEdge, DiffCount)>);
 pub type FilteredCubeEntriesEdgeId = (Vec<EdgeId>, Vec<(EdgeId, DiffCount)>);
 create_generic_pointer!(CubePointer, FilteredCubeData);
@@ -71,7 +210,50 @@ impl FilteredCube {
         differential_data: Option<DifferentialData>,
         data: FilteredCubeData<GSTimestamp>,
     ) -> Self {
-        Self { timestamp_mappings, dimension_lengths, differential_data, data }
+        Self {
+            timestamp_mappings,
+            dimension_lengths,
+            differential_data,
+            data,
+            stale: false,
+            definition: None,
+            adaptive_models: HashMap::new(),
+        }
+    }
+
+    pub fn get_totals(&self) -> CubeTotals {
+        let mut totals = CubeTotals::default();
+        let zeroth_timestamp = GSTimestamp::get_zeroth_timestamp();
+        for (_, timestamp, (full_edges, diff_edges), (adds, dels), _) in &self.data.entries {
+            totals.data += full_edges.len();
+            totals.diffs += diff_edges.len();
+            totals.adds += adds;
+            totals.dels += dels;
+            if *timestamp != zeroth_timestamp {
+                totals.partial_diffs += diff_edges.len();
+            }
+        }
+        totals
+    }
+
+    /// Returns the number of edges and vertices of the view of each entry. The sizes are summed
+    /// from the diffs over the timestamp lattice, so the full views need not be materialized.
+    pub fn get_full_view_sizes(&self) -> Vec<(isize, isize)> {
+        let mut edges = vec![0; self.timestamp_mappings.0.len()];
+        let mut vertices = vec![0; self.timestamp_mappings.0.len()];
+        for (timestamp_index, _, (_, diff_edges), _, (_, vertex_diffs)) in &self.data.entries {
+            edges[*timestamp_index] = diff_edges.iter().map(|(_, diff)| diff).sum();
+            vertices[*timestamp_index] = vertex_diffs.iter().map(|(_, diff)| diff).sum();
+        }
+        accumulate_diffs(&self.timestamp_mappings, &mut edges);
+        accumulate_diffs(&self.timestamp_mappings, &mut vertices);
+        self.data
+            .entries
+            .iter()
+            .map(|(timestamp_index, _, _, _, _)| {
+                (edges[*timestamp_index], vertices[*timestamp_index])
+            })
+            .collect()
     }
 
     pub fn prepare_differential_data(&mut self) {
@@ -88,7 +270,7 @@ impl FilteredCube {
         self.data
             .entries
             .iter()
-            .map(|(_, timestamp, (full_edges, _), _)| {
+            .map(|(_, timestamp, (full_edges, _), _, _)| {
                 format!(
                     "{}:\n\ttotal: {}, sample: {}",
                     timestamp,
@@ -103,43 +285,40 @@ impl FilteredCube {
 
 impl Display for FilteredCube {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(
+        writeln!(
             f,
-            "Timestamps count: {}\nDimensions:\n TODO(INFO)",
+            "Timestamps count: {}\nDimension lengths: {:?}",
             self.timestamp_mappings.0.len(),
-            //            self.dimensions
-            //                .iter()
-            //                .map(|(k, (t, v))| format!(
-            //                    "{} {{\n\ttype '{}';\n\t{};\n}}",
-            //                    k,
-            //                    t,
-            //                    v.iter()
-            //                        .map(|(k, v)| format!(
-            //                            "{}: {}",
-            //                            k,
-            //                            v.iter()
-            //                                .map(|(ctype, c)| format!(
-            //                                    "{}{}{}",
-            //                                    if *ctype { "!(" } else { "" },
-            //                                    c.iter()
-            //                                        .map(|(op1, c, op2)| format!(
-            //                                            "{} {} {}",
-            //                                            op1.to_string(),
-            //                                            c.as_string(),
-            //                                            op2.to_string()
-            //                                        ))
-            //                                        .collect::<Vec<String>>()
-            //                                        .join(" and "),
-            //                                    if *ctype { ")" } else { "" },
-            //                                ))
-            //                                .collect::<Vec<String>>()
-            //                                .join(" and ")
-            //                        ))
-            //                        .collect::<Vec<String>>()
-            //                        .join(";\n\t"),
-            //                ))
-            //                .collect::<Vec<String>>()
-            //                .join(",\n"),
-        )
+            self.dimension_lengths
+        )?;
+        match &self.definition {
+            Some(definition) => writeln!(
+                f,
+                "Order: {}",
+                if definition.manual_order { "manual" } else { "computed" }
+            )?,
+            None => writeln!(f, "Order: unknown (not created from view predicates)")?,
+        }
+        writeln!(f, "Views:")?;
+        for ((_, timestamp, (_, diff_edges), (adds, dels), _), (full_edges, _)) in
+            self.data.entries.iter().zip(self.get_full_view_sizes())
+        {
+            write!(f, "  {}: ", timestamp)?;
+            if let Some(definition) = &self.definition {
+                write!(f, "{} ", definition.get_view_string(*timestamp))?;
+            }
+            writeln!(
+                f,
+                "(full edges: {}, diffs: {} + {} = {})",
+                full_edges,
+                adds,
+                dels,
+                diff_edges.len()
+            )?;
+        }
+        let totals = self.get_totals();
+        writeln!(f, "Total data = {}", totals.data)?;
+        writeln!(f, "Total diffs = {} + {} = {}", totals.adds, totals.dels, totals.diffs)?;
+        write!(f, "Partial diffs = {}", totals.partial_diffs)
     }
 }
@@ -1,9 +1,16 @@
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
-use crate::filtered_cubes::{DimensionLengths, FilteredCube, FilteredCubeData};
-use crate::global_store::{deserialize_object, serialize_object};
+use crate::filtered_cubes::{
+    CollectionDefinition, DimensionLengths, FilteredCube, FilteredCubeData,
+};
+use crate::global_store::{
+    check_format_version, check_id_size, deserialize_object, serialize_format_version,
+    serialize_id_size, serialize_object,
+};
 use crate::graph::serde::{deserialize_blocks, serialize_blocks};
 use crossbeam_utils::thread;
+use gs_analytics_api::EntryPayloads;
+use hashbrown::HashMap;
 use log::info;
 
 const SERDE_FILE: &str = "cube";
@@ -11,6 +18,14 @@ const SERDE_FILE_CUBE_BLOCK_COUNT: &str = "cube_block_count";
 const SERDE_FILE_CUBE_LEN: &str = "cube_len";
 const SERDE_FILE_DIMENSION_LENGTHS: &str = "cube_dimension_lengths";
 const SERDE_FILE_NAME: &str = "cube_name";
+const SERDE_FILE_EDGE_PROPERTIES: &str = "cube_edge_properties";
+const SERDE_FILE_PAYLOADS: &str = "cube_payloads";
+const SERDE_FILE_HAS_VERTEX_VIEWS: &str = "cube_has_vertex_views";
+const SERDE_FILE_HAS_FULL_VIEWS: &str = "cube_has_full_views";
+const SERDE_FILE_STALE: &str = "cube_stale";
+const SERDE_FILE_ID_SIZE: &str = "cube_id_size";
+const SERDE_FILE_FORMAT_VERSION: &str = "cube_format_version";
+const SERDE_FILE_DEFINITION: &str = "cube_definition";
 const MIN_BLOCK_SIZE: usize = 1; // Data is allocated per timestamp.
 
 pub fn serialize(
@@ -38,6 +53,14 @@ pub fn serialize(
 
         serialize_object(bin_dir, SERDE_FILE_DIMENSION_LENGTHS, &cube.dimension_lengths)?;
         serialize_object(bin_dir, SERDE_FILE_NAME, &name)?;
+        serialize_object(bin_dir, SERDE_FILE_EDGE_PROPERTIES, &cube.data.edge_properties)?;
+        serialize_object(bin_dir, SERDE_FILE_PAYLOADS, &cube.data.payloads)?;
+        serialize_object(bin_dir, SERDE_FILE_HAS_VERTEX_VIEWS, &cube.data.has_vertex_views)?;
+        serialize_object(bin_dir, SERDE_FILE_HAS_FULL_VIEWS, &cube.data.has_full_views)?;
+        serialize_object(bin_dir, SERDE_FILE_STALE, &cube.stale)?;
+        serialize_object(bin_dir, SERDE_FILE_DEFINITION, &cube.definition)?;
+        serialize_id_size(bin_dir, SERDE_FILE_ID_SIZE)?;
+        serialize_format_version(bin_dir, SERDE_FILE_FORMAT_VERSION)?;
 
         data_thread.join().expect("Error joining data_thread")?;
 
@@ -49,7 +72,19 @@ pub fn serialize(
 }
 
 pub fn deserialize(bin_dir: &str, thread_count: usize) -> Result<(String, FilteredCube), GSError> {
-    let (dimension_lengths, name, data) = thread::scope(|s| {
+    check_format_version(bin_dir, SERDE_FILE_FORMAT_VERSION)?;
+    check_id_size(bin_dir, SERDE_FILE_ID_SIZE)?;
+    let (
+        dimension_lengths,
+        name,
+        edge_properties,
+        payloads,
+        has_vertex_views,
+        has_full_views,
+        stale,
+        definition,
+        data,
+    ) = thread::scope(|s| {
         let data_thread = s.spawn(|r| {
             deserialize_blocks(
                 r,
@@ -65,20 +100,43 @@ pub fn deserialize(bin_dir: &str, thread_count: usize) -> Result<(String, Filter
         let dimension_lengths: DimensionLengths =
             deserialize_object(bin_dir, SERDE_FILE_DIMENSION_LENGTHS)?;
         let name: String = deserialize_object(bin_dir, SERDE_FILE_NAME)?;
+        let edge_properties: Vec<String> = deserialize_object(bin_dir, SERDE_FILE_EDGE_PROPERTIES)?;
+        let payloads: Vec<EntryPayloads> = deserialize_object(bin_dir, SERDE_FILE_PAYLOADS)?;
+        let has_vertex_views: bool = deserialize_object(bin_dir, SERDE_FILE_HAS_VERTEX_VIEWS)?;
+        let has_full_views: bool = deserialize_object(bin_dir, SERDE_FILE_HAS_FULL_VIEWS)?;
+        let stale: bool = deserialize_object(bin_dir, SERDE_FILE_STALE)?;
+        let definition: Option<CollectionDefinition> =
+            deserialize_object(bin_dir, SERDE_FILE_DEFINITION)?;
 
         let data = data_thread.join().expect("Error joining data_thread")?;
 
-        Ok((dimension_lengths, name, data))
+        Ok((
+            dimension_lengths,
+            name,
+            edge_properties,
+            payloads,
+            has_vertex_views,
+            has_full_views,
+            stale,
+            definition,
+            data,
+        ))
     })
     .expect("Error ending serialization scope")?;
     info!("Deserialized {} timestamps of cube", data.len());
+    let mut cube_data = FilteredCubeData::with_payloads(data, edge_properties, payloads);
+    cube_data.has_vertex_views = has_vertex_views;
+    cube_data.has_full_views = has_full_views;
     Ok((
         name,
         FilteredCube {
             timestamp_mappings: get_timestamp_mappings(&dimension_lengths),
             dimension_lengths,
             differential_data: None,
-            data: FilteredCubeData::new(data),
+            data: cube_data,
+            stale,
+            definition,
+            adaptive_models: HashMap::new(),
         },
     ))
 }
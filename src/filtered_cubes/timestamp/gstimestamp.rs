@@ -4,20 +4,29 @@ use abomonation_derive::Abomonation;
 use differential_dataflow::lattice::Lattice;
 use gs_analytics_api::GsTs;
 use itertools::Itertools;
+use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use timely::progress::timestamp::Refines;
 use timely::progress::PathSummary;
 
-#[derive(
-    PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy, Abomonation, Hash, Serialize, Deserialize,
-)]
-pub struct GSTimestamp3D {
-    id: TimestampId,
+/// A lattice timestamp with as many dimensions as its collection, chosen at runtime.
+///
+/// The dimension ids are packed in reverse order into a single word, along with the number of
+/// dimensions, so that the timestamp stays `Copy` and as small as a 1-dimensional one. The ids of
+/// a timestamp with more dimensions get fewer bits each (see `max_dimension_length`). A timestamp
+/// with fewer dimensions equals the one with zeros in the leading dimensions.
+#[derive(Default, Clone, Copy, Abomonation, Serialize, Deserialize)]
+pub struct GSTimestamp {
+    packed: u64,
 }
+/// The dimension ids in reverse order, padded with zeros.
 pub type TimestampId = [DimensionId; MAX_DIMENSIONS];
-const MAX_DIMENSIONS: usize = 3;
+pub const MAX_DIMENSIONS: usize = 8;
+const LEN_SHIFT: u32 = 60;
+const ID_BITS: u32 = LEN_SHIFT;
 
-impl GsTs for GSTimestamp3D {
+impl GsTs for GSTimestamp {
     fn next(&self) -> Self {
         let mut next = *self;
         next.set_value_at(0, 1, next.get_value_at(0, 1) + 1);
@@ -25,7 +34,7 @@ impl GsTs for GSTimestamp3D {
     }
 }
 
-impl GSTimestamp3D {
+impl GSTimestamp {
     pub fn new(dimension_ids: &[DimensionId]) -> Self {
         let len = dimension_ids.len();
         assert!(
@@ -33,15 +42,31 @@ impl GSTimestamp3D {
             "Total dimensions should be between 1 and {}",
             MAX_DIMENSIONS
         );
-        let mut ts = Self { id: TimestampId::default() };
+        let mut id = TimestampId::default();
         for (index, dimension_id) in dimension_ids.iter().rev().enumerate() {
-            ts.id[index] = *dimension_id;
+            id[index] = *dimension_id;
         }
-        ts
+        Self::pack(&id, len)
     }
 
     pub fn get_zeroth_timestamp() -> Self {
-        Self { id: TimestampId::default() }
+        Self::default()
+    }
+
+    /// The largest number of views a dimension of a timestamp with `total_dimensions` dimensions
+    /// can have.
+    pub fn max_dimension_length(total_dimensions: usize) -> usize {
+        let bits = Self::id_bits(total_dimensions);
+        if bits >= DimensionId::BITS {
+            DimensionId::MAX as usize
+        } else {
+            1 << bits
+        }
+    }
+
+    /// The number of dimensions of the timestamp.
+    pub fn total_dimensions(self) -> usize {
+        (self.packed >> LEN_SHIFT) as usize
     }
 
     pub fn get_value_at(self, index: usize, len: usize) -> DimensionId {
@@ -51,7 +76,7 @@ impl GSTimestamp3D {
             MAX_DIMENSIONS
         );
         assert!(index < len, "Dimension id ({}) should be less than length ({})", index, len);
-        self.id[len - 1 - index]
+        self.ids()[len - 1 - index]
     }
 
     pub fn set_value_at(&mut self, index: usize, len: usize, timestamp_id: DimensionId) {
@@ -61,24 +86,63 @@ impl GSTimestamp3D {
             MAX_DIMENSIONS
         );
         assert!(index < len, "Dimension id ({}) should be less than length ({})", index, len);
-        self.id[len - 1 - index] = timestamp_id;
+        let mut id = self.ids();
+        id[len - 1 - index] = timestamp_id;
+        *self = Self::pack(&id, std::cmp::max(self.total_dimensions(), len));
+    }
+
+    /// The number of bits of each dimension id of a timestamp with `len` dimensions.
+    fn id_bits(len: usize) -> u32 {
+        std::cmp::min(ID_BITS / std::cmp::max(len, 1) as u32, DimensionId::BITS)
+    }
+
+    fn pack(id: &TimestampId, len: usize) -> Self {
+        let bits = Self::id_bits(len);
+        let mut packed = (len as u64) << LEN_SHIFT;
+        for (index, &dimension_id) in id.iter().enumerate().take(len) {
+            assert!(
+                u64::from(dimension_id) >> bits == 0,
+                "Dimension id ({}) does not fit in a timestamp with {} dimensions",
+                dimension_id,
+                len
+            );
+            packed |= u64::from(dimension_id) << (bits * index as u32);
+        }
+        for &dimension_id in &id[len..] {
+            assert_eq!(dimension_id, 0, "Dimension ids past the length should be zero");
+        }
+        Self { packed }
+    }
+
+    /// Returns the dimension ids in reverse order, padded with zeros.
+    #[inline(always)]
+    fn ids(self) -> TimestampId {
+        let len = self.total_dimensions();
+        let bits = Self::id_bits(len);
+        let mask = (1_u64 << bits) - 1;
+        let mut id = TimestampId::default();
+        for (index, dimension_id) in id.iter_mut().enumerate().take(len) {
+            *dimension_id = ((self.packed >> (bits * index as u32)) & mask) as DimensionId;
+        }
+        id
     }
 
     /// Get the neighborhood timestamps based on the inclusion-exclusion principle.
     pub fn get_diff_neighborhood(self) -> (Vec<Self>, Vec<Self>) {
         let mut diff_neighborhood = (Vec::new(), Vec::new());
-        for level in 1..=MAX_DIMENSIONS {
+        for level in 1..=self.total_dimensions() {
             // Get indices that need to be decremented `level` at-a-time.
-            'outer: for indices_to_decrement in (0..MAX_DIMENSIONS).combinations(level) {
-                let mut previous_timestamp = self;
+            'outer: for indices_to_decrement in (0..self.total_dimensions()).combinations(level) {
+                let mut previous_id = self.ids();
                 for index_to_decrement in indices_to_decrement {
-                    let timestamp_value = previous_timestamp.id[index_to_decrement];
+                    let timestamp_value = previous_id[index_to_decrement];
                     if timestamp_value == 0 {
                         // Previous timestamp does not exist.
                         continue 'outer;
                     }
-                    previous_timestamp.id[index_to_decrement] = timestamp_value - 1;
+                    previous_id[index_to_decrement] = timestamp_value - 1;
                 }
+                let previous_timestamp = Self::pack(&previous_id, self.total_dimensions());
                 if level % 2 == 1 {
                     // Add the previous sum.
                     diff_neighborhood.0.push(previous_timestamp);
@@ -91,48 +155,80 @@ impl GSTimestamp3D {
         diff_neighborhood
     }
 
-    pub fn all_timestamps(dimension_lengths: &[DimensionLength]) -> Vec<GSTimestamp3D> {
+    pub fn all_timestamps(dimension_lengths: &[DimensionLength]) -> Vec<GSTimestamp> {
         dimension_lengths
             .iter()
             .map(|&dimension_length| 0..dimension_length)
             .multi_cartesian_product()
-            .map(|values| GSTimestamp3D::new(&values))
+            .map(|values| GSTimestamp::new(&values))
             .collect_vec()
     }
 
     pub fn get_str(self, sep: char) -> String {
-        format!("{}{sep}{}{sep}{}", self.id[2], self.id[1], self.id[0], sep = sep)
+        let len = std::cmp::max(self.total_dimensions(), 1);
+        self.ids()[..len].iter().rev().join(&sep.to_string())
+    }
+}
+
+impl PartialEq for GSTimestamp {
+    fn eq(&self, other: &Self) -> bool {
+        // Timestamps with the same number of dimensions are packed the same way.
+        if self.total_dimensions() == other.total_dimensions() {
+            return self.packed == other.packed;
+        }
+        self.ids() == other.ids()
     }
 }
 
-impl std::fmt::Display for GSTimestamp3D {
+impl Eq for GSTimestamp {}
+
+impl Hash for GSTimestamp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ids().hash(state);
+    }
+}
+
+impl PartialOrd for GSTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GSTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ids().cmp(&other.ids())
+    }
+}
+
+impl std::fmt::Display for GSTimestamp {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "[{}]", self.get_str(','))
     }
 }
 
-impl Debug for GSTimestamp3D {
+impl Debug for GSTimestamp {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "[{}]", self.get_str(','))
     }
 }
 
-impl timely::PartialOrder for GSTimestamp3D {
+impl timely::PartialOrder for GSTimestamp {
     fn less_equal(&self, other: &Self) -> bool {
-        unsafe {
-            self.id.get_unchecked(0) <= other.id.get_unchecked(0)
-                && self.id.get_unchecked(1) <= other.id.get_unchecked(1)
-                && self.id.get_unchecked(2) <= other.id.get_unchecked(2)
+        if self.total_dimensions() <= 1 && other.total_dimensions() <= 1 {
+            // Both ids are in the lowest bits.
+            return self.packed & !(u64::MAX << LEN_SHIFT)
+                <= other.packed & !(u64::MAX << LEN_SHIFT);
         }
+        self.ids().iter().zip(other.ids().iter()).all(|(left, right)| left <= right)
     }
 }
 
-impl timely::progress::Timestamp for GSTimestamp3D {
+impl timely::progress::Timestamp for GSTimestamp {
     type Summary = ();
 }
 
-impl PathSummary<GSTimestamp3D> for () {
-    fn results_in(&self, src: &GSTimestamp3D) -> Option<GSTimestamp3D> {
+impl PathSummary<GSTimestamp> for () {
+    fn results_in(&self, src: &GSTimestamp) -> Option<GSTimestamp> {
         Some(*src)
     }
 
@@ -141,48 +237,40 @@ impl PathSummary<GSTimestamp3D> for () {
     }
 }
 
-impl Refines<()> for GSTimestamp3D {
+impl Refines<()> for GSTimestamp {
     fn to_inner(_outer: ()) -> Self {
-        GSTimestamp3D::default()
+        GSTimestamp::default()
     }
     fn to_outer(self) {}
     fn summarize(_summary: <Self>::Summary) {}
 }
 
-impl Lattice for GSTimestamp3D {
+impl Lattice for GSTimestamp {
     fn minimum() -> Self {
-        GSTimestamp3D::default()
+        GSTimestamp::default()
     }
 
     fn join(&self, other: &Self) -> Self {
-        unsafe {
-            Self {
-                id: [
-                    std::cmp::max(*self.id.get_unchecked(0), *other.id.get_unchecked(0)),
-                    std::cmp::max(*self.id.get_unchecked(1), *other.id.get_unchecked(1)),
-                    std::cmp::max(*self.id.get_unchecked(2), *other.id.get_unchecked(2)),
-                ],
-            }
+        let mut id = self.ids();
+        for (value, other_value) in id.iter_mut().zip(other.ids().iter()) {
+            *value = std::cmp::max(*value, *other_value);
         }
+        Self::pack(&id, std::cmp::max(self.total_dimensions(), other.total_dimensions()))
     }
 
     fn meet(&self, other: &Self) -> Self {
-        unsafe {
-            Self {
-                id: [
-                    std::cmp::min(*self.id.get_unchecked(0), *other.id.get_unchecked(0)),
-                    std::cmp::min(*self.id.get_unchecked(1), *other.id.get_unchecked(1)),
-                    std::cmp::min(*self.id.get_unchecked(2), *other.id.get_unchecked(2)),
-                ],
-            }
+        let mut id = self.ids();
+        for (value, other_value) in id.iter_mut().zip(other.ids().iter()) {
+            *value = std::cmp::min(*value, *other_value);
         }
+        Self::pack(&id, std::cmp::max(self.total_dimensions(), other.total_dimensions()))
     }
 }
 
 #[cfg(test)]
 #[allow(clippy::decimal_literal_representation)]
 mod tests {
-    use super::GSTimestamp3D;
+    use super::GSTimestamp;
     use differential_dataflow::lattice::Lattice;
     use hashbrown::HashMap;
     use itertools::Itertools;
@@ -191,42 +279,80 @@ mod tests {
     #[test]
     fn test_basic_timestamp_operations() {
         let timestamp = vec![10, 2000, 65535];
-        let ts = GSTimestamp3D::new(&timestamp);
-        let expected_ts_id = [65535, 2000, 10];
-        assert_eq!(ts.id, expected_ts_id);
+        let ts = GSTimestamp::new(&timestamp);
+        let expected_ts_id = [65535, 2000, 10, 0, 0, 0, 0, 0];
+        assert_eq!(ts.ids(), expected_ts_id);
+        assert_eq!(ts.total_dimensions(), 3);
+        assert_eq!(ts.to_string(), "[10,2000,65535]");
+        assert_eq!(GSTimestamp::new(&[7]).to_string(), "[7]");
+        assert_eq!(GSTimestamp::new(&[0, 0, 7]), GSTimestamp::new(&[7]));
+    }
+
+    #[test]
+    fn test_packed_timestamps() {
+        assert_eq!(std::mem::size_of::<GSTimestamp>(), 8);
+        let ts = GSTimestamp::new(&[u32::MAX]);
+        assert_eq!(ts.get_value_at(0, 1), u32::MAX);
+        assert!(GSTimestamp::new(&[7]).less_equal(&ts));
+
+        // Ids of timestamps with more dimensions get fewer bits.
+        assert_eq!(GSTimestamp::max_dimension_length(1), u32::MAX as usize);
+        assert_eq!(GSTimestamp::max_dimension_length(2), 1 << 30);
+        assert_eq!(GSTimestamp::max_dimension_length(8), 1 << 7);
+        let mut ts = GSTimestamp::new(&[5]);
+        ts.set_value_at(0, 2, (1 << 30) - 1);
+        assert_eq!(ts.to_string(), "[1073741823,5]");
+        assert_eq!(ts.get_value_at(1, 2), 5);
+        let result = std::panic::catch_unwind(|| GSTimestamp::new(&[0, 0, 0, 0, 0, 0, 0, 128]));
+        assert!(result.is_err(), "Ids that do not fit should panic.");
     }
 
     #[test]
     fn test_diff_neighborhood() {
-        let ts = GSTimestamp3D::new(&[10, 2000, 65535]);
+        let ts = GSTimestamp::new(&[10, 2000, 65535]);
         let diff_neighborhood = ts.get_diff_neighborhood();
         let expected_positive_neighbors =
             [[10, 2000, 65534], [10, 1999, 65535], [9, 2000, 65535], [9, 1999, 65534]]
                 .iter()
-                .map(|values| GSTimestamp3D::new(values))
+                .map(|values| GSTimestamp::new(values))
                 .collect_vec();
         assert_eq!(diff_neighborhood.0, expected_positive_neighbors);
         let expected_negative_neighbors = [[10, 1999, 65534], [9, 2000, 65534], [9, 1999, 65535]]
             .iter()
-            .map(|values| GSTimestamp3D::new(values))
+            .map(|values| GSTimestamp::new(values))
             .collect_vec();
         assert_eq!(diff_neighborhood.1, expected_negative_neighbors);
     }
 
+    #[test]
+    fn test_diff_neighborhood_runtime_dimensions() {
+        let (positive, negative) = GSTimestamp::new(&[10]).get_diff_neighborhood();
+        assert_eq!(positive, vec![GSTimestamp::new(&[9])]);
+        assert!(negative.is_empty());
+
+        // Subsets of 1, 3 and 5 dimensions are added, and subsets of 2 and 4 are subtracted.
+        let (positive, negative) = GSTimestamp::new(&[1, 1, 1, 1, 1]).get_diff_neighborhood();
+        assert_eq!(positive.len(), 5 + 10 + 1);
+        assert_eq!(negative.len(), 10 + 5);
+        assert!(positive.contains(&GSTimestamp::new(&[0, 0, 0, 0, 0])));
+        assert!(negative.contains(&GSTimestamp::new(&[1, 0, 0, 1, 1])));
+    }
+
     /// Tests that creating timestamp with illegal dimensions panics.
     #[test]
     fn test_illegal_timestamps() {
-        let result = std::panic::catch_unwind(|| GSTimestamp3D::new(&[3, 2, 367, 99, 223]));
+        let result =
+            std::panic::catch_unwind(|| GSTimestamp::new(&[3, 2, 367, 99, 223, 1, 2, 3, 4]));
         assert!(result.is_err(), "Dimensions larger than `MAX_DIMENSIONS` should panic.");
 
-        let result = std::panic::catch_unwind(|| GSTimestamp3D::new(&[]));
+        let result = std::panic::catch_unwind(|| GSTimestamp::new(&[]));
         assert!(result.is_err(), "Empty dimensions should panic.");
     }
 
     #[test]
     fn test_generating_all_timestamps() {
         let dimension_lengths = [3, 2, 4];
-        let all_timestamps = GSTimestamp3D::all_timestamps(&dimension_lengths);
+        let all_timestamps = GSTimestamp::all_timestamps(&dimension_lengths);
         let expected_timestamps = [
             [0, 0, 0],
             [0, 0, 1],
@@ -254,7 +380,7 @@ mod tests {
             [2, 1, 3],
         ]
         .iter()
-        .map(|values| GSTimestamp3D::new(values))
+        .map(|values| GSTimestamp::new(values))
         .collect_vec();
         assert_eq!(all_timestamps, expected_timestamps)
     }
@@ -262,13 +388,13 @@ mod tests {
     #[test]
     fn test_partial_order() {
         let dimension_lengths = [3, 2, 4];
-        let all_timestamps = GSTimestamp3D::all_timestamps(&dimension_lengths);
+        let all_timestamps = GSTimestamp::all_timestamps(&dimension_lengths);
         let previous_timestamps = all_timestamps
             .iter()
             .map(|timestamp| {
                 (
                     timestamp.get_str('_'),
-                    GSTimestamp3D::all_timestamps(&[10, 10, 10])
+                    GSTimestamp::all_timestamps(&[10, 10, 10])
                         .iter()
                         .copied()
                         .filter_map(|previous_timestamp| {
@@ -471,12 +597,7 @@ mod tests {
         ]
         .iter()
         .map(|(t1, t2, t3, t4)| {
-            (
-                GSTimestamp3D::new(t1),
-                GSTimestamp3D::new(t2),
-                GSTimestamp3D::new(t3),
-                GSTimestamp3D::new(t4),
-            )
+            (GSTimestamp::new(t1), GSTimestamp::new(t2), GSTimestamp::new(t3), GSTimestamp::new(t4))
         })
         .enumerate()
         {
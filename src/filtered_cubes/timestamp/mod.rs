@@ -1,11 +1,6 @@
-mod gstimestamp_1d;
-#[cfg(feature = "nd-timestamps")]
-mod gstimestamp_3d;
+mod gstimestamp;
 pub mod timestamp_mappings;
 
-pub type DimensionId = u16;
+pub type DimensionId = u32;
 
-#[cfg(not(feature = "nd-timestamps"))]
-pub use self::gstimestamp_1d::GSTimestamp1D as GSTimestamp;
-#[cfg(feature = "nd-timestamps")]
-pub use self::gstimestamp_3d::GSTimestamp3D as GSTimestamp;
+pub use self::gstimestamp::{GSTimestamp, MAX_DIMENSIONS};
@@ -1,6 +1,6 @@
 use crate::filtered_cubes::timestamp::GSTimestamp;
 use crate::filtered_cubes::DimensionLength;
-use gs_analytics_api::GsTimestampIndex;
+use gs_analytics_api::{DiffCount, GsTimestampIndex};
 use hashbrown::HashMap;
 use itertools::Itertools;
 
@@ -25,6 +25,30 @@ pub fn get_timestamp_mappings(dimension_lengths: &[DimensionLength]) -> Timestam
     timestamp_mappings
 }
 
+/// Turns the diffs at each timestamp index into the totals of the views, by adding the totals of
+/// the neighbourhood of each timestamp. The neighbourhood only has timestamps that come before it.
+pub fn accumulate_diffs(timestamp_mappings: &TimestampMappings, values: &mut [DiffCount]) {
+    for (timestamp_index, ((add_indices, subtract_indices), _)) in
+        timestamp_mappings.0.iter().enumerate()
+    {
+        let added: DiffCount = add_indices.iter().map(|&index| values[index]).sum();
+        let subtracted: DiffCount = subtract_indices.iter().map(|&index| values[index]).sum();
+        values[timestamp_index] += added - subtracted;
+    }
+}
+
+/// Returns the diff at `timestamp_index`, given the totals of the views. Inverts
+/// `accumulate_diffs`.
+pub fn get_diff(
+    timestamp_mappings: &TimestampMappings,
+    totals: &[DiffCount],
+    timestamp_index: GsTimestampIndex,
+) -> DiffCount {
+    let (add_indices, subtract_indices) = &timestamp_mappings.0[timestamp_index].0;
+    totals[timestamp_index] - add_indices.iter().map(|&index| totals[index]).sum::<DiffCount>()
+        + subtract_indices.iter().map(|&index| totals[index]).sum::<DiffCount>()
+}
+
 fn map_timestamp_to_index(
     timestamps: &[GSTimestamp],
     index_map: &TimestampToIndexMap,
@@ -38,9 +62,9 @@ fn map_timestamp_to_index(
 #[cfg(test)]
 mod tests {
     use crate::filtered_cubes::timestamp::timestamp_mappings::{
-        get_timestamp_mappings, DiffNeighborhood,
+        accumulate_diffs, get_diff, get_timestamp_mappings, DiffNeighborhood,
     };
-    use crate::filtered_cubes::DimensionLength;
+    use crate::filtered_cubes::{get_dimension_length, DimensionLength};
     use itertools::Itertools;
 
     #[test]
@@ -58,7 +82,6 @@ mod tests {
         assert_mappings(&dimension_lengths, &expected_data);
     }
 
-    #[cfg(feature = "nd-timestamps")]
     #[test]
     fn test2() {
         let dimension_lengths = vec![3, 3];
@@ -78,6 +101,34 @@ mod tests {
         assert_mappings(&dimension_lengths, &expected_data);
     }
 
+    #[test]
+    fn test_large_dimension() {
+        let views = 70_000;
+        let dimension_length = get_dimension_length(views).expect("Dimension should fit");
+        let mappings = get_timestamp_mappings(&[dimension_length]);
+        assert_eq!(mappings.0.len(), views);
+        let (neighborhood, timestamp) = &mappings.0[views - 1];
+        assert_eq!(neighborhood, &(vec![views - 2], vec![]));
+        assert_eq!(timestamp.get_value_at(0, 1), dimension_length - 1);
+
+        let error = get_dimension_length(usize::max_value()).expect_err("Should overflow");
+        assert!(error.to_string().contains("at most"));
+    }
+
+    #[test]
+    fn test_accumulate_diffs() {
+        let mappings = get_timestamp_mappings(&[2, 3]);
+        let diffs = vec![3, 1, -2, 0, 1, 4];
+        let mut totals = diffs.clone();
+        accumulate_diffs(&mappings, &mut totals);
+        // Each total is the diff plus the totals below it in both dimensions, counting their
+        // shared predecessor once.
+        assert_eq!(totals, vec![3, 4, 2, 3, 5, 7]);
+        let round_trip =
+            (0..totals.len()).map(|index| get_diff(&mappings, &totals, index)).collect_vec();
+        assert_eq!(round_trip, diffs);
+    }
+
     fn assert_mappings(
         dimension_lengths: &[DimensionLength],
         expected_mapping: &[DiffNeighborhood],
@@ -1,11 +1,13 @@
 use crate::computations::builder::{initialize_computations, ComputationBuilder};
 use crate::error::GSError;
 use crate::filtered_cubes::FilteredCubeStore;
+use crate::graph::indexes::IndexStore;
 use crate::graph::key_store::KeyStore;
 use crate::graph::Graph;
 use crate::util::io::{get_buf_reader, GsWriter};
 use crate::util::timer::GsTimer;
 use crate::GraphSurgeResult;
+use gs_analytics_api::VertexId;
 use hashbrown::HashMap;
 use log::info;
 use serde::de::DeserializeOwned;
@@ -14,13 +16,21 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 
 const SERDE_FILE_KEY_STORE: &str = "key_store";
+const SERDE_FILE_VERTEX_IDS: &str = "vertex_ids";
 const SERDE_FILE_FILTERED_CUBES: &str = "filtered_cubes";
+const SERDE_FILE_INDEXES: &str = "indexes";
 const SERDE_FILE_EXTENSION: &str = "bin";
+/// The version of the layout of serialized graphs and collections. Data with another version
+/// cannot be read, so it must be incremented whenever the layout changes.
+const SERDE_FORMAT_VERSION: u32 = 1;
 
 pub struct GlobalStore {
     pub graph: Graph,
     pub key_store: KeyStore,
+    /// Maps the vertex id strings of the loaded files to the `VertexId`s in `graph`.
+    pub vertex_ids: HashMap<String, VertexId>,
     pub filtered_cube_store: FilteredCubeStore,
+    pub index_store: IndexStore,
     pub computations: HashMap<String, Box<dyn ComputationBuilder>>,
     pub threads: NonZeroUsize,
     pub process_id: usize,
@@ -31,7 +41,9 @@ impl Default for GlobalStore {
         let mut global_store = GlobalStore {
             graph: Graph::default(),
             key_store: KeyStore::default(),
+            vertex_ids: HashMap::new(),
             filtered_cube_store: FilteredCubeStore::default(),
+            index_store: IndexStore::default(),
             computations: HashMap::new(),
             threads: NonZeroUsize::new(1).expect("Unreachable"),
             process_id: 0,
@@ -45,7 +57,9 @@ impl GlobalStore {
     fn reset(&mut self) {
         self.graph.reset();
         self.key_store.reset();
+        self.vertex_ids.clear();
         self.filtered_cube_store.reset();
+        self.index_store.reset();
         self.computations.clear();
         self.threads = NonZeroUsize::new(1).expect("Unreachable");
         initialize_computations(&mut self.computations);
@@ -62,7 +76,9 @@ impl GlobalStore {
         }
         self.graph.serialize(bin_dir, thread_count, block_size)?;
         serialize_object(bin_dir, SERDE_FILE_KEY_STORE, &self.key_store)?;
+        serialize_object(bin_dir, SERDE_FILE_VERTEX_IDS, &self.vertex_ids)?;
         serialize_object(bin_dir, SERDE_FILE_FILTERED_CUBES, &self.filtered_cube_store)?;
+        serialize_object(bin_dir, SERDE_FILE_INDEXES, &self.index_store)?;
         Ok(GraphSurgeResult::new("Serialization done.".to_owned()))
     }
 
@@ -89,7 +105,9 @@ impl GlobalStore {
     ) -> Result<GraphSurgeResult, GSError> {
         self.graph.deserialize(bin_dir, thread_count)?;
         self.key_store = deserialize_object(bin_dir, SERDE_FILE_KEY_STORE)?;
+        self.vertex_ids = deserialize_object(bin_dir, SERDE_FILE_VERTEX_IDS)?;
         self.filtered_cube_store = deserialize_object(bin_dir, SERDE_FILE_FILTERED_CUBES)?;
+        self.index_store = deserialize_object(bin_dir, SERDE_FILE_INDEXES)?;
         Ok(GraphSurgeResult::new("Deserialization done.".to_owned()))
     }
 }
@@ -120,6 +138,49 @@ pub fn deserialize_object<T: DeserializeOwned>(bin_dir: &str, name: &str) -> Res
     Ok(object)
 }
 
+/// Records the version of the layout of the serialized data.
+pub fn serialize_format_version(bin_dir: &str, name: &str) -> Result<(), GSError> {
+    serialize_object(bin_dir, name, &SERDE_FORMAT_VERSION)
+}
+
+/// Checks that the data was serialized with the layout of this build. Data serialized before the
+/// version was recorded has version 0.
+pub fn check_format_version(bin_dir: &str, name: &str) -> Result<(), GSError> {
+    let version = if Path::new(&get_file_path(bin_dir, name)).is_file() {
+        deserialize_object(bin_dir, name)?
+    } else {
+        0
+    };
+    if version == SERDE_FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(GSError::UnsupportedFormatVersion(bin_dir.to_owned(), version, SERDE_FORMAT_VERSION))
+    }
+}
+
+/// Records the size of the vertex and edge ids, which the layout of the serialized data depends on.
+pub fn serialize_id_size(bin_dir: &str, name: &str) -> Result<(), GSError> {
+    serialize_object(bin_dir, name, &std::mem::size_of::<VertexId>())
+}
+
+/// Checks that the data was serialized with ids of the same size as the ones of this build.
+pub fn check_id_size(bin_dir: &str, name: &str) -> Result<(), GSError> {
+    let id_size: usize = deserialize_object(bin_dir, name)?;
+    let expected_id_size = std::mem::size_of::<VertexId>();
+    if id_size == expected_id_size {
+        Ok(())
+    } else {
+        Err(GSError::Deserialize(
+            name.to_owned(),
+            format!(
+                "Data has {}-byte ids, but this build uses {}-byte ids. Toggle the `large-ids` \
+                feature to read it",
+                id_size, expected_id_size
+            ),
+        ))
+    }
+}
+
 fn get_file_path(bin_dir: &str, name: &str) -> String {
     format!("{}/{}.{}", bin_dir, name, SERDE_FILE_EXTENSION)
 }
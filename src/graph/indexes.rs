@@ -0,0 +1,359 @@
+use crate::graph::properties::operations::{Operator, RightOperand};
+use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::property_value_type::PropertyValueType;
+use crate::graph::properties::PropertyKeyId;
+use crate::graph::{Graph, VertexOrEdge, VertexOrEdgeId};
+use gs_analytics_api::{EdgeId, VertexId};
+use hashbrown::{HashMap, HashSet};
+use itertools::Itertools;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum IndexType {
+    /// Answers comparisons and list membership.
+    Sorted,
+    /// Answers equality and list membership.
+    Hash,
+}
+
+impl std::fmt::Display for IndexType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                IndexType::Sorted => "sorted",
+                IndexType::Hash => "hash",
+            }
+        )
+    }
+}
+
+/// Secondary indexes on vertex and edge properties. Indexes are updated whenever the graph
+/// changes, so that they always match it.
+#[derive(Default, Serialize, Deserialize)]
+pub struct IndexStore {
+    indexes: Vec<PropertyIndex>,
+}
+
+impl IndexStore {
+    pub fn reset(&mut self) {
+        self.indexes.clear();
+    }
+
+    pub fn indexes(&self) -> &[PropertyIndex] {
+        &self.indexes
+    }
+
+    pub fn get(
+        &self,
+        vertex_or_edge: VertexOrEdge,
+        key_id: PropertyKeyId,
+    ) -> Option<&PropertyIndex> {
+        self.indexes
+            .iter()
+            .find(|index| index.vertex_or_edge == vertex_or_edge && index.key_id == key_id)
+    }
+
+    /// Builds an index on the property, replacing any existing index on it.
+    pub fn create_index(
+        &mut self,
+        vertex_or_edge: VertexOrEdge,
+        key_id: PropertyKeyId,
+        index_type: IndexType,
+        graph: &Graph,
+    ) -> &PropertyIndex {
+        let index = PropertyIndex::new(vertex_or_edge, key_id, index_type, graph);
+        let position = self
+            .indexes
+            .iter()
+            .position(|index| index.vertex_or_edge == vertex_or_edge && index.key_id == key_id);
+        match position {
+            Some(position) => {
+                self.indexes[position] = index;
+                &self.indexes[position]
+            }
+            None => {
+                self.indexes.push(index);
+                self.indexes.last().expect("Index was just added")
+            }
+        }
+    }
+
+    /// Rebuilds every index from the current graph.
+    pub fn rebuild(&mut self, graph: &Graph) {
+        for index in &mut self.indexes {
+            *index =
+                PropertyIndex::new(index.vertex_or_edge, index.key_id, index.index_type, graph);
+        }
+    }
+
+    /// Adds the current values of the vertices or edges to the indexes on their properties.
+    pub fn insert(&mut self, vertex_or_edge: VertexOrEdge, ids: &[VertexOrEdgeId], graph: &Graph) {
+        for index in self.indexes.iter_mut().filter(|index| index.vertex_or_edge == vertex_or_edge)
+        {
+            let entries = index.get_entries(ids, graph);
+            index.insert(entries);
+        }
+    }
+
+    /// Removes the current values of the vertices or edges from the indexes on their properties.
+    /// Must be called before the vertices or edges are changed or deleted.
+    pub fn remove(&mut self, vertex_or_edge: VertexOrEdge, ids: &[VertexOrEdgeId], graph: &Graph) {
+        for index in self.indexes.iter_mut().filter(|index| index.vertex_or_edge == vertex_or_edge)
+        {
+            let entries = index.get_entries(ids, graph);
+            index.remove(entries);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PropertyIndex {
+    pub vertex_or_edge: VertexOrEdge,
+    pub key_id: PropertyKeyId,
+    pub index_type: IndexType,
+    entries: IndexEntries,
+    /// The distinct types of the indexed values.
+    value_types: Vec<PropertyValueType>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum IndexEntries {
+    /// Sorted by value, and then by id.
+    Sorted(Vec<(PropertyValue, VertexOrEdgeId)>),
+    Hash(HashMap<PropertyValue, Vec<VertexOrEdgeId>>),
+}
+
+impl PropertyIndex {
+    fn new(
+        vertex_or_edge: VertexOrEdge,
+        key_id: PropertyKeyId,
+        index_type: IndexType,
+        graph: &Graph,
+    ) -> Self {
+        let values: Vec<(PropertyValue, VertexOrEdgeId)> = match vertex_or_edge {
+            VertexOrEdge::Vertex => (0..graph.vertex_count() as VertexId)
+                .filter_map(|vertex_id| {
+                    get_property_value(vertex_or_edge, key_id, vertex_id, graph)
+                        .map(|value| (value, vertex_id))
+                })
+                .collect(),
+            VertexOrEdge::Edge => graph
+                .edge_iterator()
+                .filter_map(|(edge_id, _)| {
+                    let edge_id = edge_id as EdgeId;
+                    get_property_value(vertex_or_edge, key_id, edge_id, graph)
+                        .map(|value| (value, edge_id))
+                })
+                .collect(),
+        };
+        let value_types = values.iter().map(|(value, _)| value.value_type()).unique().collect();
+        let entries = match index_type {
+            IndexType::Sorted => IndexEntries::Sorted(values.into_iter().sorted().collect()),
+            IndexType::Hash => {
+                let mut map: HashMap<PropertyValue, Vec<VertexOrEdgeId>> = HashMap::new();
+                for (value, id) in values {
+                    map.entry(value).or_default().push(id);
+                }
+                IndexEntries::Hash(map)
+            }
+        };
+        Self { vertex_or_edge, key_id, index_type, entries, value_types }
+    }
+
+    /// Returns the current values of the ids that have a value for the property.
+    fn get_entries(
+        &self,
+        ids: &[VertexOrEdgeId],
+        graph: &Graph,
+    ) -> Vec<(PropertyValue, VertexOrEdgeId)> {
+        ids.iter()
+            .filter_map(|&id| {
+                get_property_value(self.vertex_or_edge, self.key_id, id, graph)
+                    .map(|value| (value, id))
+            })
+            .collect()
+    }
+
+    /// Adds the entries, merging them into sorted entries in a single pass.
+    fn insert(&mut self, new_entries: Vec<(PropertyValue, VertexOrEdgeId)>) {
+        for (value, _) in &new_entries {
+            let value_type = value.value_type();
+            if !self.value_types.contains(&value_type) {
+                self.value_types.push(value_type);
+            }
+        }
+        match &mut self.entries {
+            IndexEntries::Sorted(entries) => {
+                if !new_entries.is_empty() {
+                    let new_entries = new_entries.into_iter().sorted();
+                    *entries = entries.drain(..).merge(new_entries).collect();
+                }
+            }
+            IndexEntries::Hash(map) => {
+                for (value, id) in new_entries {
+                    map.entry(value).or_default().push(id);
+                }
+            }
+        }
+    }
+
+    /// Removes the entries in a single pass over the affected entries. The types of the values are
+    /// kept, which only makes the index decline some numeric comparisons it could have answered.
+    fn remove(&mut self, removed_entries: Vec<(PropertyValue, VertexOrEdgeId)>) {
+        match &mut self.entries {
+            IndexEntries::Sorted(entries) => {
+                if !removed_entries.is_empty() {
+                    let removed_entries: HashSet<_> = removed_entries.into_iter().collect();
+                    entries.retain(|entry| !removed_entries.contains(entry));
+                }
+            }
+            IndexEntries::Hash(map) => {
+                let mut removed_ids: HashMap<PropertyValue, HashSet<VertexOrEdgeId>> =
+                    HashMap::new();
+                for (value, id) in removed_entries {
+                    removed_ids.entry(value).or_default().insert(id);
+                }
+                for (value, ids_to_remove) in removed_ids {
+                    if let Some(ids) = map.get_mut(&value) {
+                        ids.retain(|entry_id| !ids_to_remove.contains(entry_id));
+                        if ids.is_empty() {
+                            map.remove(&value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The number of vertices or edges that have a value for the property.
+    pub fn len(&self) -> usize {
+        match &self.entries {
+            IndexEntries::Sorted(entries) => entries.len(),
+            IndexEntries::Hash(map) => map.values().map(Vec::len).sum(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the ids for which `value operator right_operand` holds, in the same way the
+    /// predicate closures evaluate it, or `None` if the index cannot answer the predicate.
+    pub fn lookup(
+        &self,
+        operator: Operator,
+        right_operand: &RightOperand,
+    ) -> Option<Vec<VertexOrEdgeId>> {
+        match (operator, right_operand) {
+            (Operator::In, RightOperand::List(values)) => {
+                if !values.iter().all(|value| self.orders_like(value)) {
+                    return None;
+                }
+                Some(values.iter().unique().flat_map(|value| self.get_equal(value)).collect())
+            }
+            (Operator::NotIn, RightOperand::List(values)) => {
+                if !values.iter().all(|value| self.orders_like(value)) {
+                    return None;
+                }
+                let excluded: HashSet<_> =
+                    values.iter().flat_map(|value| self.get_equal(value)).collect();
+                Some(self.all_ids().filter(|id| !excluded.contains(id)).collect())
+            }
+            (_, RightOperand::Value(value)) => self.lookup_value(operator, value),
+            _ => None,
+        }
+    }
+
+    fn lookup_value(
+        &self,
+        operator: Operator,
+        value: &PropertyValue,
+    ) -> Option<Vec<VertexOrEdgeId>> {
+        if !self.orders_like(value) {
+            return None;
+        }
+        match &self.entries {
+            IndexEntries::Sorted(entries) => {
+                let lower = entries.partition_point(|(entry, _)| entry < value);
+                let upper = entries.partition_point(|(entry, _)| entry <= value);
+                let ids = |range: &[(PropertyValue, VertexOrEdgeId)]| {
+                    range.iter().map(|&(_, id)| id).collect_vec()
+                };
+                match operator {
+                    Operator::Less => Some(ids(&entries[..lower])),
+                    Operator::LessEqual => Some(ids(&entries[..upper])),
+                    Operator::Greater => Some(ids(&entries[upper..])),
+                    Operator::GreaterEqual => Some(ids(&entries[lower..])),
+                    Operator::Equal => Some(ids(&entries[lower..upper])),
+                    Operator::NotEqual => Some(
+                        entries[..lower]
+                            .iter()
+                            .chain(&entries[upper..])
+                            .map(|&(_, id)| id)
+                            .collect(),
+                    ),
+                    _ => None,
+                }
+            }
+            IndexEntries::Hash(map) => match operator {
+                Operator::Equal => Some(self.get_equal(value).collect()),
+                Operator::NotEqual => Some(
+                    map.iter()
+                        .filter(|(entry, _)| *entry != value)
+                        .flat_map(|(_, ids)| ids.iter().copied())
+                        .collect(),
+                ),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether the entries are ordered as `value` compares to them. Integers and floats are
+    /// compared by value, which the order of the entries does not follow.
+    fn orders_like(&self, value: &PropertyValue) -> bool {
+        let other_numeric_type = match value {
+            PropertyValue::Isize(_) => Some(PropertyValueType::Float),
+            PropertyValue::Float(_) => Some(PropertyValueType::Isize),
+            _ => None,
+        };
+        !matches!(other_numeric_type, Some(value_type) if self.value_types.contains(&value_type))
+    }
+
+    fn get_equal<'a>(
+        &'a self,
+        value: &PropertyValue,
+    ) -> Box<dyn Iterator<Item = VertexOrEdgeId> + 'a> {
+        match &self.entries {
+            IndexEntries::Sorted(entries) => {
+                let lower = entries.partition_point(|(entry, _)| entry < value);
+                let upper = entries.partition_point(|(entry, _)| entry <= value);
+                Box::new(entries[lower..upper].iter().map(|&(_, id)| id))
+            }
+            IndexEntries::Hash(map) => {
+                Box::new(map.get(value).into_iter().flat_map(|ids| ids.iter().copied()))
+            }
+        }
+    }
+
+    fn all_ids<'a>(&'a self) -> Box<dyn Iterator<Item = VertexOrEdgeId> + 'a> {
+        match &self.entries {
+            IndexEntries::Sorted(entries) => Box::new(entries.iter().map(|&(_, id)| id)),
+            IndexEntries::Hash(map) => Box::new(map.values().flat_map(|ids| ids.iter().copied())),
+        }
+    }
+}
+
+fn get_property_value(
+    vertex_or_edge: VertexOrEdge,
+    key_id: PropertyKeyId,
+    id: VertexOrEdgeId,
+    graph: &Graph,
+) -> Option<PropertyValue> {
+    let pv = PropertyValue::get_id(id);
+    match vertex_or_edge {
+        VertexOrEdge::Vertex => graph.get_vertex_id_property_value(id, &pv, key_id),
+        VertexOrEdge::Edge => graph.get_edge_id_property_value(id, &pv, key_id),
+    }
+    .map(std::borrow::Cow::into_owned)
+}
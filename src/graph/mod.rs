@@ -2,17 +2,20 @@
 
 use crate::create_pointer;
 use crate::error::GSError;
-use crate::graph::key_store::KeyId;
+use crate::graph::key_store::{KeyId, ID_KEY_ID, STAR_KEY_ID};
+use crate::graph::properties::columns::{ColumnStore, RowIndex};
 use crate::graph::properties::property_value::PropertyValue;
 use crate::graph::properties::Properties;
+use crate::util::bitmap::Bitmap;
 use abomonation_derive::Abomonation;
 use gs_analytics_api::{EdgeId, VertexId};
+use itertools::{Either, Itertools};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use std::borrow::Cow;
 use std::convert::TryFrom;
-use std::iter::Enumerate;
-use std::slice::Iter;
 
+pub mod indexes;
 pub mod key_store;
 pub mod properties;
 pub mod serde;
@@ -21,7 +24,14 @@ pub mod stream_data;
 #[derive(Default, Debug, Clone)]
 pub struct Graph {
     edges: Vec<Edge>,
-    vertices: Vec<Vertex>,
+    /// The properties of vertex `i` are in row `i`. The number of rows is the number of vertices.
+    vertex_properties: ColumnStore,
+    /// The properties of edge `i` are in row `i`.
+    edge_properties: ColumnStore,
+    /// Tombstones for deleted edges. Deleted edges keep their slot in `edges` so that the
+    /// `EdgeId`s of the remaining edges stay the same. Empty until an edge is deleted, and then
+    /// sized to `edges`.
+    deleted_edges: Bitmap,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -39,14 +49,8 @@ pub type DstVertexTypeId = TypeId;
 pub type EdgeTypeId = TypeId;
 create_pointer!(GraphPointer, Graph);
 
-#[derive(Debug, Clone, Abomonation, Serialize, Deserialize, new)]
-pub struct Vertex {
-    pub properties: Properties,
-}
-
-#[derive(Debug, Clone, Abomonation, Serialize, Deserialize, new)]
+#[derive(Debug, Clone, Copy, Abomonation, Serialize, Deserialize, new)]
 pub struct Edge {
-    pub properties: Properties,
     pub src_vertex_id: VertexId,
     pub dst_vertex_id: VertexId,
 }
@@ -54,39 +58,106 @@ pub struct Edge {
 impl Graph {
     pub fn reset(&mut self) {
         self.edges.clear();
-        self.vertices.clear();
+        self.vertex_properties.clear();
+        self.edge_properties.clear();
+        self.deleted_edges = Bitmap::default();
     }
 
     pub fn vertex_count(&self) -> usize {
-        self.vertices.len()
+        self.vertex_properties.len()
     }
 
+    /// The number of edge ids in use, including the ids of deleted edges.
     pub fn edges_count(&self) -> usize {
         self.edges.len()
     }
 
-    pub fn append_vertex(&mut self, vertex: Vertex) -> VertexId {
-        self.vertices.push(vertex);
-        VertexId::try_from(self.vertices.len() - 1).expect("Overflow")
+    pub fn deleted_edges_count(&self) -> usize {
+        self.deleted_edges.count_ones()
+    }
+
+    pub fn is_edge_deleted(&self, edge_id: EdgeId) -> bool {
+        self.deleted_edges.get(edge_id as usize)
+    }
+
+    /// Marks the edge as deleted. Returns `false` if it was already deleted.
+    pub fn delete_edge(&mut self, edge_id: EdgeId) -> bool {
+        let edge_index = edge_id as usize;
+        assert!(edge_index < self.edges.len(), "Edge id out of bounds");
+        if self.deleted_edges.is_empty() {
+            self.deleted_edges = Bitmap::from_indices(self.edges.len(), std::iter::empty());
+        }
+        let deleted = self.deleted_edges.get(edge_index);
+        self.deleted_edges.set(edge_index, true);
+        !deleted
+    }
+
+    /// Sets a property of an existing vertex, replacing any previous value.
+    pub fn set_vertex_property(
+        &mut self,
+        vertex_id: VertexId,
+        key_id: KeyId,
+        value: PropertyValue,
+    ) {
+        self.vertex_properties.set(vertex_id as usize, key_id, value);
+    }
+
+    /// Sets a property of an existing edge, replacing any previous value.
+    pub fn set_edge_property(&mut self, edge_id: EdgeId, key_id: KeyId, value: PropertyValue) {
+        self.edge_properties.set(edge_id as usize, key_id, value);
+    }
+
+    /// Returns a copy of the properties of the vertex.
+    pub fn vertex_properties(&self, vertex_id: VertexId) -> Properties {
+        self.vertex_properties.row(vertex_id as usize)
+    }
+
+    /// Returns a copy of the properties of the edge.
+    pub fn edge_properties(&self, edge_id: EdgeId) -> Properties {
+        self.edge_properties.row(edge_id as usize)
+    }
+
+    pub fn append_vertex(&mut self, properties: Properties) -> VertexId {
+        let row = self.vertex_properties.push_row(properties);
+        VertexId::try_from(row).expect("Vertex id overflow, use the `large-ids` feature")
     }
 
-    pub fn append_edge(&mut self, edge: Edge) -> EdgeId {
+    pub fn append_edge(&mut self, edge: Edge, properties: Properties) -> EdgeId {
         self.edges.push(edge);
-        EdgeId::try_from(self.edges.len() - 1).expect("Overflow")
+        self.edge_properties.push_row(properties);
+        if !self.deleted_edges.is_empty() {
+            self.deleted_edges.set(self.edges.len() - 1, false);
+        }
+        EdgeId::try_from(self.edges.len() - 1)
+            .expect("Edge id overflow, use the `large-ids` feature")
     }
 
-    pub fn edge_iterator(&self) -> Enumerate<Iter<Edge>> {
-        self.edges.iter().enumerate()
+    /// Removes the vertices and edges appended after the graph had `vertex_count` vertices and
+    /// `edges_count` edges.
+    pub fn truncate(&mut self, vertex_count: usize, edges_count: usize) {
+        self.vertex_properties.truncate(vertex_count);
+        self.edges.truncate(edges_count);
+        self.edge_properties.truncate(edges_count);
+        self.deleted_edges.truncate(edges_count);
     }
 
-    pub fn randomize_edges(&mut self) {
-        let mut rng = thread_rng();
-        self.edges.shuffle(&mut rng);
+    /// Iterates over the edges that are not deleted, along with their ids.
+    pub fn edge_iterator(&self) -> impl Iterator<Item = (usize, &Edge)> {
+        let edges = self.edges.iter().enumerate();
+        if self.deleted_edges.is_empty() {
+            Either::Left(edges)
+        } else {
+            Either::Right(edges.filter(move |(edge_id, _)| !self.deleted_edges.get(*edge_id)))
+        }
     }
 
-    #[inline(always)]
-    fn get_vertex(&self, vertex_id: VertexId) -> &Vertex {
-        &self.vertices[vertex_id as usize]
+    pub fn randomize_edges(&mut self) {
+        assert!(self.deleted_edges_count() == 0, "Cannot randomize edges with deleted edges");
+        let mut rng = thread_rng();
+        let mut order = (0..self.edges.len()).collect_vec();
+        order.shuffle(&mut rng);
+        self.edges = order.iter().map(|&edge_id| self.edges[edge_id]).collect();
+        self.edge_properties = self.edge_properties.reordered(&order);
     }
 
     #[inline(always)]
@@ -94,57 +165,32 @@ impl Graph {
         &self.edges[edge_id as usize]
     }
 
+    /// Returns the value of a vertex property, or `vertex_id_pv` for the `id` key. Values are
+    /// borrowed where the column stores them as `PropertyValue`s, and created otherwise.
     #[inline(always)]
     pub fn get_vertex_id_property_value<'a>(
         &'a self,
         vertex_id: VertexId,
         vertex_id_pv: &'a PropertyValue,
         key_id: KeyId,
-    ) -> Option<&'a PropertyValue> {
-        let vertex = self.get_vertex(vertex_id);
-        self.get_vertex_property_value(vertex, vertex_id_pv, key_id)
-    }
-
-    #[inline(always)]
-    #[allow(clippy::unused_self)]
-    fn get_vertex_property_value<'a>(
-        &'a self,
-        vertex: &'a Vertex,
-        vertex_id: &'a PropertyValue,
-        key_id: KeyId,
-    ) -> Option<&'a PropertyValue> {
-        vertex.properties.get_property(vertex_id, key_id)
+    ) -> Option<Cow<'a, PropertyValue>> {
+        get_property_value(&self.vertex_properties, vertex_id as usize, vertex_id_pv, key_id)
     }
 
     #[inline(always)]
-    fn get_edge_id_property_value<'a>(
+    pub fn get_edge_id_property_value<'a>(
         &'a self,
         edge_id: EdgeId,
         edge_id_pv: &'a PropertyValue,
         key_id: KeyId,
-    ) -> Option<&'a PropertyValue> {
-        let edge = self.get_edge(edge_id);
-        self.get_edge_property_value(edge, edge_id_pv, key_id)
-    }
-
-    #[inline(always)]
-    fn get_edge_property_value<'a>(
-        &'a self,
-        edge: &'a Edge,
-        edge_id: &'a PropertyValue,
-        key_id: KeyId,
-    ) -> Option<&'a PropertyValue> {
-        edge.properties.get_property(edge_id, key_id)
+    ) -> Option<Cow<'a, PropertyValue>> {
+        get_property_value(&self.edge_properties, edge_id as usize, edge_id_pv, key_id)
     }
 
     pub fn edges(&self) -> &[Edge] {
         &self.edges
     }
 
-    pub fn vertices(&self) -> &[Vertex] {
-        &self.vertices
-    }
-
     pub fn serialize(
         &self,
         bin_dir: &str,
@@ -158,3 +204,19 @@ impl Graph {
         self::serde::deserialize(self, bin_dir, thread_count)
     }
 }
+
+#[inline(always)]
+fn get_property_value<'a>(
+    column_store: &'a ColumnStore,
+    row: RowIndex,
+    id: &'a PropertyValue,
+    key_id: KeyId,
+) -> Option<Cow<'a, PropertyValue>> {
+    if key_id == ID_KEY_ID {
+        return Some(Cow::Borrowed(id));
+    }
+    if key_id == STAR_KEY_ID {
+        unreachable!("Vertex or Edge stream should not have star property operand");
+    }
+    column_store.get(row, key_id)
+}
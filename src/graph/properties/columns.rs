@@ -0,0 +1,352 @@
+use crate::graph::properties::property_value::{Float, PropertyValue};
+use crate::graph::properties::{Properties, PropertyKeyId};
+use crate::util::bitmap::Bitmap;
+use hashbrown::HashMap;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+pub type RowIndex = usize;
+type StringCode = u32;
+
+/// The properties of all the vertices or all the edges of a graph, stored as one typed column per
+/// property key. Columns are indexed by `PropertyKeyId`, so looking up a property of a row is a
+/// constant time operation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnStore {
+    columns: Vec<Option<Column>>,
+    /// The keys in the order their columns were created in, which is the order rows are returned
+    /// in.
+    key_order: Vec<PropertyKeyId>,
+    rows_count: usize,
+}
+
+impl ColumnStore {
+    pub fn len(&self) -> usize {
+        self.rows_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows_count == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.columns.clear();
+        self.key_order.clear();
+        self.rows_count = 0;
+    }
+
+    /// Removes the rows from `rows` onwards, along with the columns that only had values in
+    /// them.
+    pub fn truncate(&mut self, rows: usize) {
+        if rows >= self.rows_count {
+            return;
+        }
+        self.rows_count = rows;
+        for column in &mut self.columns {
+            if let Some(inner) = column {
+                inner.truncate(rows);
+                if inner.present.count_ones() == 0 {
+                    *column = None;
+                }
+            }
+        }
+        let columns = &self.columns;
+        self.key_order.retain(|key_id| columns[key_id.to_usize()].is_some());
+    }
+
+    /// Appends a row with the given properties and returns its index.
+    pub fn push_row(&mut self, properties: Properties) -> RowIndex {
+        let row = self.rows_count;
+        self.rows_count += 1;
+        for (key_id, value) in properties.into_entries() {
+            self.set(row, key_id, value);
+        }
+        row
+    }
+
+    /// Sets the value of a property of an existing row, replacing any previous value.
+    pub fn set(&mut self, row: RowIndex, key_id: PropertyKeyId, value: PropertyValue) {
+        assert!(row < self.rows_count, "Row index out of bounds");
+        let index = key_id.to_usize();
+        if index >= self.columns.len() {
+            self.columns.resize_with(index + 1, || None);
+        }
+        let column = &mut self.columns[index];
+        if column.is_none() {
+            self.key_order.push(key_id);
+        }
+        column.get_or_insert_with(|| Column::new(&value)).set(row, value);
+    }
+
+    #[inline(always)]
+    pub fn get(&self, row: RowIndex, key_id: PropertyKeyId) -> Option<Cow<'_, PropertyValue>> {
+        self.columns.get(key_id.to_usize())?.as_ref()?.get(row)
+    }
+
+    /// Returns the properties of a row, in the order their columns were created in.
+    pub fn row(&self, row: RowIndex) -> Properties {
+        let mut properties = Properties::default();
+        for &key_id in &self.key_order {
+            if let Some(value) = self.get(row, key_id) {
+                properties.add_new_property(key_id, value.into_owned());
+            }
+        }
+        properties
+    }
+
+    /// Returns a new store with the rows in the given order.
+    pub fn reordered(&self, order: &[RowIndex]) -> Self {
+        let mut store = Self::default();
+        // Create the columns in the same order.
+        store.columns.resize_with(self.columns.len(), || None);
+        store.key_order.clone_from(&self.key_order);
+        for &row in order {
+            let new_row = store.rows_count;
+            store.rows_count += 1;
+            for &key_id in &self.key_order {
+                if let Some(value) = self.get(row, key_id) {
+                    let column = &mut store.columns[key_id.to_usize()];
+                    column
+                        .get_or_insert_with(|| Column::new(&value))
+                        .set(new_row, value.into_owned());
+                }
+            }
+        }
+        store
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Column {
+    /// Bit `i` is set if row `i` has a value. Values of null rows are placeholders.
+    present: Bitmap,
+    values: ColumnValues,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ColumnValues {
+    Isize(Vec<isize>),
+    Float(Vec<f64>),
+    Bool(Bitmap),
+    DateTime(Vec<i64>),
+    String(StringDictionary, Vec<StringCode>),
+    /// Values of the other types, and of keys that have values of more than one type.
+    Mixed(Vec<PropertyValue>),
+}
+
+impl Column {
+    /// Creates an empty column for values of the type of `value`.
+    fn new(value: &PropertyValue) -> Self {
+        let values = match value {
+            PropertyValue::Isize(_) => ColumnValues::Isize(Vec::new()),
+            PropertyValue::Float(_) => ColumnValues::Float(Vec::new()),
+            PropertyValue::Bool(_) => ColumnValues::Bool(Bitmap::default()),
+            PropertyValue::DateTime(_) => ColumnValues::DateTime(Vec::new()),
+            PropertyValue::String(_) => {
+                ColumnValues::String(StringDictionary::default(), Vec::new())
+            }
+            _ => ColumnValues::Mixed(Vec::new()),
+        };
+        Self { present: Bitmap::default(), values }
+    }
+
+    #[inline(always)]
+    fn get(&self, row: RowIndex) -> Option<Cow<'_, PropertyValue>> {
+        if !self.present.get(row) {
+            return None;
+        }
+        Some(match &self.values {
+            ColumnValues::Isize(values) => Cow::Owned(PropertyValue::Isize(values[row])),
+            ColumnValues::Float(values) => Cow::Owned(PropertyValue::Float(Float(values[row]))),
+            ColumnValues::Bool(values) => Cow::Owned(PropertyValue::Bool(values.get(row))),
+            ColumnValues::DateTime(values) => Cow::Owned(PropertyValue::DateTime(values[row])),
+            ColumnValues::String(dictionary, codes) => Cow::Borrowed(dictionary.get(codes[row])),
+            ColumnValues::Mixed(values) => Cow::Borrowed(&values[row]),
+        })
+    }
+
+    fn set(&mut self, row: RowIndex, value: PropertyValue) {
+        match (&mut self.values, value) {
+            (ColumnValues::Isize(values), PropertyValue::Isize(value)) => {
+                set_value(values, row, value, 0);
+            }
+            (ColumnValues::Float(values), PropertyValue::Float(value)) => {
+                set_value(values, row, value.0, 0.0);
+            }
+            (ColumnValues::Bool(values), PropertyValue::Bool(value)) => {
+                values.set(row, value);
+            }
+            (ColumnValues::DateTime(values), PropertyValue::DateTime(value)) => {
+                set_value(values, row, value, 0);
+            }
+            (ColumnValues::String(dictionary, codes), PropertyValue::String(value)) => {
+                let code = dictionary.encode(value);
+                set_value(codes, row, code, 0);
+            }
+            (ColumnValues::Mixed(values), value) => {
+                set_value(values, row, value, PropertyValue::Bool(false));
+            }
+            (_, value) => {
+                self.make_mixed();
+                return self.set(row, value);
+            }
+        }
+        self.present.set(row, true);
+    }
+
+    /// Converts a typed column to a `Mixed` one, when a value of another type is set.
+    fn make_mixed(&mut self) {
+        let values = (0..self.len())
+            .map(|row| self.get(row).map_or(PropertyValue::Bool(false), Cow::into_owned))
+            .collect();
+        self.values = ColumnValues::Mixed(values);
+    }
+
+    fn truncate(&mut self, rows: usize) {
+        self.present.truncate(rows);
+        match &mut self.values {
+            ColumnValues::Isize(values) => values.truncate(rows),
+            ColumnValues::Float(values) => values.truncate(rows),
+            ColumnValues::Bool(values) => values.truncate(rows),
+            ColumnValues::DateTime(values) => values.truncate(rows),
+            ColumnValues::String(_, codes) => codes.truncate(rows),
+            ColumnValues::Mixed(values) => values.truncate(rows),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.values {
+            ColumnValues::Isize(values) => values.len(),
+            ColumnValues::Float(values) => values.len(),
+            ColumnValues::Bool(values) => values.len(),
+            ColumnValues::DateTime(values) => values.len(),
+            ColumnValues::String(_, codes) => codes.len(),
+            ColumnValues::Mixed(values) => values.len(),
+        }
+    }
+}
+
+/// Sets `values[row]`, padding any rows before it with `placeholder`.
+fn set_value<T: Clone>(values: &mut Vec<T>, row: RowIndex, value: T, placeholder: T) {
+    if row < values.len() {
+        values[row] = value;
+    } else {
+        values.resize(row, placeholder);
+        values.push(value);
+    }
+}
+
+/// Stores each distinct string once, as a `PropertyValue` so that it can be returned by reference.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StringDictionary {
+    values: Vec<PropertyValue>,
+    /// Rebuilt from `values` on the first `encode` after deserialization.
+    #[serde(skip)]
+    codes: HashMap<String, StringCode>,
+}
+
+impl StringDictionary {
+    #[inline(always)]
+    fn get(&self, code: StringCode) -> &PropertyValue {
+        &self.values[code as usize]
+    }
+
+    fn encode(&mut self, value: String) -> StringCode {
+        if self.codes.len() != self.values.len() {
+            self.codes = self
+                .values
+                .iter()
+                .enumerate()
+                .filter_map(|(code, value)| match value {
+                    PropertyValue::String(value) => Some((
+                        value.clone(),
+                        StringCode::try_from(code).expect("String dictionary overflow"),
+                    )),
+                    _ => None,
+                })
+                .collect();
+        }
+        if let Some(&code) = self.codes.get(&value) {
+            return code;
+        }
+        let code = StringCode::try_from(self.values.len()).expect("String dictionary overflow");
+        self.codes.insert(value.clone(), code);
+        self.values.push(PropertyValue::String(value));
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::key_store::KeyStore;
+    use crate::graph::properties::columns::{ColumnStore, ColumnValues};
+    use crate::graph::properties::property_value::{Float, PropertyValue};
+    use crate::graph::properties::Properties;
+
+    #[test]
+    fn typed_columns_with_nulls() {
+        let mut key_store = KeyStore::default();
+        let age = key_store.get_key_id_or_insert("age");
+        let name = key_store.get_key_id_or_insert("name");
+        let score = key_store.get_key_id_or_insert("score");
+        let mut store = ColumnStore::default();
+
+        let mut properties = Properties::default();
+        properties.add_new_property(name, PropertyValue::String("a".to_owned()));
+        properties.add_new_property(age, PropertyValue::Isize(30));
+        store.push_row(properties);
+        store.push_row(Properties::default());
+        let mut properties = Properties::default();
+        properties.add_new_property(score, PropertyValue::Float(Float(1.5)));
+        properties.add_new_property(name, PropertyValue::String("a".to_owned()));
+        store.push_row(properties);
+
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get(0, age).as_deref(), Some(&PropertyValue::Isize(30)));
+        assert_eq!(store.get(1, age), None);
+        assert_eq!(store.get(2, age), None);
+        assert_eq!(store.get(0, score), None);
+        assert_eq!(store.get(2, score).as_deref(), Some(&PropertyValue::Float(Float(1.5))));
+        match &store.columns[name.to_usize()].as_ref().expect("Column should exist").values {
+            ColumnValues::String(dictionary, codes) => {
+                assert_eq!(dictionary.values.len(), 1);
+                assert_eq!(codes, &[0, 0, 0]);
+            }
+            values => panic!("Expected a string column, found {:?}", values),
+        }
+        // Rows keep the order the columns were created in.
+        assert_eq!(
+            store.row(2).into_entries(),
+            vec![
+                (name, PropertyValue::String("a".to_owned())),
+                (score, PropertyValue::Float(Float(1.5)))
+            ]
+        );
+    }
+
+    #[test]
+    fn values_of_another_type() {
+        let mut key_store = KeyStore::default();
+        let key_id = key_store.get_key_id_or_insert("value");
+        let mut store = ColumnStore::default();
+        for _ in 0..3 {
+            store.push_row(Properties::default());
+        }
+
+        store.set(0, key_id, PropertyValue::Bool(true));
+        store.set(2, key_id, PropertyValue::String("x".to_owned()));
+        store.set(0, key_id, PropertyValue::Bool(false));
+
+        assert_eq!(store.get(0, key_id).as_deref(), Some(&PropertyValue::Bool(false)));
+        assert_eq!(store.get(1, key_id), None);
+        assert_eq!(store.get(2, key_id).as_deref(), Some(&PropertyValue::String("x".to_owned())));
+
+        let reordered = store.reordered(&[2, 1, 0]);
+        assert_eq!(
+            reordered.get(0, key_id).as_deref(),
+            Some(&PropertyValue::String("x".to_owned()))
+        );
+        assert_eq!(reordered.get(1, key_id), None);
+        assert_eq!(reordered.get(2, key_id).as_deref(), Some(&PropertyValue::Bool(false)));
+    }
+}
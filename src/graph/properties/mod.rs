@@ -1,7 +1,8 @@
-use crate::graph::key_store::{KeyId, ID_KEY_ID, STAR_KEY_ID};
+use crate::graph::key_store::KeyId;
 use crate::graph::properties::property_value::PropertyValue;
 use abomonation_derive::Abomonation;
 
+pub mod columns;
 pub mod operations;
 pub mod property_value;
 pub mod property_value_type;
@@ -29,22 +30,19 @@ impl Properties {
         self.entries.push((property_key_id, property_value));
     }
 
-    pub fn get_property<'a>(
-        &'a self,
-        id: &'a PropertyValue,
-        property_key_id: PropertyKeyId,
-    ) -> Option<&'a PropertyValue> {
-        if property_key_id == ID_KEY_ID {
-            return Some(id);
-        }
-        if property_key_id == STAR_KEY_ID {
-            unreachable!("Vertex or Edge stream should not have star property operand");
+    /// Replaces the value of an existing property, or adds it otherwise.
+    pub fn set_property(&mut self, property_key_id: PropertyKeyId, property_value: PropertyValue) {
+        match self.entries.iter_mut().find(|(key_id, _)| *key_id == property_key_id) {
+            Some((_, value)) => *value = property_value,
+            None => self.entries.push((property_key_id, property_value)),
         }
-        for (key_id, property) in &self.entries {
-            if *key_id == property_key_id {
-                return Some(property);
-            }
-        }
-        None
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(PropertyKeyId, PropertyValue)> {
+        self.entries.iter()
+    }
+
+    pub fn into_entries(self) -> Vec<(PropertyKeyId, PropertyValue)> {
+        self.entries
     }
 }
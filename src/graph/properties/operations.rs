@@ -1,7 +1,7 @@
 use crate::graph::properties::property_value::PropertyValue;
 use crate::graph::properties::PropertyKeyId;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Operator {
     Less,
     LessEqual,
@@ -9,6 +9,18 @@ pub enum Operator {
     GreaterEqual,
     Equal,
     NotEqual,
+    In,
+    NotIn,
+    Like,
+    ILike,
+    StartsWith,
+    EndsWith,
+    Contains,
+    AnyIn,
+    Is,
+    IsNot,
+    /// Equality of strings ignoring case, without the wildcards of `ILike`.
+    IEqual,
 }
 
 impl Operator {
@@ -20,19 +32,80 @@ impl Operator {
             Operator::GreaterEqual => ">=",
             Operator::Equal => "=",
             Operator::NotEqual => "<>",
+            Operator::In => " in ",
+            Operator::NotIn => " not in ",
+            Operator::Like => " like ",
+            Operator::ILike => " ilike ",
+            Operator::StartsWith => " starts with ",
+            Operator::EndsWith => " ends with ",
+            Operator::Contains => " contains ",
+            Operator::AnyIn => " any in ",
+            Operator::Is => " is ",
+            Operator::IsNot => " is not ",
+            Operator::IEqual => " iequals ",
         }
         .to_owned()
     }
+
+    /// Whether the operator only applies to string or list values.
+    pub fn is_string_operator(self) -> bool {
+        matches!(
+            self,
+            Operator::Like
+                | Operator::ILike
+                | Operator::IEqual
+                | Operator::StartsWith
+                | Operator::EndsWith
+                | Operator::Contains
+                | Operator::AnyIn
+        )
+    }
+
+    /// Whether the operator tests the elements of a list value.
+    pub fn is_list_operator(self) -> bool {
+        matches!(self, Operator::Contains | Operator::AnyIn)
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Operand {
     Property(PropertyKeyId),
     Edge,
     SourceVertex(PropertyKeyId),
     DestinationVertex(PropertyKeyId),
 }
-pub type LeftOperand = Operand;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LeftOperand {
+    Operand(Operand),
+    Expression(Expression),
+}
+
+impl LeftOperand {
+    pub fn into_expression(self) -> Expression {
+        match self {
+            LeftOperand::Operand(operand) => Expression::Operand(operand),
+            LeftOperand::Expression(expression) => expression,
+        }
+    }
+}
+
+impl std::fmt::Display for LeftOperand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            LeftOperand::Operand(operand) => write!(f, "{}", operand),
+            LeftOperand::Expression(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+impl Operand {
+    /// Whether the operand refers to a property value, which can be compared with values and
+    /// used in arithmetic expressions. `e` is always a pair.
+    pub fn is_property_value(self) -> bool {
+        !matches!(self, Operand::Edge)
+    }
+}
 
 impl std::fmt::Display for Operand {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -53,6 +126,28 @@ impl std::fmt::Display for Operand {
 pub enum RightOperand {
     Value(PropertyValue),
     Variable(Operand),
+    List(Vec<PropertyValue>),
+    Expression(Expression),
+    /// Only used with `Operator::Is` and `Operator::IsNot`.
+    Null,
+}
+
+impl RightOperand {
+    pub fn is_expression(&self) -> bool {
+        matches!(self, RightOperand::Expression(_))
+    }
+
+    /// Returns `None` if the operand cannot be used inside an arithmetic expression.
+    pub fn into_expression(self) -> Option<Expression> {
+        match self {
+            RightOperand::Value(PropertyValue::Isize(value)) => Some(Expression::Value(value)),
+            RightOperand::Variable(operand) if operand.is_property_value() => {
+                Some(Expression::Operand(operand))
+            }
+            RightOperand::Expression(expression) => Some(expression),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for RightOperand {
@@ -63,7 +158,180 @@ impl std::fmt::Display for RightOperand {
             match self {
                 RightOperand::Value(v) => v.to_string(),
                 RightOperand::Variable(v) => v.to_string(),
+                RightOperand::Expression(e) => e.to_string(),
+                RightOperand::Null => "null".to_owned(),
+                RightOperand::List(l) => format!(
+                    "({})",
+                    l.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+                ),
             }
         )
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl ArithmeticOperator {
+    pub fn as_string(self) -> String {
+        match self {
+            ArithmeticOperator::Add => "+",
+            ArithmeticOperator::Subtract => "-",
+            ArithmeticOperator::Multiply => "*",
+            ArithmeticOperator::Divide => "/",
+            ArithmeticOperator::Remainder => "%",
+        }
+        .to_owned()
+    }
+
+    /// Returns `None` on overflow or division by zero.
+    pub fn apply(self, left: isize, right: isize) -> Option<isize> {
+        match self {
+            ArithmeticOperator::Add => left.checked_add(right),
+            ArithmeticOperator::Subtract => left.checked_sub(right),
+            ArithmeticOperator::Multiply => left.checked_mul(right),
+            ArithmeticOperator::Divide => left.checked_div(right),
+            ArithmeticOperator::Remainder => left.checked_rem(right),
+        }
+    }
+}
+
+/// An integer arithmetic expression over operands and literals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Expression {
+    Operand(Operand),
+    Value(isize),
+    Negation(Box<Expression>),
+    Arithmetic(Box<Expression>, ArithmeticOperator, Box<Expression>),
+}
+
+impl Expression {
+    /// Evaluates the expression using `get_operand_value` to get the values of the operands.
+    /// Returns `None` if any operand is missing or is not an integer, or if the arithmetic fails.
+    pub fn evaluate<F: Fn(Operand) -> Option<isize>>(
+        &self,
+        get_operand_value: &F,
+    ) -> Option<isize> {
+        match self {
+            Expression::Operand(operand) => get_operand_value(*operand),
+            Expression::Value(value) => Some(*value),
+            Expression::Negation(expression) => {
+                expression.evaluate(get_operand_value)?.checked_neg()
+            }
+            Expression::Arithmetic(left, operator, right) => operator
+                .apply(left.evaluate(get_operand_value)?, right.evaluate(get_operand_value)?),
+        }
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Expression::Operand(operand) => write!(f, "{}", operand),
+            Expression::Value(value) => write!(f, "{}", value),
+            Expression::Negation(expression) => write!(f, "-{}", expression),
+            Expression::Arithmetic(left, operator, right) => {
+                write!(f, "({} {} {})", left, operator.as_string(), right)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::key_store::KeyStore;
+    use crate::graph::properties::operations::{ArithmeticOperator, Expression, Operand};
+
+    #[test]
+    fn arithmetic_operators() {
+        assert_eq!(ArithmeticOperator::Add.apply(7, -2), Some(5));
+        assert_eq!(ArithmeticOperator::Subtract.apply(7, 9), Some(-2));
+        assert_eq!(ArithmeticOperator::Multiply.apply(-7, 3), Some(-21));
+        // Division and remainder truncate towards zero.
+        assert_eq!(ArithmeticOperator::Divide.apply(-7, 2), Some(-3));
+        assert_eq!(ArithmeticOperator::Remainder.apply(-7, 2), Some(-1));
+    }
+
+    #[test]
+    fn arithmetic_operators_overflow() {
+        assert_eq!(ArithmeticOperator::Add.apply(isize::MAX, 1), None);
+        assert_eq!(ArithmeticOperator::Subtract.apply(isize::MIN, 1), None);
+        assert_eq!(ArithmeticOperator::Multiply.apply(isize::MAX, 2), None);
+        assert_eq!(ArithmeticOperator::Divide.apply(1, 0), None);
+        assert_eq!(ArithmeticOperator::Remainder.apply(1, 0), None);
+        assert_eq!(ArithmeticOperator::Divide.apply(isize::MIN, -1), None);
+    }
+
+    #[test]
+    fn expression_evaluation() {
+        let mut key_store = KeyStore::default();
+        let amount = Operand::Property(key_store.get_key_id_or_insert("amount"));
+        let year = Operand::SourceVertex(key_store.get_key_id_or_insert("year"));
+        let get_operand_value = |operand| {
+            if operand == amount {
+                Some(400)
+            } else if operand == year {
+                Some(2010)
+            } else {
+                None
+            }
+        };
+
+        // (amount - (u.year - 2000) * 10) % 7
+        let expression = Expression::Arithmetic(
+            Box::new(Expression::Arithmetic(
+                Box::new(Expression::Operand(amount)),
+                ArithmeticOperator::Subtract,
+                Box::new(Expression::Arithmetic(
+                    Box::new(Expression::Arithmetic(
+                        Box::new(Expression::Operand(year)),
+                        ArithmeticOperator::Subtract,
+                        Box::new(Expression::Value(2000)),
+                    )),
+                    ArithmeticOperator::Multiply,
+                    Box::new(Expression::Value(10)),
+                )),
+            )),
+            ArithmeticOperator::Remainder,
+            Box::new(Expression::Value(7)),
+        );
+        assert_eq!(expression.evaluate(&get_operand_value), Some(6));
+        assert_eq!(
+            expression.to_string(),
+            format!("(({} - (({} - 2000) * 10)) % 7)", amount, year)
+        );
+
+        let negation = Expression::Negation(Box::new(Expression::Operand(amount)));
+        assert_eq!(negation.evaluate(&get_operand_value), Some(-400));
+        let negation = Expression::Negation(Box::new(Expression::Value(isize::MIN)));
+        assert_eq!(negation.evaluate(&get_operand_value), None);
+    }
+
+    #[test]
+    fn expression_evaluation_missing_operands() {
+        let mut key_store = KeyStore::default();
+        let amount = Operand::Property(key_store.get_key_id_or_insert("amount"));
+        let missing = Operand::DestinationVertex(key_store.get_key_id_or_insert("missing"));
+        let get_operand_value = |operand| if operand == amount { Some(400) } else { None };
+
+        let expression = Expression::Arithmetic(
+            Box::new(Expression::Operand(amount)),
+            ArithmeticOperator::Add,
+            Box::new(Expression::Operand(missing)),
+        );
+        assert_eq!(expression.evaluate(&get_operand_value), None);
+
+        let expression = Expression::Arithmetic(
+            Box::new(Expression::Operand(amount)),
+            ArithmeticOperator::Divide,
+            Box::new(Expression::Value(0)),
+        );
+        assert_eq!(expression.evaluate(&get_operand_value), None);
+    }
+}
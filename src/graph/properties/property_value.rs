@@ -3,16 +3,63 @@ use crate::graph::properties::operations::Operator;
 use crate::graph::properties::property_value_type::PropertyValueType;
 use crate::graph::VertexOrEdgeId;
 use abomonation_derive::Abomonation;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use std::cmp::Ordering;
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Abomonation, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Abomonation, Serialize, Deserialize,
+)]
 pub enum PropertyValue {
     KeyId(KeyId),
     Isize(isize),
     String(String),
     Strings(Vec<String>),
+    IntList(Vec<isize>),
     Bool(bool),
     Pair(isize, isize),
+    Float(Float),
+    /// Partial sum and count of float values, used for averages.
+    FloatPair(Float, isize),
+    /// Milliseconds since the Unix epoch in UTC.
+    DateTime(i64),
+}
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+/// A `f64` with a total order (as defined by `f64::total_cmp`), so that it can be stored in a
+/// `PropertyValue`.
+#[derive(Clone, Copy, Debug, Abomonation, Serialize, Deserialize)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Floats are equal under `f64::total_cmp` exactly when their bits are equal.
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
 }
 
 impl PropertyValue {
@@ -21,16 +68,108 @@ impl PropertyValue {
     }
 
     pub fn compare(&self, other: &Self, operator: Operator) -> bool {
+        // Integers and floats are compared by value.
+        let ordering = match (self, other) {
+            (PropertyValue::Isize(integer), PropertyValue::Float(float)) => {
+                compare_integer_float(*integer, float.0)
+            }
+            (PropertyValue::Float(float), PropertyValue::Isize(integer)) => {
+                compare_integer_float(*integer, float.0).reverse()
+            }
+            _ => self.cmp(other),
+        };
         match operator {
-            Operator::Less => self < other,
-            Operator::LessEqual => self <= other,
-            Operator::Greater => self > other,
-            Operator::GreaterEqual => self >= other,
-            Operator::Equal => self == other,
-            Operator::NotEqual => self != other,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessEqual => ordering != Ordering::Greater,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterEqual => ordering != Ordering::Less,
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::Like
+            | Operator::ILike
+            | Operator::IEqual
+            | Operator::StartsWith
+            | Operator::EndsWith => match (self, other) {
+                (PropertyValue::String(value), PropertyValue::String(pattern)) => match operator {
+                    Operator::Like => like_matches(value, pattern),
+                    Operator::ILike => like_matches(&value.to_lowercase(), &pattern.to_lowercase()),
+                    Operator::IEqual => value.to_lowercase() == pattern.to_lowercase(),
+                    Operator::StartsWith => value.starts_with(pattern.as_str()),
+                    _ => value.ends_with(pattern.as_str()),
+                },
+                _ => false,
+            },
+            Operator::Contains => match (self, other) {
+                (PropertyValue::Strings(values), PropertyValue::String(value)) => {
+                    values.contains(value)
+                }
+                (PropertyValue::IntList(values), PropertyValue::Isize(value)) => {
+                    values.contains(value)
+                }
+                (PropertyValue::String(value), PropertyValue::String(substring)) => {
+                    value.contains(substring.as_str())
+                }
+                _ => false,
+            },
+            Operator::In | Operator::NotIn | Operator::AnyIn => {
+                unreachable!("List operators should be evaluated using `compare_list`")
+            }
+            Operator::Is | Operator::IsNot => {
+                unreachable!("Null checks do not compare property values")
+            }
+        }
+    }
+
+    pub fn compare_list(&self, others: &[Self], operator: Operator) -> bool {
+        // Integers and floats are compared by value, as with `Operator::Equal`.
+        let is_in = || others.iter().any(|other| self.compare(other, Operator::Equal));
+        match operator {
+            Operator::In => is_in(),
+            Operator::NotIn => !is_in(),
+            Operator::AnyIn => match self {
+                PropertyValue::Strings(values) => values.iter().any(|value| {
+                    others
+                        .iter()
+                        .any(|other| matches!(other, PropertyValue::String(o) if o == value))
+                }),
+                PropertyValue::IntList(values) => values.iter().any(|value| {
+                    others
+                        .iter()
+                        .any(|other| matches!(other, PropertyValue::Isize(o) if o == value))
+                }),
+                _ => is_in(),
+            },
+            o => unreachable!("Operator '{}' cannot be applied to a list", o.as_string()),
+        }
+    }
+
+    pub fn as_isize(&self) -> Option<isize> {
+        match self {
+            PropertyValue::Isize(value) => Some(*value),
+            _ => None,
         }
     }
 
+    /// Parses an ISO-8601 date, e.g., `2015-06-30`, as midnight UTC.
+    pub fn parse_date(value: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(value, DATE_FORMAT)
+            .ok()
+            .map(|date| PropertyValue::DateTime(date.and_hms(0, 0, 0).timestamp_millis()))
+    }
+
+    /// Parses an ISO-8601 datetime, e.g., `2015-06-30T10:15:00` or `2015-06-30T10:15:00+02:00`.
+    /// Datetimes without an offset are treated as UTC, and dates without a time as midnight UTC.
+    pub fn parse_datetime(value: &str) -> Option<Self> {
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+            return Some(PropertyValue::DateTime(datetime.timestamp_millis()));
+        }
+        DATETIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|datetime| PropertyValue::DateTime(datetime.timestamp_millis()))
+            .or_else(|| Self::parse_date(value))
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         match self {
             PropertyValue::KeyId(key_id) => {
@@ -41,10 +180,22 @@ impl PropertyValue {
             PropertyValue::Strings(value) => {
                 bincode::serialize(value).expect("Serialization error")
             }
+            PropertyValue::IntList(value) => {
+                bincode::serialize(value).expect("Serialization error")
+            }
             PropertyValue::Bool(value) => bincode::serialize(value).expect("Serialization error"),
             PropertyValue::Pair(value1, value2) => {
                 bincode::serialize(&(value1, value2)).expect("Serialization error")
             }
+            PropertyValue::Float(value) => {
+                bincode::serialize(&value.0).expect("Serialization error")
+            }
+            PropertyValue::FloatPair(value1, value2) => {
+                bincode::serialize(&(value1.0, value2)).expect("Serialization error")
+            }
+            PropertyValue::DateTime(value) => {
+                bincode::serialize(value).expect("Serialization error")
+            }
         }
     }
 
@@ -54,10 +205,71 @@ impl PropertyValue {
             PropertyValue::Isize(_) => PropertyValueType::Isize,
             PropertyValue::String(_) => PropertyValueType::String,
             PropertyValue::Strings(_) => PropertyValueType::Strings,
+            PropertyValue::IntList(_) => PropertyValueType::IntList,
             PropertyValue::Bool(_) => PropertyValueType::Bool,
             PropertyValue::Pair(_, _) => PropertyValueType::Pair,
+            PropertyValue::Float(_) => PropertyValueType::Float,
+            PropertyValue::FloatPair(_, _) => PropertyValueType::FloatPair,
+            PropertyValue::DateTime(_) => PropertyValueType::DateTime,
+        }
+    }
+}
+
+/// Compares the integer with the float exactly, as casting the integer to a float rounds it above
+/// 2^53. Floats that are not numbers are ordered as by `f64::total_cmp`, i.e., above every integer
+/// unless their sign is negative.
+fn compare_integer_float(integer: isize, float: f64) -> Ordering {
+    if float.is_nan() {
+        return if float.is_sign_negative() { Ordering::Greater } else { Ordering::Less };
+    }
+    // The integral part of a float converts to `i128` exactly, or saturates beyond any `isize`.
+    let truncated = float.trunc();
+    (integer as i128).cmp(&(truncated as i128)).then_with(|| {
+        // The integer equals the integral part, so the fractional part decides.
+        0.0_f64.partial_cmp(&(float - truncated)).expect("Fractional part is a number")
+    })
+}
+
+/// Matches `value` against a SQL `LIKE` `pattern`, where `%` matches any sequence of characters
+/// and `_` matches exactly one character.
+fn like_matches(value: &str, pattern: &str) -> bool {
+    let value = value.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let (mut value_index, mut pattern_index) = (0, 0);
+    // Position of the last `%` in the pattern and the value index it was tried at.
+    let mut backtrack = None;
+    while value_index < value.len() {
+        if pattern_index < pattern.len()
+            && (pattern[pattern_index] == '_' || pattern[pattern_index] == value[value_index])
+        {
+            value_index += 1;
+            pattern_index += 1;
+        } else if pattern_index < pattern.len() && pattern[pattern_index] == '%' {
+            backtrack = Some((pattern_index, value_index));
+            pattern_index += 1;
+        } else if let Some((percent_index, percent_value_index)) = backtrack {
+            // Let the last `%` consume one more character.
+            backtrack = Some((percent_index, percent_value_index + 1));
+            pattern_index = percent_index + 1;
+            value_index = percent_value_index + 1;
+        } else {
+            return false;
         }
     }
+    pattern[pattern_index..].iter().all(|&c| c == '%')
+}
+
+/// Formats the datetime as a literal that can be parsed back in queries.
+fn fmt_datetime(millis: i64) -> String {
+    let datetime = NaiveDateTime::from_timestamp(
+        millis.div_euclid(1000),
+        u32::try_from(millis.rem_euclid(1000) * 1_000_000).expect("Nanoseconds overflow"),
+    );
+    if millis.rem_euclid(MILLIS_PER_DAY) == 0 {
+        format!("date '{}'", datetime.format(DATE_FORMAT))
+    } else {
+        format!("datetime '{}'", datetime.format(DATETIME_FORMATS[0]))
+    }
 }
 
 impl std::fmt::Display for PropertyValue {
@@ -70,8 +282,12 @@ impl std::fmt::Display for PropertyValue {
                 PropertyValue::Isize(value) => format!("{}", value),
                 PropertyValue::String(value) => format!("'{}'", value),
                 PropertyValue::Strings(value) => format!("{:?}", value),
+                PropertyValue::IntList(value) => format!("{:?}", value),
                 PropertyValue::Bool(value) => format!("{:?}", value),
                 PropertyValue::Pair(value1, value2) => format!("({},{})", value1, value2),
+                PropertyValue::Float(value) => format!("{:?}", value.0),
+                PropertyValue::FloatPair(value1, value2) => format!("({:?},{})", value1.0, value2),
+                PropertyValue::DateTime(millis) => fmt_datetime(*millis),
             }
         )
     }
@@ -80,7 +296,7 @@ impl std::fmt::Display for PropertyValue {
 #[cfg(test)]
 mod tests {
     use crate::graph::properties::operations::Operator;
-    use crate::graph::properties::property_value::PropertyValue;
+    use crate::graph::properties::property_value::{like_matches, Float, PropertyValue};
 
     #[test]
     fn equal_isize_isize_comparisons() {
@@ -186,6 +402,301 @@ mod tests {
         assert_greater_than(&left, &right);
     }
 
+    #[test]
+    fn float_float_comparisons() {
+        assert_equal(&PropertyValue::Float(Float(0.25)), &PropertyValue::Float(Float(0.25)));
+        assert_less_than(&PropertyValue::Float(Float(-1.5)), &PropertyValue::Float(Float(0.25)));
+        assert_less_than(
+            &PropertyValue::Float(Float(1e10)),
+            &PropertyValue::Float(Float(f64::INFINITY)),
+        );
+        assert_greater_than(
+            &PropertyValue::Float(Float(f64::NAN)),
+            &PropertyValue::Float(Float(f64::INFINITY)),
+        );
+    }
+
+    #[test]
+    fn float_isize_comparisons() {
+        assert_equal(&PropertyValue::Float(Float(3.0)), &PropertyValue::Isize(3));
+        assert_less_than(&PropertyValue::Isize(2), &PropertyValue::Float(Float(2.5)));
+        assert_greater_than(&PropertyValue::Float(Float(-0.5)), &PropertyValue::Isize(-1));
+        assert_greater_than(
+            &PropertyValue::Float(Float(f64::INFINITY)),
+            &PropertyValue::Isize(isize::MAX),
+        );
+        assert_less_than(
+            &PropertyValue::Float(Float(f64::NEG_INFINITY)),
+            &PropertyValue::Isize(isize::MIN),
+        );
+        assert_greater_than(&PropertyValue::Float(Float(f64::NAN)), &PropertyValue::Isize(0));
+        assert_less_than(&PropertyValue::Float(Float(-f64::NAN)), &PropertyValue::Isize(0));
+    }
+
+    #[test]
+    fn float_isize_comparisons_beyond_float_precision() {
+        // 2^53 + 1 is not a float, and rounds to 2^53 when cast to one.
+        let integer = PropertyValue::Isize(9_007_199_254_740_993);
+        let float = PropertyValue::Float(Float(9_007_199_254_740_992.0));
+        assert_greater_than(&integer, &float);
+        assert_equal(&PropertyValue::Isize(9_007_199_254_740_992), &float);
+        assert_less_than(&integer, &PropertyValue::Float(Float(9_007_199_254_740_994.0)));
+
+        // isize::MAX rounds up to 2^63, which is not an isize.
+        let two_pow_63 = f64::powi(2.0, 63);
+        assert_less_than(
+            &PropertyValue::Isize(isize::MAX),
+            &PropertyValue::Float(Float(two_pow_63)),
+        );
+        assert_equal(&PropertyValue::Isize(isize::MIN), &PropertyValue::Float(Float(-two_pow_63)));
+
+        assert_less_than(&PropertyValue::Isize(-3), &PropertyValue::Float(Float(-2.5)));
+        assert_greater_than(&PropertyValue::Isize(-2), &PropertyValue::Float(Float(-2.5)));
+        assert_equal(&PropertyValue::Isize(0), &PropertyValue::Float(Float(-0.0)));
+    }
+
+    #[test]
+    fn float_isize_list_membership() {
+        let integers = [PropertyValue::Isize(1), PropertyValue::Isize(3)];
+        let floats = [PropertyValue::Float(Float(1.0)), PropertyValue::Float(Float(2.5))];
+
+        assert!(PropertyValue::Float(Float(3.0)).compare_list(&integers, Operator::In));
+        assert!(!PropertyValue::Float(Float(3.0)).compare_list(&integers, Operator::NotIn));
+        assert!(!PropertyValue::Float(Float(2.5)).compare_list(&integers, Operator::In));
+        assert!(PropertyValue::Float(Float(2.5)).compare_list(&integers, Operator::NotIn));
+        assert!(PropertyValue::Isize(1).compare_list(&floats, Operator::In));
+        assert!(PropertyValue::Isize(1).compare_list(&floats, Operator::AnyIn));
+        assert!(!PropertyValue::Isize(2).compare_list(&floats, Operator::In));
+        assert!(!PropertyValue::Float(Float(f64::NAN)).compare_list(&floats, Operator::In));
+    }
+
+    #[test]
+    fn datetime_parsing_and_comparisons() {
+        let date = PropertyValue::parse_date("2015-06-30").expect("Date not parsed");
+        assert_eq!(date.to_string(), "date '2015-06-30'");
+        assert_equal(&date, &PropertyValue::parse_datetime("2015-06-30").expect("Not parsed"));
+        assert_equal(
+            &date,
+            &PropertyValue::parse_datetime("2015-06-30T02:00:00+02:00").expect("Not parsed"),
+        );
+
+        let datetime =
+            PropertyValue::parse_datetime("2015-06-30 10:15:00.25").expect("Datetime not parsed");
+        assert_eq!(datetime.to_string(), "datetime '2015-06-30T10:15:00.250'");
+        assert_less_than(&date, &datetime);
+        assert_greater_than(&date, &PropertyValue::parse_date("1969-12-31").expect("Not parsed"));
+
+        assert!(PropertyValue::parse_date("2015-02-29").is_none());
+        assert!(PropertyValue::parse_datetime("30/06/2015").is_none());
+    }
+
+    #[test]
+    fn datetime_range_comparisons() {
+        let date = |value| PropertyValue::parse_date(value).expect("Date not parsed");
+        let datetime = |value| PropertyValue::parse_datetime(value).expect("Datetime not parsed");
+
+        // A date is the start of its day, so the datetimes of that day are not before it.
+        let day = date("2016-02-29");
+        assert_less_than(&datetime("2016-02-28T23:59:59.999"), &day);
+        assert_equal(&datetime("2016-02-29 00:00:00"), &day);
+        assert_greater_than(&datetime("2016-02-29T00:00:00.001"), &day);
+        assert_less_than(&day, &date("2016-03-01"));
+
+        // Datetimes with offsets are compared in UTC.
+        assert_equal(&datetime("2016-02-29T23:30:00-01:00"), &datetime("2016-03-01T00:30:00"));
+        assert_less_than(&datetime("2016-03-01T00:30:00+01:00"), &date("2016-03-01"));
+
+        // Dates before the epoch are negative and keep their order.
+        assert_eq!(date("1969-12-31"), PropertyValue::DateTime(-86_400_000));
+        assert_eq!(date("1969-12-31").to_string(), "date '1969-12-31'");
+        assert_eq!(
+            datetime("1969-12-31T23:59:59.5").to_string(),
+            "datetime '1969-12-31T23:59:59.500'"
+        );
+        assert_less_than(&date("1900-01-01"), &datetime("1969-12-31T23:59:59.5"));
+
+        assert!(day.compare_list(&[date("2016-01-01"), date("2016-02-29")], Operator::In));
+        assert!(!day.compare_list(&[datetime("2016-02-29T12:00:00")], Operator::In));
+    }
+
+    #[test]
+    fn datetime_other_type_comparisons() {
+        let day = PropertyValue::parse_date("1970-01-02").expect("Date not parsed");
+        for other in &[
+            PropertyValue::Isize(86_400_000),
+            PropertyValue::String("1970-01-02".to_owned()),
+            PropertyValue::Float(Float(86_400_000.0)),
+        ] {
+            assert!(!day.compare(other, Operator::Equal), "{}", other);
+            assert!(day.compare(other, Operator::NotEqual), "{}", other);
+        }
+    }
+
+    #[test]
+    fn like_string_comparisons() {
+        let value = PropertyValue::String("waterloo".to_owned());
+        for (pattern, expected) in vec![
+            ("waterloo", true),
+            ("water%", true),
+            ("%loo", true),
+            ("w%l%o", true),
+            ("w_terloo", true),
+            ("%", true),
+            ("water", false),
+            ("Waterloo", false),
+            ("w_loo", false),
+            ("waterloo_", false),
+        ] {
+            let pattern = PropertyValue::String(pattern.to_owned());
+            assert_eq!(value.compare(&pattern, Operator::Like), expected, "{}", pattern);
+        }
+        let pattern = PropertyValue::String("WATER%".to_owned());
+        assert!(value.compare(&pattern, Operator::ILike));
+        assert!(!value.compare(&pattern, Operator::Like));
+        assert!(!PropertyValue::Isize(1).compare(&pattern, Operator::Like));
+
+        // Unlike `ILike`, `IEqual` has no wildcards.
+        assert!(value.compare(&PropertyValue::String("WaterLoo".to_owned()), Operator::IEqual));
+        assert!(!value.compare(&pattern, Operator::IEqual));
+        let wildcards = PropertyValue::String("50%_off".to_owned());
+        assert!(wildcards.compare(&PropertyValue::String("50%_OFF".to_owned()), Operator::IEqual));
+        assert!(!value.compare(&PropertyValue::String("WATERLOO_".to_owned()), Operator::IEqual));
+    }
+
+    #[test]
+    fn prefix_suffix_string_comparisons() {
+        let value = PropertyValue::String("waterloo".to_owned());
+        let prefix = PropertyValue::String("water".to_owned());
+        let suffix = PropertyValue::String("loo".to_owned());
+
+        assert!(value.compare(&prefix, Operator::StartsWith));
+        assert!(!value.compare(&suffix, Operator::StartsWith));
+        assert!(value.compare(&suffix, Operator::EndsWith));
+        assert!(!value.compare(&prefix, Operator::EndsWith));
+    }
+
+    #[test]
+    fn contains_strings_comparisons() {
+        let values = PropertyValue::Strings(vec!["rust".to_owned(), "graphs".to_owned()]);
+        let present = PropertyValue::String("graphs".to_owned());
+        let absent = PropertyValue::String("graph".to_owned());
+
+        assert!(values.compare(&present, Operator::Contains));
+        assert!(!values.compare(&absent, Operator::Contains));
+        assert!(present.compare(&absent, Operator::Contains));
+
+        assert!(values.compare_list(&[absent.clone(), present], Operator::AnyIn));
+        assert!(!values.compare_list(&[absent], Operator::AnyIn));
+    }
+
+    #[test]
+    fn like_patterns() {
+        for &(value, pattern, expected) in &[
+            ("", "", true),
+            ("", "%", true),
+            ("", "%%", true),
+            ("", "_", false),
+            ("a", "", false),
+            ("abc", "a%c", true),
+            ("ac", "a%c", true),
+            ("abcbc", "a%bc", true),
+            ("abcbd", "a%bc", false),
+            // The last `%` backtracks over a partial match.
+            ("aaab", "%aab", true),
+            ("mississippi", "%iss%ppi", true),
+            ("mississippi", "m%s_s%", true),
+            ("mississippi", "%p_s%", false),
+            ("abc", "___", true),
+            ("abc", "__", false),
+            ("abc", "_%_%_", true),
+            ("abc", "_%_%__", false),
+            // Characters are matched as a whole, not byte by byte.
+            ("na\u{ef}ve", "na_ve", true),
+            ("\u{65e5}\u{672c}", "_\u{672c}%", true),
+        ] {
+            assert_eq!(like_matches(value, pattern), expected, "'{}' like '{}'", value, pattern);
+        }
+    }
+
+    #[test]
+    fn list_membership_comparisons() {
+        let strings = |values: &[&str]| {
+            values.iter().map(|&value| PropertyValue::String(value.to_owned())).collect::<Vec<_>>()
+        };
+        let city = PropertyValue::String("pune".to_owned());
+
+        assert!(city.compare_list(&strings(&["toronto", "pune"]), Operator::In));
+        assert!(!city.compare_list(&strings(&["toronto", "Pune"]), Operator::In));
+        assert!(!city.compare_list(&[], Operator::In));
+        assert!(city.compare_list(&strings(&["toronto", "Pune"]), Operator::NotIn));
+        assert!(city.compare_list(&[], Operator::NotIn));
+        // A scalar value is in any of the values if it is one of them.
+        assert!(city.compare_list(&strings(&["toronto", "pune"]), Operator::AnyIn));
+        assert!(!city.compare_list(&strings(&["toronto"]), Operator::AnyIn));
+
+        // Values of other types never match.
+        let id = PropertyValue::Isize(3);
+        assert!(id.compare_list(&[PropertyValue::Isize(1), PropertyValue::Isize(3)], Operator::In));
+        assert!(!id.compare_list(&strings(&["3"]), Operator::In));
+        assert!(id.compare_list(&strings(&["3"]), Operator::NotIn));
+    }
+
+    #[test]
+    fn substring_comparisons() {
+        let value = PropertyValue::String("waterloo".to_owned());
+        for &(substring, expected) in
+            &[("ater", true), ("", true), ("waterloo", true), ("Loo", false)]
+        {
+            let substring = PropertyValue::String(substring.to_owned());
+            assert_eq!(value.compare(&substring, Operator::Contains), expected, "{}", substring);
+        }
+        assert!(!value.compare(&PropertyValue::Isize(1), Operator::Contains));
+        assert!(!value.compare(&PropertyValue::String("water".to_owned()), Operator::EndsWith));
+        assert!(!PropertyValue::Isize(10).compare(&value, Operator::StartsWith));
+    }
+
+    #[test]
+    fn int_list_comparisons() {
+        let values = PropertyValue::IntList(vec![3, -1, 7]);
+
+        assert!(values.compare(&PropertyValue::Isize(-1), Operator::Contains));
+        assert!(!values.compare(&PropertyValue::Isize(1), Operator::Contains));
+        assert!(!values.compare(&PropertyValue::String("3".to_owned()), Operator::Contains));
+
+        let others = [PropertyValue::Isize(5), PropertyValue::Isize(7)];
+        assert!(values.compare_list(&others, Operator::AnyIn));
+        assert!(!values.compare_list(&others[..1], Operator::AnyIn));
+        assert!(!values.compare_list(&[PropertyValue::String("7".to_owned())], Operator::AnyIn));
+    }
+
+    #[test]
+    fn empty_list_comparisons() {
+        let strings = PropertyValue::Strings(vec![]);
+        let integers = PropertyValue::IntList(vec![]);
+        let value = PropertyValue::String(String::new());
+
+        assert!(!strings.compare(&value, Operator::Contains));
+        assert!(!integers.compare(&PropertyValue::Isize(0), Operator::Contains));
+        assert!(!strings.compare_list(std::slice::from_ref(&value), Operator::AnyIn));
+        assert!(!integers.compare_list(&[PropertyValue::Isize(0)], Operator::AnyIn));
+
+        // Lists with an empty string are not empty.
+        let strings = PropertyValue::Strings(vec![String::new()]);
+        assert!(strings.compare(&value, Operator::Contains));
+        assert!(strings.compare_list(&[value], Operator::AnyIn));
+    }
+
+    #[test]
+    fn list_equality_comparisons() {
+        let values = PropertyValue::Strings(vec!["rust".to_owned(), "graphs".to_owned()]);
+        assert_equal(&values, &values.clone());
+        // Lists are ordered element by element.
+        assert_less_than(&values, &PropertyValue::Strings(vec!["rust".to_owned(), "z".to_owned()]));
+        assert_less_than(&PropertyValue::Strings(vec!["rust".to_owned()]), &values);
+        assert_less_than(&PropertyValue::IntList(vec![1, 2]), &PropertyValue::IntList(vec![1, 3]));
+        assert_equal(&PropertyValue::IntList(vec![]), &PropertyValue::IntList(vec![]));
+    }
+
     fn assert_equal(left: &PropertyValue, right: &PropertyValue) {
         assert!(left.compare(&right, Operator::Equal));
         assert!(!left.compare(&right, Operator::NotEqual));
@@ -1,11 +1,15 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum PropertyValueType {
     KeyId,
     Isize,
     String,
     Strings,
+    IntList,
     Bool,
     Pair,
+    Float,
+    FloatPair,
+    DateTime,
 }
 
 impl std::fmt::Display for PropertyValueType {
@@ -18,8 +22,12 @@ impl std::fmt::Display for PropertyValueType {
                 PropertyValueType::Isize => "ISize",
                 PropertyValueType::String => "String",
                 PropertyValueType::Strings => "Strings",
+                PropertyValueType::IntList => "IntList",
                 PropertyValueType::Bool => "Bool",
                 PropertyValueType::Pair => "Pair",
+                PropertyValueType::Float => "Float",
+                PropertyValueType::FloatPair => "FloatPair",
+                PropertyValueType::DateTime => "DateTime",
             }
         )
     }
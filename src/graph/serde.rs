@@ -1,5 +1,8 @@
 use crate::error::GSError;
-use crate::global_store::{deserialize_object, serialize_object};
+use crate::global_store::{
+    check_format_version, check_id_size, deserialize_object, serialize_format_version,
+    serialize_id_size, serialize_object,
+};
 use crate::graph::Graph;
 use crossbeam_utils::thread;
 use crossbeam_utils::thread::Scope;
@@ -8,12 +11,14 @@ use log::info;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-const SERDE_FILE_VERTICES: &str = "graph_vertices";
-const SERDE_FILE_VERTICES_BLOCK_COUNT: &str = "graph_vertices_block_count";
-const SERDE_FILE_VERTICES_LEN: &str = "graph_vertices_len";
+const SERDE_FILE_VERTEX_PROPERTIES: &str = "graph_vertex_properties";
+const SERDE_FILE_EDGE_PROPERTIES: &str = "graph_edge_properties";
 const SERDE_FILE_EDGES: &str = "graph_edges";
 const SERDE_FILE_EDGES_BLOCK_COUNT: &str = "graph_edges_block_count";
 const SERDE_FILE_EDGES_LEN: &str = "graph_edges_len";
+const SERDE_FILE_DELETED_EDGES: &str = "graph_deleted_edges";
+const SERDE_FILE_ID_SIZE: &str = "graph_id_size";
+const SERDE_FILE_FORMAT_VERSION: &str = "graph_format_version";
 
 pub const DEFAULT_SERDE_THREADS: usize = 6;
 const MIN_BLOCK_SIZE: usize = 500;
@@ -25,20 +30,12 @@ pub fn serialize(
     block_size: Option<usize>,
 ) -> Result<(), GSError> {
     let res = thread::scope(|s| {
-        // Vertices.
-        let vertices_thread = s.spawn(|r| {
-            serialize_blocks(
-                r,
-                bin_dir,
-                thread_count,
-                block_size,
-                &graph.vertices,
-                "vertices",
-                SERDE_FILE_VERTICES,
-                SERDE_FILE_VERTICES_BLOCK_COUNT,
-                SERDE_FILE_VERTICES_LEN,
-                MIN_BLOCK_SIZE,
-            )
+        // Vertex and edge properties.
+        let vertex_properties_thread = s.spawn(|_| {
+            serialize_object(bin_dir, SERDE_FILE_VERTEX_PROPERTIES, &graph.vertex_properties)
+        });
+        let edge_properties_thread = s.spawn(|_| {
+            serialize_object(bin_dir, SERDE_FILE_EDGE_PROPERTIES, &graph.edge_properties)
         });
 
         // Edges.
@@ -57,13 +54,17 @@ pub fn serialize(
             )
         });
 
-        vertices_thread.join().expect("Error joining vertices_thread")?;
+        vertex_properties_thread.join().expect("Error joining vertex_properties_thread")?;
+        edge_properties_thread.join().expect("Error joining edge_properties_thread")?;
         edges_thread.join().expect("Error joining edges_thread")?;
+        serialize_object(bin_dir, SERDE_FILE_DELETED_EDGES, &graph.deleted_edges)?;
+        serialize_id_size(bin_dir, SERDE_FILE_ID_SIZE)?;
+        serialize_format_version(bin_dir, SERDE_FILE_FORMAT_VERSION)?;
 
         Ok(())
     })
     .expect("Error ending serialization scope");
-    info!("Serialized {} vertices and {} edges", graph.vertices.len(), graph.edges.len());
+    info!("Serialized {} vertices and {} edges", graph.vertex_count(), graph.edges.len());
     res
 }
 
@@ -119,19 +120,14 @@ pub fn serialize_blocks<'a, T: Serialize + Sync>(
 }
 
 pub fn deserialize(graph: &mut Graph, bin_dir: &str, thread_count: usize) -> Result<(), GSError> {
+    check_format_version(bin_dir, SERDE_FILE_FORMAT_VERSION)?;
+    check_id_size(bin_dir, SERDE_FILE_ID_SIZE)?;
     let res = thread::scope(|s| {
-        // Vertices.
-        let vertices_thread = s.spawn(|r| {
-            deserialize_blocks(
-                r,
-                bin_dir,
-                thread_count,
-                "vertices",
-                SERDE_FILE_VERTICES,
-                SERDE_FILE_VERTICES_BLOCK_COUNT,
-                SERDE_FILE_VERTICES_LEN,
-            )
-        });
+        // Vertex and edge properties.
+        let vertex_properties_thread =
+            s.spawn(|_| deserialize_object(bin_dir, SERDE_FILE_VERTEX_PROPERTIES));
+        let edge_properties_thread =
+            s.spawn(|_| deserialize_object(bin_dir, SERDE_FILE_EDGE_PROPERTIES));
 
         // Edges.
         let edges_thread = s.spawn(|r| {
@@ -146,13 +142,17 @@ pub fn deserialize(graph: &mut Graph, bin_dir: &str, thread_count: usize) -> Res
             )
         });
 
-        graph.vertices = vertices_thread.join().expect("Error joining vertices_thread")?;
+        graph.vertex_properties =
+            vertex_properties_thread.join().expect("Error joining vertex_properties_thread")?;
+        graph.edge_properties =
+            edge_properties_thread.join().expect("Error joining edge_properties_thread")?;
         graph.edges = edges_thread.join().expect("Error joining edges_thread")?;
+        graph.deleted_edges = deserialize_object(bin_dir, SERDE_FILE_DELETED_EDGES)?;
 
         Ok(())
     })
     .expect("Error ending serialization scope");
-    info!("Deserialized {} vertices and {} edges", graph.vertices.len(), graph.edges.len());
+    info!("Deserialized {} vertices and {} edges", graph.vertex_count(), graph.edges.len());
     res
 }
 
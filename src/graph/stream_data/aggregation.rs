@@ -1,9 +1,10 @@
 use crate::graph::key_store::{KeyId, KeyStore};
-use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::property_value::{Float, PropertyValue};
 use crate::graph::GraphPointer;
 use crate::graph::VertexOrEdgeId;
 use abomonation_derive::Abomonation;
 use gs_analytics_api::EdgeId;
+use std::borrow::Cow;
 use std::convert::TryFrom;
 
 #[derive(Debug, Clone, Copy, Abomonation, Eq, PartialEq)]
@@ -34,20 +35,12 @@ pub fn get_aggregates(
             };
             PropertyValue::Isize(isize::try_from(count).expect("Count value overflow"))
         }
-        AggregationOperation::Avg => {
-            let mut count = 0;
-            let mut sum = 0;
-            for &vertex_id in data.iter() {
-                let pv = PropertyValue::get_id(vertex_id);
-                if let Some(PropertyValue::Isize(value)) =
-                    graph_pointer.get_vertex_id_property_value(vertex_id, &pv, property_key_id)
-                {
-                    sum += value;
-                    count += 1;
-                }
-            }
-            PropertyValue::Pair(sum, count)
-        }
+        AggregationOperation::Avg => get_average(data.iter().map(|&vertex_id| {
+            let pv = PropertyValue::get_id(vertex_id);
+            graph_pointer
+                .get_vertex_id_property_value(vertex_id, &pv, property_key_id)
+                .map(Cow::into_owned)
+        })),
     }
 }
 
@@ -74,19 +67,31 @@ pub fn get_edge_aggregates(
             };
             PropertyValue::Isize(isize::try_from(count).expect("Count value overflow"))
         }
-        AggregationOperation::Avg => {
-            let mut count = 0;
-            let mut sum = 0;
-            for &edge_id in edges.iter() {
-                let pv = PropertyValue::get_id(edge_id);
-                if let Some(PropertyValue::Isize(value)) =
-                    graph_pointer.get_edge_id_property_value(edge_id, &pv, property_key_id)
-                {
-                    sum += value;
-                    count += 1;
-                }
-            }
-            PropertyValue::Pair(sum, count)
+        AggregationOperation::Avg => get_average(edges.iter().map(|&edge_id| {
+            let pv = PropertyValue::get_id(edge_id);
+            graph_pointer
+                .get_edge_id_property_value(edge_id, &pv, property_key_id)
+                .map(Cow::into_owned)
+        })),
+    }
+}
+
+/// Returns the partial sum and count of the numeric values as a `PropertyValue::Pair`, or as a
+/// `PropertyValue::FloatPair` if any of the values is a float.
+fn get_average(values: impl Iterator<Item = Option<PropertyValue>>) -> PropertyValue {
+    let mut count = 0;
+    let mut sum = 0;
+    let mut float_sum = None;
+    for value in values {
+        match value {
+            Some(PropertyValue::Isize(value)) => sum += value,
+            Some(PropertyValue::Float(value)) => *float_sum.get_or_insert(0.0) += value.0,
+            _ => continue,
         }
+        count += 1;
+    }
+    match float_sum {
+        Some(float_sum) => PropertyValue::FloatPair(Float(float_sum + sum as f64), count),
+        None => PropertyValue::Pair(sum, count),
     }
 }
@@ -1,9 +1,12 @@
-use crate::graph::properties::operations::{LeftOperand, Operand, Operator, RightOperand};
+use crate::graph::properties::operations::{
+    Expression, LeftOperand, Operand, Operator, RightOperand,
+};
 use crate::graph::properties::property_value::PropertyValue;
-use crate::graph::Edge;
 use crate::graph::GraphPointer;
+use crate::graph::{Edge, Graph};
 use crate::query_handler::create_view::{PredicateFunction, PredicateFunctionClosure};
 use gs_analytics_api::EdgeId;
+use std::borrow::Cow;
 use std::convert::TryFrom;
 
 use std::sync::Arc;
@@ -13,28 +16,51 @@ pub fn get_edge_closure(
     operator: Operator,
     operand2: RightOperand,
 ) -> PredicateFunction {
+    let operand1 = match operand1 {
+        LeftOperand::Operand(operand) if matches!(operand2, RightOperand::Null) => {
+            return get_edge_null_closure(operand, operator);
+        }
+        LeftOperand::Operand(operand) if !operand2.is_expression() => operand,
+        operand1 => {
+            return get_edge_expression_closure(operand1.into_expression(), operator, operand2)
+        }
+    };
     let closure: PredicateFunctionClosure = match operand1 {
         Operand::Property(left_edge_pki) => match operand2 {
             RightOperand::Value(right_value) => {
                 Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
                     let graph = &*graph_pointer;
-                    let edge = graph.get_edge(edge_id);
 
                     let pv = PropertyValue::get_id(edge_id);
-                    let left = graph.get_edge_property_value(edge, &pv, left_edge_pki);
+                    let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
 
                     compare_left_opt_right(left, &right_value, operator)
                 })
             }
+            RightOperand::List(right_values) => {
+                Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                    let graph = &*graph_pointer;
+
+                    let pv = PropertyValue::get_id(edge_id);
+                    let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
+
+                    compare_left_opt_list(left, &right_values, operator)
+                })
+            }
+            RightOperand::Expression(_) => {
+                unreachable!("Expressions should be handled by `get_edge_expression_closure`")
+            }
+            RightOperand::Null => {
+                unreachable!("Null checks should be handled by `get_edge_null_closure`")
+            }
             RightOperand::Variable(right_variable) => match right_variable {
                 Operand::Property(right_edge_pki) => {
                     Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
                         let graph = &*graph_pointer;
-                        let edge = graph.get_edge(edge_id);
 
                         let pv = PropertyValue::get_id(edge_id);
-                        let left = graph.get_edge_property_value(edge, &pv, left_edge_pki);
-                        let right = graph.get_edge_property_value(edge, &pv, right_edge_pki);
+                        let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
+                        let right = graph.get_edge_id_property_value(edge_id, &pv, right_edge_pki);
 
                         compare_left_opt_right_opt(left, right, operator)
                     })
@@ -44,7 +70,7 @@ pub fn get_edge_closure(
                     let edge = graph.get_edge(edge_id);
 
                     let pv = PropertyValue::get_id(edge_id);
-                    let left = graph.get_edge_property_value(edge, &pv, left_edge_pki);
+                    let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
                     let right_value = get_edge_pair(edge);
 
                     compare_left_opt_right(left, &right_value, operator)
@@ -55,7 +81,7 @@ pub fn get_edge_closure(
                         let edge = graph.get_edge(edge_id);
 
                         let pv = PropertyValue::get_id(edge_id);
-                        let left = graph.get_edge_property_value(edge, &pv, left_edge_pki);
+                        let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
 
                         let pv = PropertyValue::get_id(edge.src_vertex_id);
                         let right = graph.get_vertex_id_property_value(
@@ -73,7 +99,7 @@ pub fn get_edge_closure(
                         let edge = graph.get_edge(edge_id);
 
                         let pv = PropertyValue::get_id(edge_id);
-                        let left = graph.get_edge_property_value(edge, &pv, left_edge_pki);
+                        let left = graph.get_edge_id_property_value(edge_id, &pv, left_edge_pki);
 
                         let pv2 = PropertyValue::get_id(edge.dst_vertex_id);
                         let right = graph.get_vertex_id_property_value(
@@ -94,9 +120,24 @@ pub fn get_edge_closure(
                     let edge = graph.get_edge(edge_id);
                     let left_value = get_edge_pair(edge);
 
-                    left_value.compare(&right_value, operator)
+                    Some(left_value.compare(&right_value, operator))
+                })
+            }
+            RightOperand::List(right_values) => {
+                Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                    let graph = &*graph_pointer;
+                    let edge = graph.get_edge(edge_id);
+                    let left_value = get_edge_pair(edge);
+
+                    Some(left_value.compare_list(&right_values, operator))
                 })
             }
+            RightOperand::Expression(_) => {
+                unreachable!("Expressions should be handled by `get_edge_expression_closure`")
+            }
+            RightOperand::Null => {
+                unreachable!("Null checks should be handled by `get_edge_null_closure`")
+            }
             RightOperand::Variable(right_variable) => match right_variable {
                 Operand::Property(right_edge_pki) => {
                     Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
@@ -105,7 +146,7 @@ pub fn get_edge_closure(
                         let left_value = get_edge_pair(edge);
 
                         let pv = PropertyValue::get_id(edge_id);
-                        let right = graph.get_edge_property_value(edge, &pv, right_edge_pki);
+                        let right = graph.get_edge_id_property_value(edge_id, &pv, right_edge_pki);
 
                         compare_left_right_opt(&left_value, right, operator)
                     })
@@ -116,7 +157,7 @@ pub fn get_edge_closure(
 
                     let edge_value = get_edge_pair(edge);
 
-                    edge_value.compare(&edge_value, operator)
+                    Some(edge_value.compare(&edge_value, operator))
                 }),
                 Operand::SourceVertex(right_src_pki) => {
                     Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
@@ -165,6 +206,24 @@ pub fn get_edge_closure(
                     compare_left_opt_right(left, &right_value, operator)
                 })
             }
+            RightOperand::List(right_values) => {
+                Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                    let graph = &*graph_pointer;
+                    let edge = graph.get_edge(edge_id);
+
+                    let pv = PropertyValue::get_id(edge.src_vertex_id);
+                    let left =
+                        graph.get_vertex_id_property_value(edge.src_vertex_id, &pv, left_src_pki);
+
+                    compare_left_opt_list(left, &right_values, operator)
+                })
+            }
+            RightOperand::Expression(_) => {
+                unreachable!("Expressions should be handled by `get_edge_expression_closure`")
+            }
+            RightOperand::Null => {
+                unreachable!("Null checks should be handled by `get_edge_null_closure`")
+            }
             RightOperand::Variable(right_variable) => match right_variable {
                 Operand::Property(right_edge_pki) => {
                     Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
@@ -179,7 +238,7 @@ pub fn get_edge_closure(
                         );
 
                         let pv2 = PropertyValue::get_id(edge_id);
-                        let right = graph.get_edge_property_value(edge, &pv2, right_edge_pki);
+                        let right = graph.get_edge_id_property_value(edge_id, &pv2, right_edge_pki);
 
                         compare_left_opt_right_opt(left, right, operator)
                     })
@@ -202,10 +261,10 @@ pub fn get_edge_closure(
                         let edge = graph.get_edge(edge_id);
 
                         let vertex_id = edge.src_vertex_id;
-                        let vertex = graph.get_vertex(vertex_id);
                         let pv = PropertyValue::get_id(vertex_id);
-                        let left = graph.get_vertex_property_value(vertex, &pv, left_src_pki);
-                        let right = graph.get_vertex_property_value(vertex, &pv, right_src_pki);
+                        let left = graph.get_vertex_id_property_value(vertex_id, &pv, left_src_pki);
+                        let right =
+                            graph.get_vertex_id_property_value(vertex_id, &pv, right_src_pki);
 
                         compare_left_opt_right_opt(left, right, operator)
                     })
@@ -247,6 +306,24 @@ pub fn get_edge_closure(
                     compare_left_opt_right(left, &right_value, operator)
                 })
             }
+            RightOperand::List(right_values) => {
+                Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                    let graph = &*graph_pointer;
+                    let edge = graph.get_edge(edge_id);
+
+                    let pv = PropertyValue::get_id(edge.dst_vertex_id);
+                    let left =
+                        graph.get_vertex_id_property_value(edge.dst_vertex_id, &pv, left_dst_pki);
+
+                    compare_left_opt_list(left, &right_values, operator)
+                })
+            }
+            RightOperand::Expression(_) => {
+                unreachable!("Expressions should be handled by `get_edge_expression_closure`")
+            }
+            RightOperand::Null => {
+                unreachable!("Null checks should be handled by `get_edge_null_closure`")
+            }
             RightOperand::Variable(right_variable) => match right_variable {
                 Operand::Property(right_edge_pki) => {
                     Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
@@ -261,7 +338,7 @@ pub fn get_edge_closure(
                         );
 
                         let pv2 = PropertyValue::get_id(edge_id);
-                        let right = graph.get_edge_property_value(edge, &pv2, right_edge_pki);
+                        let right = graph.get_edge_id_property_value(edge_id, &pv2, right_edge_pki);
 
                         compare_left_opt_right_opt(left, right, operator)
                     })
@@ -306,10 +383,10 @@ pub fn get_edge_closure(
                         let edge = graph.get_edge(edge_id);
 
                         let vertex_id = edge.dst_vertex_id;
-                        let vertex = graph.get_vertex(vertex_id);
                         let pv = PropertyValue::get_id(vertex_id);
-                        let left = graph.get_vertex_property_value(vertex, &pv, left_dst_pki);
-                        let right = graph.get_vertex_property_value(vertex, &pv, right_dst_pki);
+                        let left = graph.get_vertex_id_property_value(vertex_id, &pv, left_dst_pki);
+                        let right =
+                            graph.get_vertex_id_property_value(vertex_id, &pv, right_dst_pki);
 
                         compare_left_opt_right_opt(left, right, operator)
                     })
@@ -320,33 +397,139 @@ pub fn get_edge_closure(
     PredicateFunction::new(closure)
 }
 
+/// Closure for `is null` and `is not null` predicates. These are never unknown.
+fn get_edge_null_closure(operand1: Operand, operator: Operator) -> PredicateFunction {
+    let is_null = matches!(operator, Operator::Is);
+    let closure: PredicateFunctionClosure = match operand1 {
+        Operand::Property(key_id) => {
+            Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                let graph = &*graph_pointer;
+
+                let pv = PropertyValue::get_id(edge_id);
+                Some(graph.get_edge_id_property_value(edge_id, &pv, key_id).is_none() == is_null)
+            })
+        }
+        Operand::SourceVertex(key_id) => {
+            Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                let graph = &*graph_pointer;
+                let edge = graph.get_edge(edge_id);
+
+                let pv = PropertyValue::get_id(edge.src_vertex_id);
+                let value = graph.get_vertex_id_property_value(edge.src_vertex_id, &pv, key_id);
+                Some(value.is_none() == is_null)
+            })
+        }
+        Operand::DestinationVertex(key_id) => {
+            Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                let graph = &*graph_pointer;
+                let edge = graph.get_edge(edge_id);
+
+                let pv = PropertyValue::get_id(edge.dst_vertex_id);
+                let value = graph.get_vertex_id_property_value(edge.dst_vertex_id, &pv, key_id);
+                Some(value.is_none() == is_null)
+            })
+        }
+        Operand::Edge => unreachable!("Edge operand is never null"),
+    };
+    PredicateFunction::new(closure)
+}
+
+/// Closure for predicates where at least one side is an arithmetic expression.
+fn get_edge_expression_closure(
+    expression1: Expression,
+    operator: Operator,
+    operand2: RightOperand,
+) -> PredicateFunction {
+    let closure: PredicateFunctionClosure = match operand2 {
+        RightOperand::List(right_values) => {
+            Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                let graph = &*graph_pointer;
+                let get_operand_value = |operand| get_edge_operand_isize(graph, edge_id, operand);
+
+                expression1.evaluate(&get_operand_value).map(|left_value| {
+                    PropertyValue::Isize(left_value).compare_list(&right_values, operator)
+                })
+            })
+        }
+        operand2 => {
+            let expression2 =
+                operand2.into_expression().expect("Right operand should be an integer expression");
+            Arc::new(move |edge_id: EdgeId, graph_pointer: GraphPointer| {
+                let graph = &*graph_pointer;
+                let get_operand_value = |operand| get_edge_operand_isize(graph, edge_id, operand);
+
+                expression1.evaluate(&get_operand_value).and_then(|left_value| {
+                    expression2.evaluate(&get_operand_value).map(|right_value| {
+                        PropertyValue::Isize(left_value)
+                            .compare(&PropertyValue::Isize(right_value), operator)
+                    })
+                })
+            })
+        }
+    };
+    PredicateFunction::new(closure)
+}
+
+#[inline(always)]
+fn get_edge_operand_isize(graph: &Graph, edge_id: EdgeId, operand: Operand) -> Option<isize> {
+    let edge = graph.get_edge(edge_id);
+    match operand {
+        Operand::Property(key_id) => {
+            let pv = PropertyValue::get_id(edge_id);
+            graph
+                .get_edge_id_property_value(edge_id, &pv, key_id)
+                .and_then(|value| value.as_isize())
+        }
+        Operand::SourceVertex(key_id) => {
+            let pv = PropertyValue::get_id(edge.src_vertex_id);
+            graph
+                .get_vertex_id_property_value(edge.src_vertex_id, &pv, key_id)
+                .and_then(|value| value.as_isize())
+        }
+        Operand::DestinationVertex(key_id) => {
+            let pv = PropertyValue::get_id(edge.dst_vertex_id);
+            graph
+                .get_vertex_id_property_value(edge.dst_vertex_id, &pv, key_id)
+                .and_then(|value| value.as_isize())
+        }
+        Operand::Edge => unreachable!("Edge operand cannot be used in arithmetic expressions"),
+    }
+}
+
 #[inline(always)]
 fn compare_left_opt_right_opt(
-    left: Option<&PropertyValue>,
-    right: Option<&PropertyValue>,
+    left: Option<Cow<PropertyValue>>,
+    right: Option<Cow<PropertyValue>>,
     operator: Operator,
-) -> bool {
-    left.map_or(false, |left_value| {
-        right.map_or(false, |right_value| left_value.compare(&right_value, operator))
-    })
+) -> Option<bool> {
+    left.and_then(|left_value| right.map(|right_value| left_value.compare(&right_value, operator)))
 }
 
 #[inline(always)]
 fn compare_left_right_opt(
     left_value: &PropertyValue,
-    right: Option<&PropertyValue>,
+    right: Option<Cow<PropertyValue>>,
     operator: Operator,
-) -> bool {
-    right.map_or(false, |right_value| left_value.compare(&right_value, operator))
+) -> Option<bool> {
+    right.map(|right_value| left_value.compare(&right_value, operator))
 }
 
 #[inline(always)]
 fn compare_left_opt_right(
-    left: Option<&PropertyValue>,
+    left: Option<Cow<PropertyValue>>,
     right_value: &PropertyValue,
     operator: Operator,
-) -> bool {
-    left.map_or(false, |left_value| left_value.compare(right_value, operator))
+) -> Option<bool> {
+    left.map(|left_value| left_value.compare(right_value, operator))
+}
+
+#[inline(always)]
+fn compare_left_opt_list(
+    left: Option<Cow<PropertyValue>>,
+    right_values: &[PropertyValue],
+    operator: Operator,
+) -> Option<bool> {
+    left.map(|left_value| left_value.compare_list(right_values, operator))
 }
 
 #[inline(always)]
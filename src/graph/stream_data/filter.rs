@@ -7,38 +7,77 @@ pub fn test_where_conditions(
     where_conditions: &[WhereCondition],
     graph_pointer: GraphPointer,
 ) -> bool {
-    for (is_negation, where_predicates) in where_conditions {
-        let is_success = where_predicates
-            .iter()
-            .all(|(_, closure)| test_edge_where_predicate(closure, id, graph_pointer));
-        if (*is_negation && is_success) || (!*is_negation && !is_success) {
-            return false;
+    evaluate_where_conditions(id, where_conditions, graph_pointer) == Some(true)
+}
+
+/// Evaluates a conjunction using three-valued logic, where `None` is unknown. A conjunction is
+/// false if any condition is false, and unknown if no condition is false but some are unknown.
+fn evaluate_where_conditions(
+    id: VertexOrEdgeId,
+    where_conditions: &[WhereCondition],
+    graph_pointer: GraphPointer,
+) -> Option<bool> {
+    let mut result = Some(true);
+    for where_condition in where_conditions {
+        match evaluate_where_condition(id, where_condition, graph_pointer) {
+            Some(false) => return Some(false),
+            None => result = None,
+            Some(true) => {}
+        }
+    }
+    result
+}
+
+fn evaluate_where_condition(
+    id: VertexOrEdgeId,
+    where_condition: &WhereCondition,
+    graph_pointer: GraphPointer,
+) -> Option<bool> {
+    match where_condition {
+        WhereCondition::Predicate((_, closure)) => (&*closure.0)(id, graph_pointer),
+        WhereCondition::Negation(where_conditions) => {
+            evaluate_where_conditions(id, where_conditions, graph_pointer).map(|result| !result)
+        }
+        WhereCondition::Disjunction(disjuncts) => {
+            let mut result = Some(false);
+            for where_conditions in disjuncts {
+                match evaluate_where_conditions(id, where_conditions, graph_pointer) {
+                    Some(true) => return Some(true),
+                    None => result = None,
+                    Some(false) => {}
+                }
+            }
+            result
         }
     }
-    true
 }
 
+/// Returns `false` if the predicate evaluates to unknown.
 #[inline]
 pub fn test_edge_where_predicate(
     closure: &PredicateFunction,
     id: VertexOrEdgeId,
     graph_pointer: GraphPointer,
 ) -> bool {
-    (&*closure.0)(id, graph_pointer)
+    (&*closure.0)(id, graph_pointer) == Some(true)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::global_store::GlobalStore;
-    use crate::graph::properties::operations::{Operand, Operator, RightOperand};
+    use crate::graph::properties::operations::{LeftOperand, Operand, Operator, RightOperand};
     use crate::graph::properties::property_value::PropertyValue;
     use crate::graph::stream_data::edge_data::get_edge_closure;
-    use crate::graph::stream_data::filter::test_edge_where_predicate;
-    use crate::graph::GraphPointer;
+    use crate::graph::stream_data::filter::{
+        evaluate_where_conditions, test_edge_where_predicate, test_where_conditions,
+    };
+    use crate::graph::{Graph, GraphPointer};
     use crate::process_query;
+    use crate::query_handler::create_view::{PredicateFunction, WhereCondition};
     use gs_analytics_api::EdgeId;
     use itertools::Itertools;
     use std::convert::TryFrom;
+    use std::sync::Arc;
 
     #[test]
     fn test_edge_filter() {
@@ -159,7 +198,8 @@ mod tests {
         .into_iter()
         .enumerate()
         {
-            let closure = get_edge_closure(left_operand, operator, right_operand);
+            let closure =
+                get_edge_closure(LeftOperand::Operand(left_operand), operator, right_operand);
             let filtered_edge_ids = (0..graph.edges_count())
                 .filter(|edge_id| {
                     test_edge_where_predicate(
@@ -176,4 +216,263 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_where_conditions_disjunctions_and_lists() {
+        assert_collection_edges(
+            "[year = 2000 or year = 2018],
+            [year in (2005, 2010) and include = false],
+            [!(year not in (2000) or amount > 300)],
+            [(u.city = 'waterloo' or v.city = 'pune') and amount < 1000],
+            [year = 2000 and amount = 100 or year = 2010]",
+            vec![vec![1, 2, 3, 5], vec![6], vec![1, 3], vec![1, 2, 6], vec![1, 4]],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_arithmetic() {
+        assert_collection_edges(
+            "[amount - year > -1700],
+            [amount * 2 > 1000],
+            [amount >= (year - 1995) * 40],
+            [year % 4 = 0 and -amount < -300],
+            [u.id + v.id in (3, 5)],
+            [amount / (year - 2000) > 10]",
+            vec![
+                vec![0, 4, 5, 6],
+                vec![4, 5, 6],
+                vec![3, 4, 5, 6],
+                vec![0, 5],
+                vec![0, 3],
+                vec![0, 2, 4, 6],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_string_patterns() {
+        assert_collection_edges(
+            "[u.city like 'w%loo'],
+            [v.city ilike 'PUN_'],
+            [u.city starts with 'tor' and v.city ends with 'loo'],
+            [u.country contains 'ind'],
+            [v.city any in ('pune', 'toronto')],
+            [v.city iequals 'PUNE'],
+            [v.city iequals 'PUN_']",
+            vec![vec![1, 2], vec![4, 6], vec![0, 5], vec![4], vec![2, 3, 4, 6], vec![4, 6], vec![]],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_null_checks() {
+        assert_collection_edges_in(
+            "data/small_missing_properties",
+            "",
+            "[exists(amount)],
+            [amount is null],
+            [year is not null and amount is null],
+            [amount > 200 or year = 2018],
+            [!(amount < 400 and year > 2005)],
+            [u.city is null],
+            [exists(v.city) and note is null]",
+            vec![
+                vec![0, 2, 4],
+                vec![1, 3],
+                vec![1],
+                vec![1, 2, 4],
+                vec![0, 4],
+                vec![2, 4],
+                vec![1],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_thresholds() {
+        assert_collection_edges(
+            "[year <= 2005], [year <= 2000], [year <= 2010]",
+            vec![vec![1, 3, 5, 6], vec![1, 3, 5], vec![0, 1, 3, 4, 5, 6]],
+        );
+        assert_collection_edges(
+            "[u.city > 'p'], [u.city > 'a'], [u.city > 'w']",
+            vec![vec![0, 1, 2, 3, 5, 6], vec![0, 1, 2, 3, 4, 5, 6], vec![1, 2]],
+        );
+        assert_collection_edges_in(
+            "data/small_missing_properties",
+            "",
+            "[amount < 600], [amount < 300], [amount < 100]",
+            vec![vec![0, 2, 4], vec![0], vec![]],
+        );
+        assert_collection_edges_in(
+            "data/small_float_properties",
+            "",
+            "[weight >= 1], [weight >= 0], [weight >= 2]",
+            vec![vec![1, 2], vec![0, 1, 2], vec![2]],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_floats() {
+        assert_collection_edges_in(
+            "data/small_float_properties",
+            "",
+            "[weight > 1.0],
+            [weight <= 0.5],
+            [score >= 99.5],
+            [weight = 2 or hops = 1.0],
+            [u.rank < v.rank and v.rank in (0.5, 1.0)],
+            [weight in (-0.75, 2.0)],
+            [weight in (2, 5)],
+            [hops in (1.0, 4.5) or weight not in (0.5, 2)]",
+            vec![
+                vec![1, 2],
+                vec![0, 3],
+                vec![0, 3],
+                vec![0, 2],
+                vec![1, 3],
+                vec![2, 3],
+                vec![2],
+                vec![0, 1, 3],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_dates() {
+        assert_collection_edges_in(
+            "data/small_date_properties",
+            "",
+            "[created <= date '2015-06-30'],
+            [created > date '2015-06-30' and created < date '2016-12-31'],
+            [updated >= datetime '2015-06-30T23:59:59'],
+            [updated < date '2015-07-01'],
+            [updated = datetime '2015-06-30T23:00:00Z'],
+            [u.joined < v.joined and v.joined >= date '2015-06-30']",
+            vec![vec![0, 1], vec![2, 3], vec![1, 3], vec![0, 1, 2], vec![2], vec![0, 3]],
+        );
+    }
+
+    #[test]
+    fn test_where_conditions_lists() {
+        assert_collection_edges_in(
+            "data/small_list_properties",
+            "list separator '|'",
+            "[tags contains 'blue'],
+            [tags any in ('green', 'yellow')],
+            [codes contains 5 or codes any in (3, 4)],
+            [u.authors contains 'bob' and v.authors any in ('dave', 'carol')],
+            [tags is null or codes is null]",
+            vec![vec![0, 3], vec![1, 3], vec![0, 1, 2], vec![0, 1], vec![2, 3]],
+        );
+    }
+
+    #[test]
+    fn test_three_valued_logic() {
+        let graph = Graph::default();
+        let graph_pointer = GraphPointer::new(&graph);
+        let evaluate = |where_conditions: &[WhereCondition]| {
+            evaluate_where_conditions(0, where_conditions, graph_pointer)
+        };
+        let (t, f, u) = (Some(true), Some(false), None);
+
+        // Conjunctions are false if any condition is false, and unknown if any other is unknown.
+        assert_eq!(evaluate(&[]), t);
+        for &(left, right, expected) in
+            &[(t, t, t), (t, u, u), (u, t, u), (u, u, u), (f, u, f), (u, f, f), (t, f, f)]
+        {
+            assert_eq!(
+                evaluate(&[constant(left), constant(right)]),
+                expected,
+                "{:?} and {:?}",
+                left,
+                right
+            );
+        }
+
+        // Disjunctions are true if any disjunct is true, and unknown if any other is unknown.
+        assert_eq!(evaluate(&[WhereCondition::Disjunction(vec![])]), f);
+        for &(left, right, expected) in
+            &[(f, f, f), (f, u, u), (u, f, u), (u, u, u), (t, u, t), (u, t, t), (f, t, t)]
+        {
+            let disjunction =
+                WhereCondition::Disjunction(vec![vec![constant(left)], vec![constant(right)]]);
+            assert_eq!(evaluate(&[disjunction]), expected, "{:?} or {:?}", left, right);
+        }
+
+        // Negations of unknown are unknown.
+        for &(value, expected) in &[(t, f), (f, t), (u, u)] {
+            let negation = WhereCondition::Negation(vec![constant(value)]);
+            assert_eq!(evaluate(&[negation]), expected, "not {:?}", value);
+        }
+        let negation = WhereCondition::Negation(vec![WhereCondition::Disjunction(vec![
+            vec![constant(f)],
+            vec![constant(u)],
+        ])]);
+        assert_eq!(evaluate(std::slice::from_ref(&negation)), u);
+
+        // Only true conditions select the vertex or edge.
+        assert!(test_where_conditions(0, &[constant(t)], graph_pointer));
+        assert!(!test_where_conditions(0, &[constant(u)], graph_pointer));
+        assert!(!test_where_conditions(0, &[negation], graph_pointer));
+    }
+
+    /// A predicate that always evaluates to `value`.
+    fn constant(value: Option<bool>) -> WhereCondition {
+        WhereCondition::Predicate((
+            (LeftOperand::Operand(Operand::Edge), Operator::IsNot, RightOperand::Null),
+            PredicateFunction::new(Arc::new(move |_, _| value)),
+        ))
+    }
+
+    fn assert_collection_edges(dimensions: &str, expected_edge_ids: Vec<Vec<EdgeId>>) {
+        assert_collection_edges_in("data/small_properties", "", dimensions, expected_edge_ids);
+    }
+
+    /// Creates a collection over the graph in `data_dir`, loaded with the extra `load_options`,
+    /// using the given `dimensions` and checks that the full edges of each view match the
+    /// `expected_edge_ids`.
+    fn assert_collection_edges_in(
+        data_dir: &str,
+        load_options: &str,
+        dimensions: &str,
+        expected_edge_ids: Vec<Vec<EdgeId>>,
+    ) {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = format!(
+            "load graph with vertices from '{0}/vertices.txt' and edges from '{0}/edges.txt' \
+             {1} comment '#';",
+            data_dir, load_options
+        );
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        let mut cube_query = format!(
+            "create view collection test_collection where {} manually_ordered \
+             materialize_full_view;",
+            dimensions
+        );
+        process_query(&mut global_store, &mut cube_query).expect("Collection not created");
+
+        let graph = &global_store.graph;
+        let cube =
+            global_store.filtered_cube_store.cubes.get("test_collection").expect("Cube not found");
+        for (index, ((_, _, (full_edges, _), _, _), expected)) in
+            cube.data.entries.iter().zip_eq(expected_edge_ids).enumerate()
+        {
+            let expected_edges = expected
+                .into_iter()
+                .map(|edge_id| {
+                    let edge = graph.get_edge(edge_id);
+                    (edge.src_vertex_id, edge.dst_vertex_id)
+                })
+                .sorted()
+                .collect_vec();
+            assert_eq!(
+                full_edges.iter().copied().sorted().collect_vec(),
+                expected_edges,
+                "view {} has unexpected edges",
+                index
+            );
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::graph::Graph;
 use gs_analytics_api::{EdgeId, VertexId};
+use itertools::Either;
 use log::info;
 use std::convert::TryFrom;
 
@@ -19,11 +20,12 @@ pub fn get_timely_vertex_stream(
         ..VertexId::try_from(right_index).expect("Overflow")
 }
 
+/// Edge ids are split by index, so deleted edges are skipped instead of being compacted away.
 pub fn get_timely_edgeid_stream(
     graph: &Graph,
     worker_index: usize,
     worker_count: usize,
-) -> impl Iterator<Item = EdgeId> {
+) -> impl Iterator<Item = EdgeId> + '_ {
     let (left_index, right_index) =
         get_worker_indices(graph.edges.len(), worker_index, worker_count);
     if worker_index == 0 {
@@ -34,8 +36,13 @@ pub fn get_timely_edgeid_stream(
             graph.edges.len()
         );
     }
-    EdgeId::try_from(left_index).expect("Overflow")
-        ..EdgeId::try_from(right_index).expect("Overflow")
+    let edge_ids = EdgeId::try_from(left_index).expect("Overflow")
+        ..EdgeId::try_from(right_index).expect("Overflow");
+    if graph.deleted_edges.is_empty() {
+        Either::Left(edge_ids)
+    } else {
+        Either::Right(edge_ids.filter(move |edge_id| !graph.is_edge_deleted(*edge_id)))
+    }
 }
 
 #[inline]
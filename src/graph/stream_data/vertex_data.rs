@@ -1,4 +1,6 @@
-use crate::graph::properties::operations::{LeftOperand, Operand, Operator, RightOperand};
+use crate::graph::properties::operations::{
+    Expression, LeftOperand, Operand, Operator, RightOperand,
+};
 use crate::graph::properties::property_value::PropertyValue;
 use crate::graph::{GraphPointer, VertexId};
 use crate::query_handler::create_view::{PredicateFunction, PredicateFunctionClosure};
@@ -10,6 +12,15 @@ pub fn get_vertex_closure(
     operator: Operator,
     operand2: RightOperand,
 ) -> PredicateFunction {
+    let operand1 = match operand1 {
+        LeftOperand::Operand(operand) if matches!(operand2, RightOperand::Null) => {
+            return get_vertex_null_closure(operand, operator);
+        }
+        LeftOperand::Operand(operand) if !operand2.is_expression() => operand,
+        operand1 => {
+            return get_vertex_expression_closure(operand1.into_expression(), operator, operand2)
+        }
+    };
     let closure: PredicateFunctionClosure = match operand1 {
         Operand::Property(key_id1) => match operand2 {
             RightOperand::Value(right_value) => {
@@ -17,20 +28,25 @@ pub fn get_vertex_closure(
                     let pv = PropertyValue::get_id(vertex_id);
                     graph_pointer
                         .get_vertex_id_property_value(vertex_id, &pv, key_id1)
-                        .map_or(false, |left_value| left_value.compare(&right_value, operator))
+                        .map(|left_value| left_value.compare(&right_value, operator))
+                })
+            }
+            RightOperand::List(right_values) => {
+                Arc::new(move |vertex_id: VertexId, graph_pointer: GraphPointer| {
+                    let pv = PropertyValue::get_id(vertex_id);
+                    graph_pointer
+                        .get_vertex_id_property_value(vertex_id, &pv, key_id1)
+                        .map(|left_value| left_value.compare_list(&right_values, operator))
                 })
             }
             RightOperand::Variable(Operand::Property(key_id2)) => {
                 Arc::new(move |vertex_id: VertexId, graph_pointer: GraphPointer| {
                     let pv = PropertyValue::get_id(vertex_id);
-                    graph_pointer.get_vertex_id_property_value(vertex_id, &pv, key_id2).map_or(
-                        false,
+                    graph_pointer.get_vertex_id_property_value(vertex_id, &pv, key_id2).and_then(
                         |right_value| {
                             graph_pointer
                                 .get_vertex_id_property_value(vertex_id, &pv, key_id1)
-                                .map_or(false, |left_value| {
-                                    left_value.compare(&right_value, operator)
-                                })
+                                .map(|left_value| left_value.compare(&right_value, operator))
                         },
                     )
                 })
@@ -38,8 +54,81 @@ pub fn get_vertex_closure(
             RightOperand::Variable(..) => {
                 unreachable!("Right vertex operand should not have edge properties")
             }
+            RightOperand::Expression(_) => {
+                unreachable!("Expressions should be handled by `get_vertex_expression_closure`")
+            }
+            RightOperand::Null => {
+                unreachable!("Null checks should be handled by `get_vertex_null_closure`")
+            }
         },
         _ => unreachable!("Left vertex operand should not have edge properties"),
     };
     PredicateFunction::new(closure)
 }
+
+/// Closure for `is null` and `is not null` predicates. These are never unknown.
+fn get_vertex_null_closure(operand1: Operand, operator: Operator) -> PredicateFunction {
+    let is_null = matches!(operator, Operator::Is);
+    let closure: PredicateFunctionClosure = match operand1 {
+        Operand::Property(key_id) => {
+            Arc::new(move |vertex_id: VertexId, graph_pointer: GraphPointer| {
+                let pv = PropertyValue::get_id(vertex_id);
+                let value = graph_pointer.get_vertex_id_property_value(vertex_id, &pv, key_id);
+                Some(value.is_none() == is_null)
+            })
+        }
+        _ => unreachable!("Vertex null checks should not have edge properties"),
+    };
+    PredicateFunction::new(closure)
+}
+
+/// Closure for predicates where at least one side is an arithmetic expression.
+fn get_vertex_expression_closure(
+    expression1: Expression,
+    operator: Operator,
+    operand2: RightOperand,
+) -> PredicateFunction {
+    let closure: PredicateFunctionClosure = match operand2 {
+        RightOperand::List(right_values) => {
+            Arc::new(move |vertex_id: VertexId, graph_pointer: GraphPointer| {
+                let get_operand_value =
+                    |operand| get_vertex_operand_isize(graph_pointer, vertex_id, operand);
+                expression1.evaluate(&get_operand_value).map(|left_value| {
+                    PropertyValue::Isize(left_value).compare_list(&right_values, operator)
+                })
+            })
+        }
+        operand2 => {
+            let expression2 =
+                operand2.into_expression().expect("Right operand should be an integer expression");
+            Arc::new(move |vertex_id: VertexId, graph_pointer: GraphPointer| {
+                let get_operand_value =
+                    |operand| get_vertex_operand_isize(graph_pointer, vertex_id, operand);
+                expression1.evaluate(&get_operand_value).and_then(|left_value| {
+                    expression2.evaluate(&get_operand_value).map(|right_value| {
+                        PropertyValue::Isize(left_value)
+                            .compare(&PropertyValue::Isize(right_value), operator)
+                    })
+                })
+            })
+        }
+    };
+    PredicateFunction::new(closure)
+}
+
+#[inline(always)]
+fn get_vertex_operand_isize(
+    graph_pointer: GraphPointer,
+    vertex_id: VertexId,
+    operand: Operand,
+) -> Option<isize> {
+    match operand {
+        Operand::Property(key_id) => {
+            let pv = PropertyValue::get_id(vertex_id);
+            graph_pointer
+                .get_vertex_id_property_value(vertex_id, &pv, key_id)
+                .and_then(|value| value.as_isize())
+        }
+        _ => unreachable!("Vertex expression should not have edge properties"),
+    }
+}
@@ -4,32 +4,44 @@
 
 use crate::computations::ComputationProperties;
 use crate::error::GSError;
+use crate::graph::indexes::IndexType;
 use crate::graph::key_store::KeyId;
 use crate::graph::key_store::KeyStore;
-use crate::graph::properties::operations::{Operand, Operator, RightOperand};
-use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::operations::{
+    ArithmeticOperator, Expression, LeftOperand, Operand, Operator, RightOperand,
+};
+use crate::graph::properties::property_value::{Float, PropertyValue};
+use crate::graph::properties::property_value_type::PropertyValueType;
 use crate::graph::serde::DEFAULT_SERDE_THREADS;
 use crate::graph::stream_data::aggregation::AggregationOperation;
 use crate::graph::stream_data::edge_data::get_edge_closure;
 use crate::graph::stream_data::vertex_data::get_vertex_closure;
 use crate::graph::VertexOrEdge;
+use crate::query_handler::alter_collection::AlterCollectionAst;
 use crate::query_handler::create_aggregated_cube::CreateAggregatedCubeAst;
-use crate::query_handler::create_filtered_cube::CreateViewCollectionAst;
+use crate::query_handler::create_filtered_cube::{CreateViewCollectionAst, Dimension};
+use crate::query_handler::create_index::CreateIndexAst;
 use crate::query_handler::create_view::{
     AggregateClause, CreateViewAst, GroupClause, GroupCondition, SectionDetails, WhereCondition,
     WhereConditions, WherePredicate,
 };
 use crate::query_handler::delete_cubes::DeleteCollectionsAst;
+use crate::query_handler::describe_collection::DescribeCollectionAst;
+use crate::query_handler::explain::ExplainAst;
 use crate::query_handler::generate_cube::GenerateCubeAst;
 use crate::query_handler::load_cube::LoadCubeAst;
 use crate::query_handler::load_graph::executor::DEFAULT_HAS_HEADERS;
-use crate::query_handler::load_graph::LoadGraphAst;
+use crate::query_handler::load_graph::{GraphFormat, LoadGraphAst, RejectMode};
+use crate::query_handler::manage_collection::{CollectionOperation, ManageCollectionAst};
+use crate::query_handler::modify_graph::{ModifyGraphAst, PropertyAssignment, PropertyAssignments};
+use crate::query_handler::reorder_collection::{CollectionOrdering, ReorderCollectionAst};
 use crate::query_handler::run_computation::RunComputationAst;
 use crate::query_handler::serde::{Operation, Serde};
 use crate::query_handler::set_threads::SetThreads;
 use crate::query_handler::show_computations::ShowComputationsAst;
 use crate::query_handler::show_cube_data::ShowCollectionDataAst;
 use crate::query_handler::show_cubes::ShowCollectionsAst;
+use crate::query_handler::show_indexes::ShowIndexesAst;
 use crate::query_handler::window_cube::WindowCubeAst;
 use crate::query_handler::write_cube::WriteCubeAst;
 use crate::query_handler::write_graph::WriteGraphAst;
@@ -69,8 +81,23 @@ impl<'a> GraphSurgeParser<'a> {
             Rule::write_collection => self.parse_write_collection(queries_rule),
             Rule::show_queries => self.parse_show_queries(queries_rule),
             Rule::create_view_or_collection => self.parse_create_view_or_collection(queries_rule),
+            Rule::alter_collection => self.parse_alter_collection(queries_rule),
             Rule::create_aggregated_cube => self.parse_create_aggregated_cube(queries_rule),
+            Rule::create_index => self.parse_create_index(queries_rule),
             Rule::delete_collections => Ok(Box::new(DeleteCollectionsAst {})),
+            Rule::drop_collection | Rule::rename_collection | Rule::copy_collection => {
+                self.parse_manage_collection(queries_rule)
+            }
+            Rule::describe_collection => {
+                let mut rules = queries_rule.into_inner();
+                Ok(Box::new(DescribeCollectionAst::new(
+                    self.parse_variable(rules.next(), "describe_collection::variable")?,
+                )))
+            }
+            Rule::reorder_collection => self.parse_reorder_collection(queries_rule),
+            Rule::insert_vertex | Rule::insert_edge | Rule::delete_edges | Rule::set_property => {
+                Ok(Box::new(self.parse_modify_graph(queries_rule)?))
+            }
             Rule::set_threads => {
                 let mut rules = queries_rule.into_inner();
                 Ok(Box::new(SetThreads(
@@ -87,7 +114,8 @@ impl<'a> GraphSurgeParser<'a> {
             Rule::deserialize_collection => {
                 self.parse_serde(queries_rule, Operation::DeserializeCollection)
             }
-            Rule::run_computation => self.parse_run_computation(queries_rule),
+            Rule::run_computation => Ok(Box::new(self.parse_run_computation(queries_rule)?)),
+            Rule::explain => self.parse_explain(queries_rule),
             r => Err(unknown_rule_error("graphsurge_query::query", r)),
         }
     }
@@ -97,25 +125,40 @@ impl<'a> GraphSurgeParser<'a> {
 
         let mut vertex_file = None;
         let mut only_edge_files = true;
-        let next_rule = get_next_rule(&mut rules, "load_graph::vertex_file?")?;
+        let mut next_rule = get_next_rule(&mut rules, "load_graph::append?")?;
+        let append = next_rule.as_rule() == Rule::keyword_append;
+        if append {
+            next_rule = get_next_rule(&mut rules, "load_graph::vertex_file?")?;
+        }
+        let is_graph_file = next_rule.as_rule() == Rule::graph_file;
         let next_rule = if next_rule.as_rule() == Rule::vertex_file {
             let rule = inner_and_get_next_rule(next_rule)?;
             only_edge_files = false;
             vertex_file = Some(self.parse_string(Some(rule), "vertex_file::non_empty_string")?);
             get_next_rule(&mut rules, "load_graph::edge_file")?
+        } else if is_graph_file {
+            only_edge_files = false;
+            inner_and_get_next_rule(next_rule)?
         } else {
             next_rule
         };
 
         let edge_file = self.parse_string(Some(next_rule), "load_graph::non_empty_string")?;
 
+        let mut format = GraphFormat::Csv;
         let mut separator = None;
+        let mut list_separator = None;
         let mut comment_char = None;
         let mut has_headers = DEFAULT_HAS_HEADERS;
+        let mut reject_mode = RejectMode::Default;
+        let mut rejects_dir = None;
         let mut save_mappings_dir = None;
         let mut randomize = false;
         for rule in rules {
             match rule.as_rule() {
+                Rule::graph_format => {
+                    format = self.parse_graph_format(rule)?;
+                }
                 Rule::separator => {
                     separator = Some(
                         self.parse_string(
@@ -127,6 +170,17 @@ impl<'a> GraphSurgeParser<'a> {
                         .expect("One byte expected"),
                     );
                 }
+                Rule::list_separator => {
+                    list_separator = Some(
+                        self.parse_string(
+                            rule.into_inner().next(),
+                            "load_graph::list_separator::non_empty_string",
+                        )?
+                        .chars()
+                        .next()
+                        .expect("One char expected"),
+                    );
+                }
                 Rule::comment_char => {
                     comment_char = Some(
                         self.parse_string(
@@ -143,6 +197,20 @@ impl<'a> GraphSurgeParser<'a> {
                     let bool_rule = get_next_rule(&mut rules, "load_graph::has_headers")?;
                     has_headers = self.parse_bool(bool_rule, "load_graph::has_headers::bool")?;
                 }
+                Rule::keyword_strict => {
+                    reject_mode = RejectMode::Strict;
+                }
+                Rule::keyword_lenient => {
+                    reject_mode = RejectMode::Lenient;
+                }
+                Rule::rejects_to => {
+                    let mut rules = rule.into_inner();
+                    rejects_dir =
+                        Some(self.parse_string(
+                            rules.next(),
+                            "load_graph::rejects_to::non_empty_string",
+                        )?);
+                }
                 Rule::save_mappings => {
                     let mut rules = rule.into_inner();
                     save_mappings_dir = Some(self.parse_string(
@@ -159,13 +227,41 @@ impl<'a> GraphSurgeParser<'a> {
             }
         }
 
+        if is_graph_file != (format == GraphFormat::GraphMl) {
+            return Err(GSError::Parsing(
+                "GraphML graphs, and only those, are loaded 'from' a single file".to_owned(),
+            ));
+        }
+        if format != GraphFormat::Csv && (separator.is_some() || has_headers != DEFAULT_HAS_HEADERS)
+        {
+            return Err(GSError::Parsing(format!(
+                "Separator and headers options cannot be used with format '{}'",
+                format
+            )));
+        }
+
+        if reject_mode == RejectMode::Strict && rejects_dir.is_some() {
+            return Err(GSError::Parsing(
+                "Rejected rows cannot be saved in strict mode".to_owned(),
+            ));
+        }
+
+        if append && randomize {
+            return Err(GSError::Parsing("Edges cannot be randomized when appending".to_owned()));
+        }
+
         Ok(Box::new(LoadGraphAst::new(
+            append,
             only_edge_files,
             vertex_file,
             edge_file,
+            format,
             separator,
+            list_separator,
             comment_char,
             has_headers,
+            reject_mode,
+            rejects_dir,
             save_mappings_dir,
             randomize,
         )))
@@ -284,12 +380,106 @@ impl<'a> GraphSurgeParser<'a> {
         )))
     }
 
+    fn parse_modify_graph(&self, rule: Pair<Rule>) -> Result<ModifyGraphAst, GSError> {
+        let rule_token = rule.as_rule();
+        let mut rules = rule.into_inner();
+        match rule_token {
+            Rule::insert_vertex => {
+                let vertex = self.parse_string(rules.next(), "insert_vertex::non_empty_string")?;
+                let assignments = self.parse_property_assignments(rules.next())?;
+                Ok(ModifyGraphAst::InsertVertex(vertex, assignments))
+            }
+            Rule::insert_edge => {
+                let src_vertex =
+                    self.parse_string(rules.next(), "insert_edge::non_empty_string[1]")?;
+                let dst_vertex =
+                    self.parse_string(rules.next(), "insert_edge::non_empty_string[2]")?;
+                let assignments = self.parse_property_assignments(rules.next())?;
+                Ok(ModifyGraphAst::InsertEdge(src_vertex, dst_vertex, assignments))
+            }
+            Rule::delete_edges => {
+                let next_rule = get_next_rule(&mut rules, "delete_edges::where_conditions")?;
+                Ok(ModifyGraphAst::DeleteEdges(
+                    self.parse_where_conditions(next_rule, VertexOrEdge::Edge)?,
+                ))
+            }
+            Rule::set_property => {
+                let next_rule = get_next_rule(&mut rules, "set_property::property_assignment")?;
+                let assignment = self.parse_property_assignment(next_rule)?;
+                let vertex_or_edge =
+                    match get_next_rule(&mut rules, "set_property::[vertices|edges]")?.as_rule() {
+                        Rule::keyword_vertices => VertexOrEdge::Vertex,
+                        Rule::keyword_edges => VertexOrEdge::Edge,
+                        r => return Err(unknown_rule_error("set_property::[vertices|edges]", r)),
+                    };
+                let next_rule = get_next_rule(&mut rules, "set_property::where_conditions")?;
+                let where_conditions = self.parse_where_conditions(next_rule, vertex_or_edge)?;
+                Ok(ModifyGraphAst::SetProperty(vertex_or_edge, assignment, where_conditions))
+            }
+            r => Err(unknown_rule_error("modify_graph", r)),
+        }
+    }
+
+    fn parse_create_index(&self, rule: Pair<Rule>) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+        let mut rules = rule.into_inner();
+
+        let vertex_or_edge =
+            match get_next_rule(&mut rules, "create_index::[vertices|edges]")?.as_rule() {
+                Rule::keyword_vertices => VertexOrEdge::Vertex,
+                Rule::keyword_edges => VertexOrEdge::Edge,
+                r => return Err(unknown_rule_error("create_index::[vertices|edges]", r)),
+            };
+        let property = self.parse_variable(rules.next(), "create_index::variable")?;
+        let key_id = self.get_key_id(&property, "create_index::variable")?;
+        let index_type = match rules.next().map(|rule| rule.as_rule()) {
+            None | Some(Rule::keyword_sorted) => IndexType::Sorted,
+            Some(Rule::keyword_hash) => IndexType::Hash,
+            Some(r) => return Err(unknown_rule_error("create_index::[sorted|hash]", r)),
+        };
+
+        Ok(Box::new(CreateIndexAst::new(vertex_or_edge, property, key_id, index_type)))
+    }
+
+    fn parse_property_assignments(
+        &self,
+        rule: Option<Pair<Rule>>,
+    ) -> Result<PropertyAssignments, GSError> {
+        match rule {
+            None => Ok(Vec::new()),
+            Some(rule) => rule
+                .into_inner()
+                .map(|assignment_rule| self.parse_property_assignment(assignment_rule))
+                .collect(),
+        }
+    }
+
+    fn parse_property_assignment(&self, rule: Pair<Rule>) -> Result<PropertyAssignment, GSError> {
+        let mut rules = rule.into_inner();
+        let name = self.parse_variable(rules.next(), "property_assignment::variable")?;
+        let next_rule = get_next_rule(&mut rules, "property_assignment::value")?;
+        let value = self.parse_value(next_rule, "property_assignment::value")?;
+        Ok((name, value))
+    }
+
     fn parse_write_graph(&self, rule: Pair<Rule>) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
         let mut rules = rule.into_inner();
 
         let filename = self.parse_string(rules.next(), "write_graph::non_empty_string")?;
+        let format = match rules.next() {
+            Some(rule) => self.parse_graph_format(rule)?,
+            None => GraphFormat::Csv,
+        };
+
+        Ok(Box::new(WriteGraphAst::new(filename, format)))
+    }
 
-        Ok(Box::new(WriteGraphAst::new(filename)))
+    fn parse_graph_format(&self, rule: Pair<Rule>) -> Result<GraphFormat, GSError> {
+        match inner_and_get_next_rule(rule)?.as_rule() {
+            Rule::keyword_csv => Ok(GraphFormat::Csv),
+            Rule::keyword_graphml => Ok(GraphFormat::GraphMl),
+            Rule::keyword_jsonl => Ok(GraphFormat::JsonLines),
+            r => Err(unknown_rule_error("graph_format", r)),
+        }
     }
 
     fn parse_write_collection(
@@ -313,6 +503,7 @@ impl<'a> GraphSurgeParser<'a> {
         match rule.as_rule() {
             Rule::keyword_computations => Ok(Box::new(ShowComputationsAst {})),
             Rule::keyword_collections => Ok(Box::new(ShowCollectionsAst {})),
+            Rule::keyword_indexes => Ok(Box::new(ShowIndexesAst {})),
             Rule::collection_data => {
                 let name =
                     self.parse_string(rule.into_inner().next(), "show_queries::cube_data")?;
@@ -411,7 +602,7 @@ impl<'a> GraphSurgeParser<'a> {
         let rule = get_next_rule(&mut rules, "create_view_or_collection")?;
         match rule.as_rule() {
             Rule::single_sections => Ok(Box::new(self.parse_sections(rule)?)),
-            Rule::collection => self.parse_view_collection(rule),
+            Rule::collection => Ok(Box::new(self.parse_view_collection(rule)?)),
             r => Err(unknown_rule_error("create_view_or_collection", r)),
         }
     }
@@ -657,16 +848,30 @@ impl<'a> GraphSurgeParser<'a> {
         &self,
         rule: Pair<Rule>,
         vertex_or_edge: VertexOrEdge,
-    ) -> Result<Vec<WhereCondition>, GSError> {
-        let rules = rule.into_inner();
+    ) -> Result<WhereConditions, GSError> {
+        let mut disjuncts = Vec::new();
+        for where_conjunction_rule in rule.into_inner() {
+            disjuncts.push(self.parse_where_conjunction(where_conjunction_rule, vertex_or_edge)?);
+        }
 
-        let mut where_conditions = Vec::new();
-        for where_condition_rule in rules {
-            let where_condition =
-                self.parse_where_condition(where_condition_rule, vertex_or_edge)?;
-            where_conditions.push(where_condition);
+        // Avoid wrapping a plain conjunction inside a single-element disjunction.
+        if disjuncts.len() == 1 {
+            Ok(disjuncts.pop().expect("Disjuncts should not be empty"))
+        } else {
+            Ok(vec![WhereCondition::Disjunction(disjuncts)])
         }
+    }
 
+    fn parse_where_conjunction(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<WhereConditions, GSError> {
+        let mut where_conditions = Vec::new();
+        for where_condition_rule in rule.into_inner() {
+            where_conditions
+                .extend(self.parse_where_condition(where_condition_rule, vertex_or_edge)?);
+        }
         Ok(where_conditions)
     }
 
@@ -674,45 +879,29 @@ impl<'a> GraphSurgeParser<'a> {
         &self,
         rule: Pair<Rule>,
         vertex_or_edge: VertexOrEdge,
-    ) -> Result<WhereCondition, GSError> {
+    ) -> Result<WhereConditions, GSError> {
         let mut rule = rule.into_inner();
 
         let where_condition_rule = get_next_rule(&mut rule, "condition_clause")?;
         match where_condition_rule.as_rule() {
-            Rule::positive_where_condition => Ok((
-                false,
-                self.parse_positive_where_condition(where_condition_rule, vertex_or_edge)?,
-            )),
+            Rule::where_predicate => Ok(vec![WhereCondition::Predicate(
+                self.parse_where_predicate(where_condition_rule, vertex_or_edge)?,
+            )]),
             Rule::negative_where_condition => {
-                let positive_where_condition_rule = inner_and_get_next_rule(where_condition_rule)?;
-                Ok((
-                    true,
-                    self.parse_positive_where_condition(
-                        positive_where_condition_rule,
-                        vertex_or_edge,
-                    )?,
-                ))
+                let where_conditions_rule = inner_and_get_next_rule(where_condition_rule)?;
+                Ok(vec![WhereCondition::Negation(
+                    self.parse_where_conditions(where_conditions_rule, vertex_or_edge)?,
+                )])
+            }
+            Rule::nested_where_conditions => {
+                // Nested conjunctions are flattened into the enclosing conjunction.
+                let where_conditions_rule = inner_and_get_next_rule(where_condition_rule)?;
+                self.parse_where_conditions(where_conditions_rule, vertex_or_edge)
             }
             r => Err(unknown_rule_error("condition_clause", r)),
         }
     }
 
-    fn parse_positive_where_condition(
-        &self,
-        rule: Pair<Rule>,
-        vertex_or_edge: VertexOrEdge,
-    ) -> Result<Vec<WherePredicate>, GSError> {
-        let rule = rule.into_inner();
-
-        let mut where_predicates = Vec::new();
-        for where_predicate_rule in rule {
-            let where_predicate =
-                self.parse_where_predicate(where_predicate_rule, vertex_or_edge)?;
-            where_predicates.push(where_predicate);
-        }
-        Ok(where_predicates)
-    }
-
     fn parse_where_predicate(
         &self,
         rule: Pair<Rule>,
@@ -720,14 +909,32 @@ impl<'a> GraphSurgeParser<'a> {
     ) -> Result<WherePredicate, GSError> {
         let mut rules = rule.into_inner();
 
-        let next_rule = get_next_rule(&mut rules, "where_clause::complex_variable")?;
-        let operand1 = self.parse_complex_variable(
-            next_rule,
-            "where_clause::complex_variable",
-            vertex_or_edge,
-        )?;
+        let mut next_rule = get_next_rule(&mut rules, "where_clause::[left_operand]")?;
+        // `exists(x)` is the same as `x is not null`.
+        let is_exists = next_rule.as_rule() == Rule::keyword_exists;
+        if is_exists {
+            next_rule = get_next_rule(&mut rules, "where_clause::exists::complex_variable")?;
+        }
+        let operand1 = match next_rule.as_rule() {
+            Rule::complex_variable => LeftOperand::Operand(self.parse_complex_variable(
+                next_rule,
+                "where_clause::complex_variable",
+                vertex_or_edge,
+            )?),
+            Rule::arithmetic_expression => {
+                match self.parse_arithmetic_expression(next_rule, vertex_or_edge)? {
+                    Expression::Operand(operand) => LeftOperand::Operand(operand),
+                    expression => LeftOperand::Expression(expression),
+                }
+            }
+            r => {
+                return Err(unknown_rule_error("where_clause::[left_operand]", r));
+            }
+        };
 
-        let operator = {
+        let operator = if is_exists {
+            Operator::IsNot
+        } else {
             let comparator_rule = get_next_rule(&mut rules, "where_clause::[operator]")?;
             match comparator_rule.as_rule() {
                 Rule::char_less => Operator::Less,
@@ -736,29 +943,118 @@ impl<'a> GraphSurgeParser<'a> {
                 Rule::char_greater_equal => Operator::GreaterEqual,
                 Rule::char_equal => Operator::Equal,
                 Rule::char_not_equal => Operator::NotEqual,
+                Rule::keyword_in => Operator::In,
+                Rule::keyword_not_in => Operator::NotIn,
+                Rule::keyword_any_in => Operator::AnyIn,
+                Rule::keyword_like => Operator::Like,
+                Rule::keyword_ilike => Operator::ILike,
+                Rule::keyword_iequals => Operator::IEqual,
+                Rule::keyword_starts_with => Operator::StartsWith,
+                Rule::keyword_ends_with => Operator::EndsWith,
+                Rule::keyword_contains => Operator::Contains,
+                Rule::keyword_is_null => Operator::Is,
+                Rule::keyword_is_not_null => Operator::IsNot,
                 r => {
                     return Err(unknown_rule_error("where_clause::[operator]", r));
                 }
             }
         };
 
-        let next_rule = get_next_rule(&mut rules, "where_clause::variable_or_value")?;
-        let value_or_variable_rule = inner_and_get_next_rule(next_rule)?;
-        let operand2 = match value_or_variable_rule.as_rule() {
-            Rule::value => RightOperand::Value(
-                self.parse_value(value_or_variable_rule, "where_clause::variable_or_value::value")?,
-            ),
-            Rule::complex_variable => RightOperand::Variable(self.parse_complex_variable(
-                value_or_variable_rule,
-                "where_clause::variable_or_value::complex_variable",
-                vertex_or_edge,
-            )?),
-            r => {
-                return Err(unknown_rule_error("where_clause::variable_or_value", r));
+        if let Operator::Is | Operator::IsNot = operator {
+            if matches!(operand1, LeftOperand::Operand(Operand::Edge) | LeftOperand::Expression(_))
+            {
+                return Err(GSError::Parsing(format!(
+                    "Null checks can only be applied to properties but found '{}'",
+                    operand1
+                )));
+            }
+        }
+
+        let operand2 = if let Operator::Is | Operator::IsNot = operator {
+            RightOperand::Null
+        } else {
+            let next_rule = get_next_rule(&mut rules, "where_clause::variable_or_value")?;
+            if next_rule.as_rule() == Rule::values {
+                let values: Result<Vec<_>, _> = next_rule
+                    .into_inner()
+                    .map(|value_rule| self.parse_value(value_rule, "where_clause::values::value"))
+                    .collect();
+                RightOperand::List(values?)
+            } else if next_rule.as_rule() == Rule::arithmetic_expression {
+                match self.parse_arithmetic_expression(next_rule, vertex_or_edge)? {
+                    Expression::Operand(operand) => RightOperand::Variable(operand),
+                    Expression::Value(value) => RightOperand::Value(PropertyValue::Isize(value)),
+                    expression => RightOperand::Expression(expression),
+                }
+            } else {
+                let value_or_variable_rule = inner_and_get_next_rule(next_rule)?;
+                match value_or_variable_rule.as_rule() {
+                    Rule::value => RightOperand::Value(self.parse_value(
+                        value_or_variable_rule,
+                        "where_clause::variable_or_value::value",
+                    )?),
+                    Rule::complex_variable => RightOperand::Variable(self.parse_complex_variable(
+                        value_or_variable_rule,
+                        "where_clause::variable_or_value::complex_variable",
+                        vertex_or_edge,
+                    )?),
+                    r => {
+                        return Err(unknown_rule_error("where_clause::variable_or_value", r));
+                    }
+                }
             }
         };
 
-        let predicate = (operand1, operator, operand2.clone());
+        // Both sides of an arithmetic comparison must be integer expressions.
+        let is_arithmetic =
+            matches!(operand1, LeftOperand::Expression(_)) || operand2.is_expression();
+        if is_arithmetic
+            && !matches!(operand2, RightOperand::List(_))
+            && operand2.clone().into_expression().is_none()
+        {
+            return Err(GSError::Parsing(format!(
+                "Arithmetic expression cannot be compared with non-integer operand '{}'",
+                operand2
+            )));
+        }
+        if operator.is_string_operator() {
+            if is_arithmetic {
+                return Err(GSError::Parsing(format!(
+                    "Operator '{}' cannot be applied to arithmetic expressions",
+                    operator.as_string().trim()
+                )));
+            }
+            // List operators also apply to integer lists.
+            let is_valid_value = |value: &PropertyValue| match value.value_type() {
+                PropertyValueType::String => true,
+                PropertyValueType::Isize => operator.is_list_operator(),
+                _ => false,
+            };
+            let is_valid_operand = match &operand2 {
+                RightOperand::Value(value) => is_valid_value(value),
+                RightOperand::List(values) => values.iter().all(is_valid_value),
+                RightOperand::Variable(operand) => operand.is_property_value(),
+                RightOperand::Expression(_) | RightOperand::Null => false,
+            };
+            if !is_valid_operand {
+                return Err(GSError::Parsing(format!(
+                    "Operator '{}' expects {} operands but found '{}'",
+                    operator.as_string().trim(),
+                    if operator.is_list_operator() { "string or integer" } else { "string" },
+                    operand2
+                )));
+            }
+        }
+        if let LeftOperand::Operand(operand) = operand1 {
+            if operand2.is_expression() && !operand.is_property_value() {
+                return Err(GSError::Parsing(format!(
+                    "Operand '{}' cannot be compared with an arithmetic expression",
+                    operand
+                )));
+            }
+        }
+
+        let predicate = (operand1.clone(), operator, operand2.clone());
         let closure = match vertex_or_edge {
             VertexOrEdge::Edge => get_edge_closure(operand1, operator, operand2),
             VertexOrEdge::Vertex => get_vertex_closure(operand1, operator, operand2),
@@ -767,6 +1063,92 @@ impl<'a> GraphSurgeParser<'a> {
         Ok((predicate, closure))
     }
 
+    fn parse_arithmetic_expression(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<Expression, GSError> {
+        let mut rules = rule.into_inner();
+
+        let next_rule = get_next_rule(&mut rules, "arithmetic_expression::arithmetic_term")?;
+        let mut expression = self.parse_arithmetic_term(next_rule, vertex_or_edge)?;
+        while let Some(operator_rule) = rules.next() {
+            let operator = match operator_rule.as_rule() {
+                Rule::char_plus => ArithmeticOperator::Add,
+                Rule::char_minus => ArithmeticOperator::Subtract,
+                r => return Err(unknown_rule_error("arithmetic_expression::[operator]", r)),
+            };
+            let next_rule = get_next_rule(&mut rules, "arithmetic_expression::arithmetic_term")?;
+            let right = self.parse_arithmetic_term(next_rule, vertex_or_edge)?;
+            expression = Expression::Arithmetic(Box::new(expression), operator, Box::new(right));
+        }
+        Ok(expression)
+    }
+
+    fn parse_arithmetic_term(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<Expression, GSError> {
+        let mut rules = rule.into_inner();
+
+        let next_rule = get_next_rule(&mut rules, "arithmetic_term::arithmetic_factor")?;
+        let mut expression = self.parse_arithmetic_factor(next_rule, vertex_or_edge)?;
+        while let Some(operator_rule) = rules.next() {
+            let operator = match operator_rule.as_rule() {
+                Rule::char_star => ArithmeticOperator::Multiply,
+                Rule::char_slash => ArithmeticOperator::Divide,
+                Rule::char_percent => ArithmeticOperator::Remainder,
+                r => return Err(unknown_rule_error("arithmetic_term::[operator]", r)),
+            };
+            let next_rule = get_next_rule(&mut rules, "arithmetic_term::arithmetic_factor")?;
+            let right = self.parse_arithmetic_factor(next_rule, vertex_or_edge)?;
+            expression = Expression::Arithmetic(Box::new(expression), operator, Box::new(right));
+        }
+        Ok(expression)
+    }
+
+    fn parse_arithmetic_factor(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<Expression, GSError> {
+        let factor_rule = inner_and_get_next_rule(rule)?;
+        let expression = match factor_rule.as_rule() {
+            Rule::num_isize => Expression::Value(
+                self.parse_num_isize(Some(factor_rule), "arithmetic_factor::num_isize")?,
+            ),
+            Rule::complex_variable => {
+                let operand = self.parse_complex_variable(
+                    factor_rule,
+                    "arithmetic_factor::complex_variable",
+                    vertex_or_edge,
+                )?;
+                if !operand.is_property_value() {
+                    return Err(GSError::Parsing(format!(
+                        "Operand '{}' cannot be used in an arithmetic expression",
+                        operand
+                    )));
+                }
+                Expression::Operand(operand)
+            }
+            Rule::negated_arithmetic_factor => {
+                let mut rules = factor_rule.into_inner();
+                get_next_rule(&mut rules, "negated_arithmetic_factor::char_minus")?;
+                let next_rule =
+                    get_next_rule(&mut rules, "negated_arithmetic_factor::arithmetic_factor")?;
+                Expression::Negation(Box::new(
+                    self.parse_arithmetic_factor(next_rule, vertex_or_edge)?,
+                ))
+            }
+            Rule::arithmetic_expression => {
+                self.parse_arithmetic_expression(factor_rule, vertex_or_edge)?
+            }
+            r => return Err(unknown_rule_error("arithmetic_factor", r)),
+        };
+        Ok(expression)
+    }
+
     fn parse_group_clauses(
         &self,
         rule: Pair<Rule>,
@@ -832,22 +1214,19 @@ impl<'a> GraphSurgeParser<'a> {
         Ok(aggregate_clauses)
     }
 
-    fn parse_view_collection(&self, rule: Pair<Rule>) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+    fn parse_view_collection(&self, rule: Pair<Rule>) -> Result<CreateViewCollectionAst, GSError> {
         let mut rules = rule.into_inner();
 
         let name = self.parse_variable(rules.next(), "collection::variable")?;
 
-        let mut dimensions = Vec::new();
-        for dimensions_rule in get_next_and_inner_rules(&mut rules, "collection::dimensions")? {
-            let dimension: Result<Vec<WhereConditions>, _> = dimensions_rule
-                .into_inner()
-                .map(|where_conditions_rules| {
-                    self.parse_where_conditions(where_conditions_rules, VertexOrEdge::Edge)
-                })
-                .collect();
-            dimensions.push(dimension?);
-        }
+        let dimensions_rule = get_next_rule(&mut rules, "collection::dimensions")?;
+        let view_predicates =
+            dimensions_rule.clone().into_inner().map(get_view_predicates).collect();
+        let dimensions = self.parse_dimensions(dimensions_rule, VertexOrEdge::Edge)?;
 
+        let mut vertex_dimensions = Vec::new();
+        let mut vertex_view_predicates = Vec::new();
+        let mut edge_properties = Vec::new();
         let mut manual_order = false;
         let mut materialized = false;
         let mut store_total_data = false;
@@ -855,6 +1234,24 @@ impl<'a> GraphSurgeParser<'a> {
 
         for rule in rules {
             match rule.as_rule() {
+                Rule::vertex_dimensions => {
+                    let vertex_dimensions_rule = inner_and_get_next_rule(rule)?;
+                    vertex_view_predicates = vertex_dimensions_rule
+                        .clone()
+                        .into_inner()
+                        .map(get_view_predicates)
+                        .collect();
+                    vertex_dimensions =
+                        self.parse_dimensions(vertex_dimensions_rule, VertexOrEdge::Vertex)?;
+                }
+                Rule::edge_properties => {
+                    for variable_rule in rule.into_inner() {
+                        let property =
+                            self.parse_variable(Some(variable_rule), "edge_properties::variable")?;
+                        let key_id = self.get_key_id(&property, "edge_properties::variable")?;
+                        edge_properties.push((property, key_id));
+                    }
+                }
                 Rule::keyword_manually_ordered => {
                     manual_order = true;
                 }
@@ -878,16 +1275,117 @@ impl<'a> GraphSurgeParser<'a> {
             }
         }
 
-        Ok(Box::new(CreateViewCollectionAst::new(
+        Ok(CreateViewCollectionAst::new(
             name,
             dimensions,
+            vertex_dimensions,
+            view_predicates,
+            vertex_view_predicates,
+            edge_properties,
             manual_order,
             materialized,
             store_total_data,
             hosts,
+        ))
+    }
+
+    fn parse_alter_collection(
+        &self,
+        rule: Pair<Rule>,
+    ) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+        let mut rules = rule.into_inner();
+
+        let name = self.parse_variable(rules.next(), "alter_collection::variable")?;
+        let views_rule = get_next_rule(&mut rules, "alter_collection::dimension")?;
+        let view_predicates = get_view_predicates(views_rule.clone());
+        let views = self.parse_dimension(views_rule, VertexOrEdge::Edge)?;
+        let (vertex_views, vertex_view_predicates) = match rules.next() {
+            Some(rule) => {
+                let vertex_views_rule = inner_and_get_next_rule(rule)?;
+                let vertex_view_predicates = get_view_predicates(vertex_views_rule.clone());
+                (
+                    self.parse_dimension(vertex_views_rule, VertexOrEdge::Vertex)?,
+                    vertex_view_predicates,
+                )
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
+        Ok(Box::new(AlterCollectionAst::new(
+            name,
+            views,
+            vertex_views,
+            view_predicates,
+            vertex_view_predicates,
         )))
     }
 
+    fn parse_manage_collection(
+        &self,
+        rule: Pair<Rule>,
+    ) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+        let query_rule = rule.as_rule();
+        let mut rules = rule.into_inner();
+
+        let name = self.parse_variable(rules.next(), "manage_collection::variable")?;
+        let operation = match query_rule {
+            Rule::drop_collection => CollectionOperation::Drop(name),
+            Rule::rename_collection => CollectionOperation::Rename(
+                name,
+                self.parse_variable(rules.next(), "rename_collection::variable")?,
+            ),
+            Rule::copy_collection => CollectionOperation::Copy(
+                name,
+                self.parse_variable(rules.next(), "copy_collection::variable")?,
+            ),
+            r => return Err(unknown_rule_error("manage_collection", r)),
+        };
+
+        Ok(Box::new(ManageCollectionAst::new(operation)))
+    }
+
+    fn parse_reorder_collection(
+        &self,
+        rule: Pair<Rule>,
+    ) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+        let mut rules = rule.into_inner();
+
+        let name = self.parse_variable(rules.next(), "reorder_collection::variable")?;
+        let ordering = match get_next_rule(&mut rules, "reorder_collection::ordering")?.as_rule() {
+            Rule::keyword_optimal => CollectionOrdering::Optimal,
+            Rule::keyword_manual => CollectionOrdering::Manual,
+            Rule::keyword_greedy => CollectionOrdering::Greedy,
+            Rule::keyword_random => CollectionOrdering::Random(
+                self.parse_num_usize(rules.next(), "reorder_collection::seed")?,
+            ),
+            r => return Err(unknown_rule_error("reorder_collection::ordering", r)),
+        };
+
+        Ok(Box::new(ReorderCollectionAst::new(name, ordering)))
+    }
+
+    fn parse_dimensions(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<Vec<Dimension>, GSError> {
+        rule.into_inner()
+            .map(|dimension_rule| self.parse_dimension(dimension_rule, vertex_or_edge))
+            .collect()
+    }
+
+    fn parse_dimension(
+        &self,
+        rule: Pair<Rule>,
+        vertex_or_edge: VertexOrEdge,
+    ) -> Result<Dimension, GSError> {
+        rule.into_inner()
+            .map(|where_conditions_rules| {
+                self.parse_where_conditions(where_conditions_rules, vertex_or_edge)
+            })
+            .collect()
+    }
+
     fn parse_computation_property(
         &self,
         rule: Pair<Rule>,
@@ -929,6 +1427,9 @@ impl<'a> GraphSurgeParser<'a> {
         let rule = get_next_rule(&mut rule, location)?;
         let value = match rule.as_rule() {
             Rule::num_isize => PropertyValue::Isize(self.parse_num_isize(Some(rule), location)?),
+            Rule::num_float => {
+                PropertyValue::Float(Float(self.parse_num_float(Some(rule), location)?))
+            }
             Rule::string => {
                 let type_str = self.parse_string(Some(rule), location)?;
                 PropertyValue::String(type_str)
@@ -943,6 +1444,25 @@ impl<'a> GraphSurgeParser<'a> {
                     self.parse_num_isize(inner_rules.next(), location)?,
                 )
             }
+            Rule::datetime => {
+                let mut inner_rules = rule.into_inner();
+                let is_date =
+                    get_next_rule(&mut inner_rules, location)?.as_rule() == Rule::keyword_date;
+                let datetime_str = self.parse_string(inner_rules.next(), location)?;
+                let datetime = if is_date {
+                    PropertyValue::parse_date(&datetime_str)
+                } else {
+                    PropertyValue::parse_datetime(&datetime_str)
+                };
+                datetime.ok_or_else(|| {
+                    GSError::Parsing(format!(
+                        "Could not parse '{}' as {} at '{}'",
+                        datetime_str,
+                        if is_date { "date" } else { "datetime" },
+                        location
+                    ))
+                })?
+            }
             r => {
                 return Err(unknown_rule_error(location, r));
             }
@@ -950,7 +1470,7 @@ impl<'a> GraphSurgeParser<'a> {
         Ok(value)
     }
 
-    fn parse_run_computation(&self, rule: Pair<Rule>) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+    fn parse_run_computation(&self, rule: Pair<Rule>) -> Result<RunComputationAst, GSError> {
         let mut rules = rule.into_inner();
 
         let next_rule = get_next_rule(&mut rules, "run_computation::[type]?")?;
@@ -1086,7 +1606,7 @@ impl<'a> GraphSurgeParser<'a> {
             }
         }
 
-        Ok(Box::new(RunComputationAst::new(
+        Ok(RunComputationAst::new(
             computation,
             file,
             properties,
@@ -1101,7 +1621,20 @@ impl<'a> GraphSurgeParser<'a> {
             diff_multipler,
             limit,
             use_lr,
-        )))
+        ))
+    }
+
+    fn parse_explain(&self, rule: Pair<Rule>) -> Result<Box<dyn GraphSurgeQuery>, GSError> {
+        let rule = inner_and_get_next_rule(rule)?;
+        match rule.as_rule() {
+            Rule::explain_collection => Ok(Box::new(ExplainAst::CreateCollection(
+                self.parse_view_collection(inner_and_get_next_rule(rule)?)?,
+            ))),
+            Rule::run_computation => {
+                Ok(Box::new(ExplainAst::RunComputation(self.parse_run_computation(rule)?)))
+            }
+            r => Err(unknown_rule_error("explain", r)),
+        }
     }
 
     fn parse_string(&self, rule: Option<Pair<Rule>>, location: &str) -> Result<String, GSError> {
@@ -1137,6 +1670,12 @@ impl<'a> GraphSurgeParser<'a> {
         })
     }
 
+    fn parse_num_float(&self, rule: Option<Pair<Rule>>, location: &str) -> Result<f64, GSError> {
+        self.parse_variable(rule, location)?.parse().map_err(|e| {
+            GSError::Parsing(format!("Could not parse '{}' as f64: {:?}", location, e))
+        })
+    }
+
     fn parse_num_usize(&self, rule: Option<Pair<Rule>>, location: &str) -> Result<usize, GSError> {
         self.parse_variable(rule, location)?.parse().map_err(|e| {
             GSError::Parsing(format!("Could not parse '{}' as usize: {:?}", location, e))
@@ -1207,6 +1746,37 @@ impl<'a> GraphSurgeParser<'a> {
     }
 }
 
+/// Returns the predicates of the views of a `dimension` rule as written in the query, with
+/// whitespace collapsed outside of string literals.
+fn get_view_predicates(rule: Pair<Rule>) -> Vec<String> {
+    rule.into_inner()
+        .map(|where_conditions_rule| collapse_whitespace(where_conditions_rule.as_str()))
+        .collect()
+}
+
+/// Replaces each run of whitespace outside of single-quoted strings with a single space, and
+/// trims it. Strings cannot contain escaped single quotes, so every quote opens or closes one.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut after_whitespace = false;
+    for c in text.chars() {
+        if !in_string && c.is_whitespace() {
+            after_whitespace = true;
+            continue;
+        }
+        if after_whitespace && !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        after_whitespace = false;
+        if c == '\'' {
+            in_string = !in_string;
+        }
+        collapsed.push(c);
+    }
+    collapsed
+}
+
 fn inner_and_get_next_rule(rule: Pair<Rule>) -> Result<Pair<Rule>, GSError> {
     let rule_token = rule.as_rule();
     let mut inner = rule.into_inner();
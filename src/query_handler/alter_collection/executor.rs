@@ -0,0 +1,306 @@
+use crate::computations::filtered_cubes::execute::execute;
+use crate::error::GSError;
+use crate::filtered_cubes::get_dimension_length;
+use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
+use crate::filtered_cubes::timestamp::GSTimestamp;
+use crate::global_store::GlobalStore;
+use crate::query_handler::alter_collection::AlterCollectionAst;
+use crate::query_handler::create_filtered_cube::executor::print_totals;
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+use gs_analytics_api::{DiffCount, EdgePayload, FilteredCubeData, SimpleEdge, VertexId};
+use hashbrown::HashMap;
+use itertools::Itertools;
+use log::info;
+use std::hash::Hash;
+
+impl GraphSurgeQuery for AlterCollectionAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let cube = global_store
+            .filtered_cube_store
+            .cubes
+            .get(&self.name)
+            .ok_or_else(|| GSError::CollectionMissing(self.name.clone()))?;
+
+        if cube.stale {
+            return Err(GSError::Collection(format!(
+                "Collection '{}' is stale, since the graph was modified after it was created. \
+                 Create it again before adding views",
+                self.name
+            )));
+        }
+        if cube.dimension_lengths.len() != 1 {
+            return Err(GSError::Collection(format!(
+                "Views can only be added to 1-dimensional collections, but '{}' has {} dimensions",
+                self.name,
+                cube.dimension_lengths.len()
+            )));
+        }
+        let adds_vertex_views = !self.vertex_views.is_empty();
+        if cube.data.has_vertex_views != adds_vertex_views {
+            return Err(GSError::Collection(format!(
+                "Collection '{}' {} vertex views, so the added views should {}",
+                self.name,
+                if cube.data.has_vertex_views { "has" } else { "does not have" },
+                if cube.data.has_vertex_views { "have them too" } else { "not have them" }
+            )));
+        }
+        if adds_vertex_views && self.vertex_views.len() != self.views.len() {
+            return Err(GSError::Collection(format!(
+                "Vertex views ({}) should have the same length as the edge views ({})",
+                self.vertex_views.len(),
+                self.views.len()
+            )));
+        }
+
+        let old_length = cube.data.entries.len();
+        let new_dimension_lengths = vec![get_dimension_length(old_length + self.views.len())?];
+        let edge_properties = cube
+            .data
+            .edge_properties
+            .iter()
+            .map(|property| {
+                let key_id = global_store.key_store.get_key_id(property).ok_or_else(|| {
+                    GSError::Collection(format!("Edge property '{}' no longer exists", property))
+                })?;
+                Ok((property.clone(), key_id))
+            })
+            .collect::<Result<Vec<_>, GSError>>()?;
+        let store_total_data = cube.data.has_full_views;
+        let (last_edges, last_vertices) = get_last_view(&cube.data);
+
+        // The added views are computed as a collection of their own, whose first diff holds the
+        // full first view. It is then diffed against the current last view.
+        let dimension_lengths = vec![get_dimension_length(self.views.len())?];
+        let (mut entries, mut payloads, _) = execute(
+            vec![self.views.clone()],
+            if self.vertex_views.is_empty() { Vec::new() } else { vec![self.vertex_views.clone()] },
+            dimension_lengths.clone(),
+            &global_store.graph,
+            &global_store.index_store,
+            &edge_properties,
+            true,
+            store_total_data,
+            &get_timestamp_mappings(&dimension_lengths),
+            global_store.threads.get(),
+            global_store.process_id,
+            &[],
+        )?;
+
+        if let Some((_, _, (_, diff_edges), counts, (_, diff_vertices))) = entries.first_mut() {
+            let first_payloads = payloads.first().map(|(_, diff_payloads)| diff_payloads);
+            let (diffs, diff_payloads): (Vec<_>, Vec<_>) = diff_against(
+                diff_edges.iter().enumerate().map(|(edge_index, &(edge, change))| {
+                    let payload = first_payloads
+                        .map_or_else(Vec::new, |diff_payloads| diff_payloads[edge_index].clone());
+                    ((edge, payload), change)
+                }),
+                &last_edges,
+            )
+            .into_iter()
+            .map(|((edge, payload), change)| ((edge, change), payload))
+            .unzip();
+            let additions = diffs.iter().filter(|(_, change)| *change > 0).count();
+            *counts = (additions, diffs.len() - additions);
+            *diff_edges = diffs;
+            if let Some((_, first_diff_payloads)) = payloads.first_mut() {
+                *first_diff_payloads = diff_payloads;
+            }
+            *diff_vertices = diff_against(diff_vertices.iter().copied(), &last_vertices);
+        }
+
+        let cube = global_store
+            .filtered_cube_store
+            .cubes
+            .get_mut(&self.name)
+            .expect("Collection should exist");
+        for (offset, (timestamp_index, timestamp, _, _, _)) in entries.iter_mut().enumerate() {
+            *timestamp_index = old_length + offset;
+            *timestamp = GSTimestamp::new(&[get_dimension_length(old_length + offset)?]);
+        }
+        // The added views are manually ordered, after the existing views.
+        if let Some(definition) = &mut cube.definition {
+            definition.manual_order = true;
+            definition.orders[0].extend(old_length..old_length + self.views.len());
+            definition.dimensions[0].extend(self.view_predicates.iter().cloned());
+            if let Some(vertex_dimension) = definition.vertex_dimensions.first_mut() {
+                vertex_dimension.extend(self.vertex_view_predicates.iter().cloned());
+            }
+        }
+        cube.data.entries.extend(entries);
+        cube.data.payloads.append(&mut payloads);
+        cube.timestamp_mappings = get_timestamp_mappings(&new_dimension_lengths);
+        cube.dimension_lengths = new_dimension_lengths;
+        cube.differential_data = None;
+        info!("Added {} views to '{}'", self.views.len(), self.name);
+        print_totals(cube);
+
+        Ok(GraphSurgeResult::new(format!(
+            "Added {} views to collection '{}'",
+            self.views.len(),
+            self.name
+        )))
+    }
+}
+
+/// Returns the edges, with their payloads, and the vertices of the last view of a 1-dimensional
+/// collection, along with their multiplicities.
+fn get_last_view(
+    data: &FilteredCubeData<GSTimestamp>,
+) -> (HashMap<(SimpleEdge, EdgePayload), DiffCount>, HashMap<VertexId, DiffCount>) {
+    let mut edges = HashMap::new();
+    let mut vertices = HashMap::new();
+    for (entry_index, (_, _, (_, diff_edges), _, (_, diff_vertices))) in
+        data.entries.iter().enumerate()
+    {
+        for (edge_index, &(edge, change)) in diff_edges.iter().enumerate() {
+            *edges.entry((edge, data.diff_payload(entry_index, edge_index))).or_insert(0) += change;
+        }
+        for &(vertex_id, change) in diff_vertices {
+            *vertices.entry(vertex_id).or_insert(0) += change;
+        }
+    }
+    edges.retain(|_, count| *count != 0);
+    vertices.retain(|_, count| *count != 0);
+    (edges, vertices)
+}
+
+/// Returns the diffs from `view` to the view given by `diffs`, which start from an empty view.
+fn diff_against<K: Clone + Eq + Hash + Ord>(
+    diffs: impl Iterator<Item = (K, DiffCount)>,
+    view: &HashMap<K, DiffCount>,
+) -> Vec<(K, DiffCount)> {
+    let mut counts: HashMap<K, DiffCount> =
+        view.iter().map(|(key, count)| (key.clone(), -count)).collect();
+    for (key, change) in diffs {
+        *counts.entry(key).or_insert(0) += change;
+    }
+    counts.into_iter().filter(|(_, change)| *change != 0).sorted().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+    use gs_analytics_api::{
+        DiffCount, EdgePayload, FilteredCubeData, FilteredCubeVertices, GsTimestampIndex,
+        SimpleEdge,
+    };
+    use itertools::Itertools;
+
+    type SortedEntry<T> = (
+        GsTimestampIndex,
+        T,
+        Vec<SimpleEdge>,
+        Vec<((SimpleEdge, DiffCount), EdgePayload)>,
+        (usize, usize),
+        FilteredCubeVertices,
+    );
+
+    #[test]
+    fn test_add_views() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = "
+            load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        for query in &[
+            "create view collection altered
+            where [year <= 2000],[year <= 2008]
+            vertices where [country = 'canada'],[include = true]
+            with edge properties (amount)
+            manually_ordered
+            materialized
+            materialize_full_view;",
+            "alter view collection altered add [year <= 2005],[year <= 2018]
+            vertices where [include2 = true],[country = 'india'];",
+            "create view collection created
+            where [year <= 2000],[year <= 2008],[year <= 2005],[year <= 2018]
+            vertices where [country = 'canada'],[include = true],[include2 = true],
+                [country = 'india']
+            with edge properties (amount)
+            manually_ordered
+            materialize_full_view;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+
+        let cubes = &global_store.filtered_cube_store.cubes;
+        let altered = cubes.get("altered").expect("Cube not found");
+        let created = cubes.get("created").expect("Cube not found");
+        assert!(altered.differential_data.is_none());
+        assert_eq!(altered.dimension_lengths, created.dimension_lengths);
+        assert_eq!(altered.timestamp_mappings, created.timestamp_mappings);
+        assert_eq!(get_sorted_entries(&altered.data), get_sorted_entries(&created.data));
+
+        let mut query = "alter view collection altered add [year <= 2020];".to_owned();
+        let error = process_query(&mut global_store, &mut query)
+            .expect_err("Vertex views should be required");
+        assert!(error.to_string().contains("vertex views"));
+
+        let mut query = "alter view collection missing add [year <= 2020];".to_owned();
+        let error =
+            process_query(&mut global_store, &mut query).expect_err("Collection should be missing");
+        assert!(error.to_string().contains("has not been created yet"));
+    }
+
+    #[test]
+    fn test_add_views_full_views_and_stale() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection empty where [year > 3000] materialize_full_view;",
+            "alter view collection empty add [year <= 2000];",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+        // The full views are kept even though every existing view was empty.
+        let cube = &global_store.filtered_cube_store.cubes["empty"];
+        assert!(cube.data.has_full_views);
+        assert_eq!((cube.data.entries[1].2).0.len(), 3);
+
+        let mut query = "insert vertex '9' (city = 'ottawa');".to_owned();
+        process_query(&mut global_store, &mut query).expect("Vertex not inserted");
+        let mut query = "alter view collection empty add [year <= 2018];".to_owned();
+        let error = process_query(&mut global_store, &mut query)
+            .expect_err("Stale collection should be rejected");
+        assert!(error.to_string().contains("is stale"));
+        assert_eq!(global_store.filtered_cube_store.cubes["empty"].data.entries.len(), 2);
+    }
+
+    /// Returns the entries with their edges and vertices sorted, and the diffs paired with their
+    /// payloads.
+    fn get_sorted_entries<T: Copy>(data: &FilteredCubeData<T>) -> Vec<SortedEntry<T>> {
+        data.entries
+            .iter()
+            .enumerate()
+            .map(|(entry_index, (ts_index, timestamp, (full, diffs), counts, vertices))| {
+                let diffs = diffs
+                    .iter()
+                    .enumerate()
+                    .map(|(edge_index, &diff)| (diff, data.diff_payload(entry_index, edge_index)))
+                    .sorted()
+                    .collect();
+                let vertices = (
+                    vertices.0.iter().copied().sorted().collect(),
+                    vertices.1.iter().copied().sorted().collect(),
+                );
+                (
+                    *ts_index,
+                    *timestamp,
+                    full.iter().copied().sorted().collect(),
+                    diffs,
+                    *counts,
+                    vertices,
+                )
+            })
+            .collect()
+    }
+}
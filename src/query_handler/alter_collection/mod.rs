@@ -0,0 +1,21 @@
+use crate::query_handler::create_filtered_cube::Dimension;
+
+pub mod executor;
+
+#[derive(new)]
+pub struct AlterCollectionAst {
+    name: String,
+    /// The views appended to the end of the collection, in order.
+    views: Dimension,
+    /// The vertex views of the appended views. Empty if the collection has no vertex views.
+    vertex_views: Dimension,
+    /// The predicates of `views` and `vertex_views`, as written in the query.
+    view_predicates: Vec<String>,
+    vertex_view_predicates: Vec<String>,
+}
+
+impl std::fmt::Display for AlterCollectionAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "alter view collection {} add {} views", self.name, self.views.len())
+    }
+}
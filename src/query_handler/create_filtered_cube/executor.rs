@@ -1,15 +1,17 @@
-use crate::computations::filtered_cubes::execute::execute;
+use crate::computations::filtered_cubes::execute::{estimate_orders, execute};
+use crate::computations::filtered_cubes::optimal_orders::get_order_diffs;
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
-use crate::filtered_cubes::timestamp::GSTimestamp;
-use crate::filtered_cubes::{DimensionLength, DimensionLengths, FilteredCube};
+use crate::filtered_cubes::timestamp::MAX_DIMENSIONS;
+use crate::filtered_cubes::{
+    get_dimension_lengths, CollectionDefinition, DimensionLengths, FilteredCube,
+};
 use crate::global_store::GlobalStore;
 use crate::query_handler::create_filtered_cube::CreateViewCollectionAst;
 use crate::query_handler::GraphSurgeQuery;
 use crate::query_handler::GraphSurgeResult;
 use gs_analytics_api::FilteredCubeData;
 use log::info;
-use std::convert::TryFrom;
 
 impl GraphSurgeQuery for CreateViewCollectionAst {
     fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
@@ -17,19 +19,18 @@ impl GraphSurgeQuery for CreateViewCollectionAst {
             return Err(GSError::CollectionAlreadyExists(self.name.clone()));
         }
 
-        let dimension_lengths: DimensionLengths = self
-            .dimensions
-            .iter()
-            .map(|vec| DimensionLength::try_from(vec.len()).expect("DimensionLength overflow"))
-            .collect();
+        let dimension_lengths = self.get_dimension_lengths()?;
         let timestamp_mappings = get_timestamp_mappings(&dimension_lengths);
 
         info!("Dimension lengths: {:?}", dimension_lengths);
 
-        let data = execute(
+        let (data, payloads, orders) = execute(
             self.dimensions.clone(),
+            self.vertex_dimensions.clone(),
             dimension_lengths.clone(),
             &global_store.graph,
+            &global_store.index_store,
+            &self.edge_properties,
             self.manual_order,
             self.store_total_data,
             &timestamp_mappings,
@@ -37,10 +38,24 @@ impl GraphSurgeQuery for CreateViewCollectionAst {
             global_store.process_id,
             &self.hosts,
         )?;
-        let filtered_cube_data = FilteredCubeData::new(data);
+        let edge_properties =
+            self.edge_properties.iter().map(|(property, _)| property.clone()).collect();
+        let mut filtered_cube_data =
+            FilteredCubeData::with_payloads(data, edge_properties, payloads);
+        filtered_cube_data.has_vertex_views = !self.vertex_dimensions.is_empty();
+        filtered_cube_data.has_full_views = self.store_total_data;
 
         let mut cube =
             FilteredCube::new(timestamp_mappings, dimension_lengths, None, filtered_cube_data);
+        cube.definition = Some(CollectionDefinition::new(
+            self.view_predicates.clone(),
+            self.vertex_view_predicates.clone(),
+            orders
+                .iter()
+                .map(|order| order.iter().map(|&view| view as usize - 1).collect())
+                .collect(),
+            self.manual_order,
+        ));
         print_totals(&cube);
 
         if self.materialized {
@@ -52,19 +67,77 @@ impl GraphSurgeQuery for CreateViewCollectionAst {
     }
 }
 
+impl CreateViewCollectionAst {
+    /// Checks the shape of the views and returns the lengths of the dimensions.
+    fn get_dimension_lengths(&self) -> Result<DimensionLengths, GSError> {
+        if self.dimensions.len() > MAX_DIMENSIONS {
+            return Err(GSError::Collection(format!(
+                "Collections can have at most {} dimensions, found {}",
+                MAX_DIMENSIONS,
+                self.dimensions.len()
+            )));
+        }
+
+        if !self.vertex_dimensions.is_empty()
+            && self.vertex_dimensions.iter().map(Vec::len).ne(self.dimensions.iter().map(Vec::len))
+        {
+            return Err(GSError::Collection(format!(
+                "Vertex views {:?} should have the same dimensions as the edge views {:?}",
+                self.vertex_dimensions.iter().map(Vec::len).collect::<Vec<_>>(),
+                self.dimensions.iter().map(Vec::len).collect::<Vec<_>>()
+            )));
+        }
+
+        get_dimension_lengths(self.dimensions.iter().map(Vec::len))
+    }
+
+    /// Runs only the filter matrix and ordering stages of the creation of the collection, and
+    /// reports the order of the views and the diffs they are estimated to have.
+    pub fn explain(&self, global_store: &GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let dimension_lengths = self.get_dimension_lengths()?;
+        let (orders, matrices) = estimate_orders(
+            self.dimensions.clone(),
+            &dimension_lengths,
+            &global_store.graph,
+            &global_store.index_store,
+            self.manual_order,
+            global_store.threads.get(),
+            global_store.process_id,
+            &self.hosts,
+        )?;
+
+        let mut lines = vec![format!(
+            "Collection '{}': {} views in dimensions of lengths {:?}, {} order",
+            self.name,
+            dimension_lengths.iter().map(|&length| length as usize).product::<usize>(),
+            dimension_lengths,
+            if self.manual_order { "manual" } else { "computed" }
+        )];
+        for (dimension_index, (order, matrix)) in orders.iter().zip(&matrices).enumerate() {
+            let manual_order = (1..=dimension_lengths[dimension_index]).collect::<Vec<_>>();
+            let diffs = get_order_diffs(matrix, order);
+            let manual_diffs = get_order_diffs(matrix, &manual_order);
+            lines.push(format!("Dimension {}:", dimension_index + 1));
+            for (timestamp_id, (&view, view_diffs)) in order.iter().zip(&diffs).enumerate() {
+                let view = view as usize - 1;
+                lines.push(format!(
+                    "  {}: view {} [{}], ~{} diffs",
+                    timestamp_id, view, self.view_predicates[dimension_index][view], view_diffs
+                ));
+            }
+            lines.push(format!(
+                "  Estimated diffs = {}, with manual order = {}",
+                diffs.iter().sum::<usize>(),
+                manual_diffs.iter().sum::<usize>()
+            ));
+        }
+        Ok(GraphSurgeResult::new(lines.join("\n")))
+    }
+}
+
 pub fn print_totals(cube: &FilteredCube) {
-    let mut total_data_count = 0;
-    let mut total_diff_count = 0;
-    let mut add_diff_count = 0;
-    let mut del_diff_count = 0;
-    let mut partial_diff_count = 0;
-    let zeroth_timestamp = GSTimestamp::get_zeroth_timestamp();
-    for (_, timestamp, (full_edges, diff_edges), (adds, dels)) in &cube.data.entries {
+    for (_, timestamp, (full_edges, diff_edges), (adds, dels), _) in &cube.data.entries {
         info!("{}: ({}, {} + {} = {})", timestamp, full_edges.len(), adds, dels, diff_edges.len());
-        total_data_count += full_edges.len();
-        total_diff_count += diff_edges.len();
-        add_diff_count += adds;
-        del_diff_count += dels;
         assert_eq!(
             diff_edges.len(),
             adds + dels,
@@ -74,20 +147,24 @@ pub fn print_totals(cube: &FilteredCube) {
             adds + dels,
             diff_edges.len()
         );
-        if *timestamp != zeroth_timestamp {
-            partial_diff_count += diff_edges.len();
-        }
     }
-    info!("Total data = {}", total_data_count);
-    info!("Total diffs = {} + {} = {}", add_diff_count, del_diff_count, total_diff_count);
-    info!("Partial diffs = {}", partial_diff_count);
+    let totals = cube.get_totals();
+    info!("Total data = {}", totals.data);
+    info!("Total diffs = {} + {} = {}", totals.adds, totals.dels, totals.diffs);
+    info!("Partial diffs = {}", totals.partial_diffs);
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::filtered_cubes::timestamp::GSTimestamp;
+    use crate::filtered_cubes::timestamp::{GSTimestamp, MAX_DIMENSIONS};
     use crate::global_store::GlobalStore;
+    use crate::graph::properties::property_value::PropertyValue;
     use crate::process_query;
+    use gs_analytics_api::{EdgeId, VertexId};
+    use hashbrown::{HashMap, HashSet};
+    use itertools::Itertools;
+    use std::convert::TryFrom;
+    use timely::PartialOrder;
 
     #[test]
     fn test_filtered_matrix() {
@@ -120,6 +197,7 @@ mod tests {
                     vec![((0, 5), 1), ((1, 6), 1), ((1, 7), 1), ((2, 8), 1)],
                 ),
                 (4, 0),
+                (vec![], vec![]),
             ),
             (
                 1,
@@ -137,6 +215,7 @@ mod tests {
                     ],
                 ),
                 (3, 4),
+                (vec![], vec![]),
             ),
             (
                 2,
@@ -154,9 +233,214 @@ mod tests {
                     ],
                 ),
                 (4, 3),
+                (vec![], vec![]),
             ),
         ];
 
         assert_eq!(created_cube.data.entries, expected_data);
     }
+
+    #[test]
+    fn test_edge_properties() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = "
+            load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        let mut cube_query = "
+            create view collection amounts
+            where [year <= 2000],[year <= 2008],[year <= 2018]
+            with edge properties (amount, year)
+            manually_ordered
+            materialize_full_view;"
+            .to_owned();
+        process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+
+        let amount = global_store.key_store.get_key_id("amount").expect("Key not found");
+        let year = global_store.key_store.get_key_id("year").expect("Key not found");
+        let graph = &global_store.graph;
+        let expected_payloads: HashMap<_, _> = graph
+            .edges()
+            .iter()
+            .enumerate()
+            .map(|(edge_id, edge)| {
+                let edge_id = EdgeId::try_from(edge_id).expect("Overflow");
+                let edge_id_pv = PropertyValue::get_id(edge_id);
+                let payload = [amount, year]
+                    .iter()
+                    .map(|&key_id| {
+                        graph
+                            .get_edge_id_property_value(edge_id, &edge_id_pv, key_id)
+                            .and_then(|value| value.as_isize())
+                            .expect("Property not found")
+                    })
+                    .collect::<Vec<_>>();
+                ((edge.src_vertex_id, edge.dst_vertex_id), payload)
+            })
+            .collect();
+
+        let data =
+            &global_store.filtered_cube_store.cubes.get("amounts").expect("Cube not found").data;
+        assert_eq!(data.edge_properties, vec!["amount".to_owned(), "year".to_owned()]);
+        assert_eq!(data.payloads.len(), data.entries.len());
+        for (entry_index, (_, _, (full_edges, diff_edges), _, _)) in data.entries.iter().enumerate()
+        {
+            let (full_payloads, _) = &data.payloads[entry_index];
+            assert_eq!(full_payloads.len(), full_edges.len());
+            for (edge, payload) in full_edges.iter().zip(full_payloads) {
+                assert_eq!(payload, &expected_payloads[edge]);
+            }
+            for (edge_index, (edge, _)) in diff_edges.iter().enumerate() {
+                assert_eq!(data.diff_payload(entry_index, edge_index), expected_payloads[edge]);
+            }
+        }
+
+        let mut cube_query = "
+            create view collection included
+            where [year <= 2000],[year <= 2008]
+            with edge properties (include);"
+            .to_owned();
+        let error = process_query(&mut global_store, &mut cube_query)
+            .expect_err("Boolean properties cannot be carried");
+        assert!(error.to_string().contains("integer value for property 'include'"));
+    }
+
+    #[test]
+    fn test_multiple_dimensions() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = "
+            load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        let mut cube_query = "
+            create view collection years_included
+            where [year <= 2000],[year <= 2018]
+            by [include = true],[include = false]
+            manually_ordered
+            materialize_full_view;"
+            .to_owned();
+        process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+
+        let cube =
+            global_store.filtered_cube_store.cubes.get("years_included").expect("Cube not found");
+        assert_eq!(cube.dimension_lengths, vec![2, 2]);
+        let expected_full_edges = vec![
+            (GSTimestamp::new(&[0, 0]), vec![(0, 1), (3, 2)]),
+            (GSTimestamp::new(&[0, 1]), vec![(4, 5)]),
+            (GSTimestamp::new(&[1, 0]), vec![(0, 1), (2, 1), (3, 2), (6, 7)]),
+            (GSTimestamp::new(&[1, 1]), vec![(0, 2), (3, 7), (4, 5)]),
+        ];
+        for (entry, (expected_timestamp, expected_edges)) in
+            cube.data.entries.iter().zip(&expected_full_edges)
+        {
+            let (_, timestamp, (full_edges, _), _, _) = entry;
+            assert_eq!(timestamp, expected_timestamp);
+            assert_eq!(timestamp.total_dimensions(), 2);
+            assert_eq!(&full_edges.iter().copied().sorted().collect_vec(), expected_edges);
+
+            // The full view is the sum of the diffs at or before its timestamp.
+            let mut sums = HashMap::new();
+            for (_, previous, (_, diff_edges), _, _) in &cube.data.entries {
+                if previous.less_equal(timestamp) {
+                    for (edge, change) in diff_edges {
+                        *sums.entry(*edge).or_insert(0) += change;
+                    }
+                }
+            }
+            sums.retain(|_, count| *count != 0);
+            assert_eq!(&sums.keys().copied().sorted().collect_vec(), expected_edges);
+        }
+
+        // Dimensions can also be separated by whitespace alone, as in earlier versions.
+        let mut cube_query = "
+            create view collection years_included_old_syntax
+            where [year <= 2000],[year <= 2018] [include = true],[include = false]
+            manually_ordered
+            materialize_full_view;"
+            .to_owned();
+        process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+        let cubes = &global_store.filtered_cube_store.cubes;
+        let old_syntax = &cubes["years_included_old_syntax"];
+        assert_eq!(old_syntax.dimension_lengths, vec![2, 2]);
+        assert_eq!(old_syntax.data.entries, cubes["years_included"].data.entries);
+
+        let mut cube_query = format!(
+            "create view collection too_many where {};",
+            std::iter::repeat("[year <= 2000]").take(MAX_DIMENSIONS + 1).join(" by ")
+        );
+        let error = process_query(&mut global_store, &mut cube_query)
+            .expect_err("Too many dimensions should fail");
+        assert!(error.to_string().contains("at most"));
+    }
+
+    #[test]
+    fn test_vertex_views() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = "
+            load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        let mut cube_query = "
+            create view collection people
+            where [year <= 2000],[year <= 2018]
+            vertices where [country = 'canada'],[include = true]
+            manually_ordered
+            materialize_full_view;"
+            .to_owned();
+        process_query(&mut global_store, &mut cube_query).expect("Cube not created");
+
+        let graph = &global_store.graph;
+        let country = global_store.key_store.get_key_id("country").expect("Key not found");
+        let include = global_store.key_store.get_key_id("include").expect("Key not found");
+        let vertices_where = |key_id, value: PropertyValue| {
+            (0..graph.vertex_count())
+                .map(|vertex_id| VertexId::try_from(vertex_id).expect("Overflow"))
+                .filter(|&vertex_id| {
+                    let vertex_id_pv = PropertyValue::get_id(vertex_id);
+                    graph.get_vertex_id_property_value(vertex_id, &vertex_id_pv, key_id).as_deref()
+                        == Some(&value)
+                })
+                .collect::<HashSet<_>>()
+        };
+        let expected_vertices = vec![
+            vertices_where(country, PropertyValue::String("canada".to_owned())),
+            vertices_where(include, PropertyValue::Bool(true)),
+        ];
+
+        let data =
+            &global_store.filtered_cube_store.cubes.get("people").expect("Cube not found").data;
+        assert!(data.has_vertex_views);
+        let mut current = HashMap::new();
+        for ((_, _, _, _, (full_vertices, diff_vertices)), expected) in
+            data.entries.iter().zip(&expected_vertices)
+        {
+            assert_eq!(&full_vertices.iter().copied().collect::<HashSet<_>>(), expected);
+            for (vertex_id, change) in diff_vertices {
+                *current.entry(*vertex_id).or_insert(0) += change;
+            }
+            current.retain(|_, count| *count != 0);
+            assert_eq!(&current.keys().copied().collect::<HashSet<_>>(), expected);
+        }
+
+        let mut cube_query = "
+            create view collection mismatched
+            where [year <= 2000],[year <= 2018]
+            vertices where [include = true];"
+            .to_owned();
+        let error = process_query(&mut global_store, &mut cube_query)
+            .expect_err("Vertex views should match the edge views");
+        assert!(error.to_string().contains("should have the same dimensions"));
+    }
 }
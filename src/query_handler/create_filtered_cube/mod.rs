@@ -1,4 +1,5 @@
-use crate::query_handler::create_view::WhereConditions;
+use crate::graph::properties::PropertyKeyId;
+use crate::query_handler::create_view::{PropertyName, WhereConditions};
 
 pub mod executor;
 
@@ -6,6 +7,14 @@ pub mod executor;
 pub struct CreateViewCollectionAst {
     name: String,
     dimensions: Vec<Dimension>,
+    /// The vertex views of the collection, with the same shape as `dimensions`. Empty if the
+    /// vertices of each view are the endpoints of its edges.
+    vertex_dimensions: Vec<Dimension>,
+    /// The predicates of `dimensions` and `vertex_dimensions`, as written in the query.
+    view_predicates: Vec<Vec<String>>,
+    vertex_view_predicates: Vec<Vec<String>>,
+    /// The edge properties whose values are carried by the diffs of the collection.
+    edge_properties: Vec<(PropertyName, PropertyKeyId)>,
     manual_order: bool,
     materialized: bool,
     store_total_data: bool,
@@ -0,0 +1,99 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::query_handler::create_index::CreateIndexAst;
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+
+impl GraphSurgeQuery for CreateIndexAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let index = global_store.index_store.create_index(
+            self.vertex_or_edge,
+            self.key_id,
+            self.index_type,
+            &global_store.graph,
+        );
+        Ok(GraphSurgeResult::new(format!(
+            "Index on '{}' created with {} entries",
+            self.property,
+            index.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+
+    #[test]
+    fn test_indexed_collection() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query = "load graph with vertices from 'data/small_properties/vertices.txt'
+         and edges from 'data/small_properties/edges.txt' comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        let views = "[year <= 2005], [year <= 2010 and include = true],
+            [year in (2000, 2018) and u.city = 'toronto'], [year <> 2000 or amount > 500], [year <> 2000 and v.city in ('pune', 'waterloo')]";
+        let create_collection = |global_store: &mut GlobalStore, name: &str| {
+            let mut cube_query =
+                format!("create view collection {} where {} manually_ordered;", name, views);
+            process_query(global_store, &mut cube_query).expect("Cube not created");
+        };
+        create_collection(&mut global_store, "plain_cube");
+
+        for (mut query, expected_result) in vec![
+            ("show indexes".to_owned(), "No indexes registered"),
+            ("create index on edges(year)".to_owned(), "Index on 'year' created with 7 entries"),
+            (
+                "create index on vertices(city) using hash".to_owned(),
+                "Index on 'city' created with 9 entries",
+            ),
+            (
+                "show indexes".to_owned(),
+                "Indexes:\n(1) index on edges(year) using sorted, 7 entries\n\
+                (2) index on vertices(city) using hash, 9 entries",
+            ),
+        ] {
+            let result = process_query(&mut global_store, &mut query).expect("Query failed");
+            assert_eq!(result, expected_result);
+        }
+        create_collection(&mut global_store, "indexed_cube");
+
+        let cubes = &global_store.filtered_cube_store.cubes;
+        let without_indexes = cubes.get("plain_cube").expect("Cube not found");
+        let with_indexes = cubes.get("indexed_cube").expect("Cube not found");
+        assert_eq!(with_indexes.data.entries, without_indexes.data.entries);
+    }
+
+    #[test]
+    fn test_indexed_collection_mixed_numbers() {
+        let mut global_store = GlobalStore::default();
+
+        let mut graph_query =
+            "load graph with vertices from 'data/small_float_properties/vertices.txt'
+         and edges from 'data/small_float_properties/edges.txt' comment '#';"
+                .to_owned();
+        process_query(&mut global_store, &mut graph_query).expect("Graph not loaded");
+
+        // Integers and floats are compared by value, which indexes cannot answer.
+        let views =
+            "[weight in (2, 5)], [weight not in (0.5, 2)], [hops in (1.0, 4.5)], [weight = 2]";
+        let create_collection = |global_store: &mut GlobalStore, name: &str| {
+            let mut cube_query =
+                format!("create view collection {} where {} manually_ordered;", name, views);
+            process_query(global_store, &mut cube_query).expect("Cube not created");
+        };
+        create_collection(&mut global_store, "plain_cube");
+        for query in &["create index on edges(weight)", "create index on edges(hops) using hash"] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Index not created");
+        }
+        create_collection(&mut global_store, "indexed_cube");
+
+        let cubes = &global_store.filtered_cube_store.cubes;
+        let without_indexes = cubes.get("plain_cube").expect("Cube not found");
+        let with_indexes = cubes.get("indexed_cube").expect("Cube not found");
+        assert_eq!(with_indexes.data.entries, without_indexes.data.entries);
+    }
+}
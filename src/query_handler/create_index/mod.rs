@@ -0,0 +1,26 @@
+use crate::graph::indexes::IndexType;
+use crate::graph::properties::PropertyKeyId;
+use crate::graph::VertexOrEdge;
+use crate::query_handler::create_view::PropertyName;
+
+pub mod executor;
+
+#[derive(new)]
+pub struct CreateIndexAst {
+    pub vertex_or_edge: VertexOrEdge,
+    pub property: PropertyName,
+    pub key_id: PropertyKeyId,
+    pub index_type: IndexType,
+}
+
+impl std::fmt::Display for CreateIndexAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "create index on {}({}) using {}",
+            if self.vertex_or_edge == VertexOrEdge::Vertex { "vertices" } else { "edges" },
+            self.property,
+            self.index_type
+        )
+    }
+}
@@ -28,18 +28,25 @@ pub struct SectionDetails {
     pub group_clauses: Vec<GroupClause>,
     pub aggregate_clauses: Vec<AggregateClause>,
 }
+/// Conditions that must all hold, i.e., a conjunction.
 pub type WhereConditions = Vec<WhereCondition>;
-pub type WhereCondition = (IsNegation, Vec<WherePredicate>);
+#[derive(Debug, Clone)]
+pub enum WhereCondition {
+    Predicate(WherePredicate),
+    Negation(WhereConditions),
+    Disjunction(Vec<WhereConditions>),
+}
 pub type WherePredicate = ((LeftOperand, Operator, RightOperand), PredicateFunction);
 #[derive(Clone, new)]
 pub struct PredicateFunction(pub PredicateFunctionClosure);
-pub type PredicateFunctionClosure = Arc<dyn Fn(VertexOrEdgeId, GraphPointer) -> bool + Send + Sync>;
+/// Returns `None` if the predicate evaluates to unknown, e.g., when a compared property is missing.
+pub type PredicateFunctionClosure =
+    Arc<dyn Fn(VertexOrEdgeId, GraphPointer) -> Option<bool> + Send + Sync>;
 impl std::fmt::Debug for PredicateFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "[closure]",)
     }
 }
-pub type IsNegation = bool;
 pub type GroupClause = Vec<GroupCondition>;
 #[derive(Debug, Clone)]
 pub enum GroupCondition {
@@ -163,13 +170,15 @@ pub fn fmt_condition_clauses(cc: &[WhereCondition]) -> String {
     cc.iter().map(fmt_condition_clause).collect::<Vec<_>>().join(" and ")
 }
 
-pub fn fmt_condition_clause((ctype, c): &WhereCondition) -> String {
-    format!(
-        "{}{}{}",
-        if *ctype { "!(" } else { "" },
-        c.iter().map(fmt_where_clause).collect::<Vec<String>>().join(" and "),
-        if *ctype { ")" } else { "" },
-    )
+pub fn fmt_condition_clause(condition: &WhereCondition) -> String {
+    match condition {
+        WhereCondition::Predicate(wp) => fmt_where_clause(wp),
+        WhereCondition::Negation(cc) => format!("!({})", fmt_condition_clauses(cc)),
+        WhereCondition::Disjunction(ccs) => format!(
+            "({})",
+            ccs.iter().map(|cc| fmt_condition_clauses(cc)).collect::<Vec<_>>().join(" or ")
+        ),
+    }
 }
 
 pub fn fmt_where_clause(((op1, c, op2), _): &WherePredicate) -> String {
@@ -0,0 +1,134 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::query_handler::describe_collection::DescribeCollectionAst;
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+
+impl GraphSurgeQuery for DescribeCollectionAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let cube = global_store
+            .filtered_cube_store
+            .cubes
+            .get(&self.name)
+            .ok_or_else(|| GSError::CollectionMissing(self.name.clone()))?;
+        Ok(GraphSurgeResult::new(format!(
+            "Collection '{}'{}\n{}",
+            self.name,
+            if cube.stale { " (stale)" } else { "" },
+            cube
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+    use hashbrown::HashMap;
+
+    #[test]
+    fn test_describe_collection() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection years
+            where [year <= 2000],[year  <=  2008]
+            vertices where [country = 'canada'],[city  =  'new  york']
+            manually_ordered;",
+            "alter view collection years add [year <= 2018] vertices where [include2 = true];",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+
+        let cube = global_store.filtered_cube_store.cubes.get("years").expect("Cube not found");
+        let definition = cube.definition.as_ref().expect("Definition missing");
+        assert_eq!(
+            definition.dimensions,
+            vec![vec![
+                "year <= 2000".to_owned(),
+                "year <= 2008".to_owned(),
+                "year <= 2018".to_owned()
+            ]]
+        );
+        assert_eq!(definition.orders, vec![vec![0, 1, 2]]);
+        let totals = cube.get_totals();
+
+        let mut query = "describe collection years;".to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Query failed");
+        let lines = result.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Collection 'years'");
+        assert_eq!(lines[3], "Order: manual");
+        assert!(
+            lines[5].starts_with("  [0]: [year <= 2000] vertices [country = 'canada'] (full"),
+            "{}",
+            result
+        );
+        // Whitespace is only collapsed outside of strings.
+        assert!(lines[6].starts_with("  [1]: [year <= 2008] vertices [city = 'new  york'] (full"));
+        assert!(lines[7].starts_with("  [2]: [year <= 2018] vertices [include2 = true] (full"));
+        assert!(result.contains(&format!(
+            "Total diffs = {} + {} = {}",
+            totals.adds, totals.dels, totals.diffs
+        )));
+
+        let mut query = "describe collection missing;".to_owned();
+        let error =
+            process_query(&mut global_store, &mut query).expect_err("Collection should be missing");
+        assert!(error.to_string().contains("has not been created yet"));
+    }
+
+    #[test]
+    fn test_describe_collection_full_edges() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection years
+            where [year <= 2000],[year <= 2008]
+            by [include = true],[amount >= 100];",
+            "create view collection appended where [year <= 2000],[year <= 2008];",
+            "alter view collection appended add [year <= 2018];",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+
+        // The full views are not materialized, so their sizes come from the diffs.
+        let expected = vec![
+            ("[year <= 2000] by [include = true]", 2),
+            ("[year <= 2000] by [amount >= 100]", 3),
+            ("[year <= 2008] by [include = true]", 3),
+            ("[year <= 2008] by [amount >= 100]", 5),
+        ];
+        assert_eq!(get_full_edges(&mut global_store, "years"), to_map(&expected));
+
+        let expected = vec![("[year <= 2000]", 3), ("[year <= 2008]", 5), ("[year <= 2018]", 7)];
+        let mut query = "describe collection appended;".to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Query failed");
+        assert_eq!(result.lines().nth(3), Some("Order: manual"));
+        assert_eq!(get_full_edges(&mut global_store, "appended"), to_map(&expected));
+    }
+
+    /// Returns the number of full edges of each view in the description of a collection.
+    fn get_full_edges(global_store: &mut GlobalStore, name: &str) -> HashMap<String, usize> {
+        let mut query = format!("describe collection {};", name);
+        let result = process_query(global_store, &mut query).expect("Query failed");
+        result
+            .lines()
+            .filter_map(|line| {
+                let (view, counts) = line.split_once(" (full edges: ")?;
+                let view = view.split_once(": ")?.1;
+                let full_edges = counts.split(',').next()?.parse().ok()?;
+                Some((view.to_owned(), full_edges))
+            })
+            .collect()
+    }
+
+    fn to_map(expected: &[(&str, usize)]) -> HashMap<String, usize> {
+        expected.iter().map(|(view, full_edges)| ((*view).to_owned(), *full_edges)).collect()
+    }
+}
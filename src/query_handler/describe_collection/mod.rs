@@ -0,0 +1,12 @@
+pub mod executor;
+
+#[derive(new)]
+pub struct DescribeCollectionAst {
+    name: String,
+}
+
+impl std::fmt::Display for DescribeCollectionAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "describe collection {}", self.name)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::query_handler::explain::ExplainAst;
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+
+impl GraphSurgeQuery for ExplainAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        match self {
+            ExplainAst::CreateCollection(ast) => ast.explain(global_store),
+            ExplainAst::RunComputation(ast) => ast.explain(global_store),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+
+    #[test]
+    fn test_explain() {
+        let mut global_store = GlobalStore::default();
+
+        let mut query = "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not loaded");
+
+        let views = "[year <= 2018],[year <= 2000],[year <= 2008] manually_ordered";
+        let mut query = format!("explain create view collection years where {};", views);
+        let result = process_query(&mut global_store, &mut query).expect("Query failed");
+        assert!(global_store.filtered_cube_store.cubes.is_empty(), "Explain created a collection");
+        let lines = result.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("Collection 'years': 3 views"), "{}", result);
+        assert!(lines[2].starts_with("  0: view 0 [year <= 2018], ~"), "{}", result);
+        assert!(lines[4].starts_with("  2: view 2 [year <= 2008], ~"), "{}", result);
+
+        let mut query = format!("create view collection years where {};", views);
+        process_query(&mut global_store, &mut query).expect("Query failed");
+        let totals = global_store.filtered_cube_store.cubes["years"].get_totals();
+        assert_eq!(
+            lines[5],
+            format!("  Estimated diffs = {0}, with manual order = {0}", totals.diffs)
+        );
+
+        let mut query = "explain run adaptive upto 1 comp_x 1.0 diff_x 1.0 limit 1 algo lr
+            computation wcc on collection years;"
+            .to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Query failed");
+        assert!(result.starts_with("Runner: "), "{}", result);
+        assert!(result.contains(&format!("Diffs: {} edge diffs", totals.diffs)), "{}", result);
+        assert!(result.contains("Splits: no fitted model for 'wcc' on 'years' yet"), "{}", result);
+
+        let mut query = "explain run computation missing on collection years;".to_owned();
+        process_query(&mut global_store, &mut query).expect_err("Computation should be missing");
+    }
+}
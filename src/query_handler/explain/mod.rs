@@ -0,0 +1,19 @@
+use crate::query_handler::create_filtered_cube::CreateViewCollectionAst;
+use crate::query_handler::run_computation::RunComputationAst;
+
+pub mod executor;
+
+/// A query that is only planned and reported on, without being executed.
+pub enum ExplainAst {
+    CreateCollection(CreateViewCollectionAst),
+    RunComputation(RunComputationAst),
+}
+
+impl std::fmt::Display for ExplainAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ExplainAst::CreateCollection(ast) => write!(f, "explain {}", ast),
+            ExplainAst::RunComputation(ast) => write!(f, "explain {}", ast),
+        }
+    }
+}
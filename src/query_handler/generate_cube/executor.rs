@@ -1,7 +1,7 @@
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
 use crate::filtered_cubes::timestamp::GSTimestamp;
-use crate::filtered_cubes::{DimensionLength, FilteredCube};
+use crate::filtered_cubes::{get_dimension_length, FilteredCube};
 use crate::global_store::GlobalStore;
 use crate::query_handler::create_filtered_cube::executor::print_totals;
 use crate::query_handler::generate_cube::GenerateCubeAst;
@@ -21,16 +21,16 @@ impl GraphSurgeQuery for GenerateCubeAst {
             return Err(GSError::CollectionAlreadyExists(self.name.clone()));
         }
 
+        let dimension_lengths = vec![get_dimension_length(self.batch_count + 1)?];
         info!("Generating '{}'", self.name);
         let mut cube_data = Vec::new();
 
         let graph = GraphMMap::new(&self.graph_filename);
         let mut edges = (0..graph.nodes())
             .flat_map(|node| {
-                graph
-                    .edges(node)
-                    .iter()
-                    .map(move |neighbor| (VertexId::try_from(node).expect("Overflow"), *neighbor))
+                graph.edges(node).iter().map(move |&neighbor| {
+                    (VertexId::try_from(node).expect("Overflow"), VertexId::from(neighbor))
+                })
             })
             .collect::<Vec<_>>();
         info!("Loaded {} edges", edges.len(),);
@@ -39,7 +39,13 @@ impl GraphSurgeQuery for GenerateCubeAst {
         edges.shuffle(&mut rng);
         let initial_edges = edges[..self.first_view].iter().map(|edge| (*edge, 1)).collect_vec();
         let inital_len = initial_edges.len();
-        cube_data.push((0, GSTimestamp::new(&[0]), (Vec::new(), initial_edges), (inital_len, 0)));
+        cube_data.push((
+            0,
+            GSTimestamp::new(&[0]),
+            (Vec::new(), initial_edges),
+            (inital_len, 0),
+            (Vec::new(), Vec::new()),
+        ));
 
         for index in 0..self.batch_count {
             info!("Processing batch {} of {}", index + 1, self.batch_count);
@@ -81,14 +87,13 @@ impl GraphSurgeQuery for GenerateCubeAst {
 
             cube_data.push((
                 index + 1,
-                GSTimestamp::new(&[DimensionLength::try_from(index + 1).expect("Overflow")]),
+                GSTimestamp::new(&[get_dimension_length(index + 1)?]),
                 (Vec::new(), data),
                 (adds_len, dels_len),
+                (Vec::new(), Vec::new()),
             ));
         }
 
-        let dimension_lengths =
-            vec![DimensionLength::try_from(self.batch_count + 1).expect("Overflow")];
         let timestamp_mappings = get_timestamp_mappings(&dimension_lengths);
         let cube = FilteredCube::new(
             timestamp_mappings,
@@ -1,7 +1,7 @@
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
 use crate::filtered_cubes::timestamp::GSTimestamp;
-use crate::filtered_cubes::{DimensionLength, DimensionLengths, FilteredCube};
+use crate::filtered_cubes::{get_dimension_lengths, DimensionLengths, FilteredCube};
 use crate::global_store::GlobalStore;
 use crate::query_handler::create_filtered_cube::executor::print_totals;
 use crate::query_handler::load_cube::LoadCubeAst;
@@ -31,22 +31,15 @@ impl GraphSurgeQuery for LoadCubeAst {
             info!("Ignoring threads...");
         }
 
-        let dimension_lengths: DimensionLengths = if cfg!(feature = "nd-timestamps") {
-            vec![self.m, self.n]
-        } else {
-            if self.m != 1 {
-                return Err(GSError::Collection("Multiple dimensions not supported".to_owned()));
-            }
-            vec![self.n]
-        }
-        .into_iter()
-        .map(|length| DimensionLength::try_from(length).expect("DimensionLength overflow"))
-        .collect();
+        // A single row of batches is loaded as a 1-dimensional collection.
+        let dimension_lengths: DimensionLengths = get_dimension_lengths(
+            if self.m == 1 { vec![self.n] } else { vec![self.m, self.n] }.into_iter(),
+        )?;
         let mut filtered_cube_data: Vec<CubeDataEntries<GSTimestamp>> = Vec::new();
         let timestamp_mappings = get_timestamp_mappings(&dimension_lengths);
 
         for (ts_index, (_, timestamp)) in timestamp_mappings.0.iter().enumerate() {
-            let (i, j) = if cfg!(feature = "nd-timestamps") {
+            let (i, j) = if dimension_lengths.len() == 2 {
                 (timestamp.get_value_at(0, 2), timestamp.get_value_at(1, 2))
             } else {
                 (0, timestamp.get_value_at(0, 1))
@@ -58,7 +51,13 @@ impl GraphSurgeQuery for LoadCubeAst {
                 let batch = (0..graph.nodes())
                     .flat_map(|node| {
                         graph.edges(node).iter().map(move |&neighbor| {
-                            ((VertexId::try_from(node).expect("overflow"), neighbor), 1)
+                            (
+                                (
+                                    VertexId::try_from(node).expect("overflow"),
+                                    VertexId::from(neighbor),
+                                ),
+                                1,
+                            )
                         })
                     })
                     .collect_vec();
@@ -121,7 +120,7 @@ impl GraphSurgeQuery for LoadCubeAst {
             info!("Loaded {} updates at {}", batch.len(), timestamp);
             let full = if self.with_full {
                 let mut previous = Vec::new();
-                for (_, ts, (_, diff_data), _) in &filtered_cube_data {
+                for (_, ts, (_, diff_data), _, _) in &filtered_cube_data {
                     if ts.less_than(timestamp) {
                         previous.push(diff_data);
                     }
@@ -147,15 +146,18 @@ impl GraphSurgeQuery for LoadCubeAst {
                 Vec::<SimpleEdge>::new()
             };
 
-            filtered_cube_data.push((ts_index, *timestamp, (full, batch), (adds, dels)));
+            filtered_cube_data.push((
+                ts_index,
+                *timestamp,
+                (full, batch),
+                (adds, dels),
+                (Vec::new(), Vec::new()),
+            ));
         }
         info!("Total updates = {}", filtered_cube_data.len());
-        let cube = FilteredCube::new(
-            timestamp_mappings,
-            dimension_lengths,
-            None,
-            FilteredCubeData::new(filtered_cube_data),
-        );
+        let mut cube_data = FilteredCubeData::new(filtered_cube_data);
+        cube_data.has_full_views = self.with_full;
+        let cube = FilteredCube::new(timestamp_mappings, dimension_lengths, None, cube_data);
         print_totals(&cube);
 
         global_store.filtered_cube_store.cubes.insert(self.name.clone(), cube);
@@ -170,7 +172,6 @@ mod tests {
     use crate::process_query;
     use gs_analytics_api::CubeDataEntries;
 
-    #[cfg(feature = "nd-timestamps")]
     #[test]
     fn test_2d() {
         assert(
@@ -196,10 +197,29 @@ mod tests {
                         ],
                     ),
                     (8, 0),
+                    (vec![], vec![]),
+                ),
+                (
+                    1,
+                    GSTimestamp::new(&[0, 1]),
+                    (vec![], vec![((6, 5), 1), ((5, 9), -1)]),
+                    (1, 1),
+                    (vec![], vec![]),
+                ),
+                (
+                    2,
+                    GSTimestamp::new(&[1, 0]),
+                    (vec![], vec![((2, 9), 1), ((4, 5), -1)]),
+                    (1, 1),
+                    (vec![], vec![]),
+                ),
+                (
+                    3,
+                    GSTimestamp::new(&[1, 1]),
+                    (vec![], vec![((9, 3), 1), ((2, 3), -1)]),
+                    (1, 1),
+                    (vec![], vec![]),
                 ),
-                (1, GSTimestamp::new(&[0, 1]), (vec![], vec![((6, 5), 1), ((5, 9), -1)]), (1, 1)),
-                (2, GSTimestamp::new(&[1, 0]), (vec![], vec![((2, 9), 1), ((4, 5), -1)]), (1, 1)),
-                (3, GSTimestamp::new(&[1, 1]), (vec![], vec![((9, 3), 1), ((2, 3), -1)]), (1, 1)),
             ],
         );
     }
@@ -229,19 +249,28 @@ mod tests {
                         ],
                     ),
                     (8, 0),
+                    (vec![], vec![]),
+                ),
+                (
+                    1,
+                    GSTimestamp::new(&[1]),
+                    (vec![], vec![((2, 9), 1), ((4, 5), -1)]),
+                    (1, 1),
+                    (vec![], vec![]),
                 ),
-                (1, GSTimestamp::new(&[1]), (vec![], vec![((2, 9), 1), ((4, 5), -1)]), (1, 1)),
                 (
                     2,
                     GSTimestamp::new(&[2]),
                     (vec![], vec![((9, 3), 1), ((6, 5), 1), ((5, 9), -1), ((2, 3), -1)]),
                     (2, 2),
+                    (vec![], vec![]),
                 ),
                 (
                     3,
                     GSTimestamp::new(&[3]),
                     (vec![], vec![((4, 5), 1), ((2, 3), 1), ((2, 9), -1), ((9, 3), -1)]),
                     (2, 2),
+                    (vec![], vec![]),
                 ),
             ],
         );
@@ -1,63 +1,72 @@
 use crate::error::GSError;
 use crate::global_store::GlobalStore;
 use crate::graph::key_store::KeyStore;
-use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::property_value::{Float, PropertyValue};
 use crate::graph::properties::Properties;
 use crate::graph::properties::PropertyKeyId;
-use crate::graph::Vertex;
 use crate::graph::{Edge, Graph};
-use crate::query_handler::load_graph::LoadGraphAst;
+use crate::query_handler::load_graph::{graphml, jsonl, GraphFormat, LoadGraphAst, RejectMode};
 use crate::query_handler::GraphSurgeQuery;
 use crate::query_handler::GraphSurgeResult;
 use crate::util::io::{get_buf_reader, GsWriter};
 use crate::util::timer::GsTimer;
-use csv::Reader;
+use csv::{Position, Reader, StringRecord};
 use gs_analytics_api::VertexId;
 use hashbrown::HashMap;
+use itertools::Itertools;
 use log::{debug, info, warn};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write};
 
 const DEFAULT_TYPE_STRING: &str = "string";
 const DEFAULT_SEPARATOR: u8 = b',';
+pub const DEFAULT_LIST_SEPARATOR: char = ';';
+const REJECTS_FILE_NAME: &str = "rejects.txt";
 pub const DEFAULT_HAS_HEADERS: bool = true;
 
-type Function = dyn Fn(&str, &str, &str) -> Result<PropertyValue, GSError>;
+type Function = dyn Fn(&str) -> Option<PropertyValue>;
 type PropertyValueClosure = Box<Function>;
-type PropertyValueClosureIndex<'a> = &'a Function;
-type ClosureMappings<'a> = HashMap<String, PropertyValueClosureIndex<'a>>;
+pub(super) type PropertyValueClosureIndex<'a> = &'a Function;
+pub(super) type ClosureMappings<'a> = HashMap<String, PropertyValueClosureIndex<'a>>;
+/// The key, type name and parsing closure for each property column.
+type PropertyColumns<'a> = Vec<(PropertyKeyId, &'a str, PropertyValueClosureIndex<'a>)>;
 
 impl GraphSurgeQuery for LoadGraphAst {
     fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
-        global_store.graph.reset();
-        global_store.key_store.reset();
+        if !self.append {
+            global_store.graph.reset();
+            global_store.key_store.reset();
+            global_store.vertex_ids.clear();
+            global_store.index_store.reset();
+        }
 
         let mut total_vertices_count = 0;
         let mut total_edges_count = 0;
 
+        let list_separator = self.list_separator.unwrap_or(DEFAULT_LIST_SEPARATOR);
         let closures: Vec<PropertyValueClosure> = vec![
-            Box::new(|property_string: &str, vertex_id_string: &str, file_path: &str| {
-                property_string.parse().map(PropertyValue::Isize).map_err(|_| {
-                    GSError::GraphParse(
-                        property_string.to_owned(),
-                        "isize",
-                        vertex_id_string.to_owned(),
-                        file_path.to_owned(),
-                    )
-                })
+            Box::new(|property_string: &str| {
+                property_string.parse().ok().map(PropertyValue::Isize)
+            }),
+            Box::new(|property_string: &str| property_string.parse().ok().map(PropertyValue::Bool)),
+            Box::new(|property_string: &str| {
+                Some(PropertyValue::String(property_string.to_owned()))
             }),
-            Box::new(|property_string: &str, vertex_id_string: &str, file_path: &str| {
-                property_string.parse().map(PropertyValue::Bool).map_err(|_| {
-                    GSError::GraphParse(
-                        property_string.to_owned(),
-                        "bool",
-                        vertex_id_string.to_owned(),
-                        file_path.to_owned(),
-                    )
-                })
+            Box::new(|property_string: &str| {
+                property_string.parse().ok().map(|value| PropertyValue::Float(Float(value)))
             }),
-            Box::new(|property_string: &str, _vertex_id_string: &str, _file_path: &str| {
-                Ok(PropertyValue::String(property_string.to_owned()))
+            Box::new(PropertyValue::parse_date),
+            Box::new(PropertyValue::parse_datetime),
+            Box::new(move |property_string: &str| {
+                split_list(property_string, list_separator).map(PropertyValue::Strings)
+            }),
+            Box::new(move |property_string: &str| {
+                split_list(property_string, list_separator)?
+                    .iter()
+                    .map(|element| element.parse())
+                    .collect::<Result<_, _>>()
+                    .ok()
+                    .map(PropertyValue::IntList)
             }),
         ];
 
@@ -65,60 +74,240 @@ impl GraphSurgeQuery for LoadGraphAst {
             ("int".into(), &*closures[0]),
             ("bool".into(), &*closures[1]),
             ("string".into(), &*closures[2]),
+            ("float".into(), &*closures[3]),
+            ("double".into(), &*closures[3]),
+            ("date".into(), &*closures[4]),
+            ("datetime".into(), &*closures[5]),
+            ("strings".into(), &*closures[6]),
+            ("ints".into(), &*closures[7]),
         ]
         .into_iter()
         .collect::<ClosureMappings>();
 
-        let mut vertices_map = HashMap::new();
-        if let Some(vertex_file) = &self.vertex_file {
-            info!("Loading vertices from file '{}'", vertex_file);
-            load_vertices(
-                vertex_file,
-                &mut vertices_map,
-                global_store,
-                &mut total_vertices_count,
-                &mappings,
-                &self,
-            )?;
-        }
-        info!("Loading edges from file '{}'", self.edge_file);
-        load_edges(
-            &self.edge_file,
+        let mut rejects = Rejects::new(self.reject_mode, self.rejects_dir.as_deref())?;
+        let previous_vertex_count = global_store.graph.vertex_count();
+        let previous_edges_count = global_store.graph.edges_count();
+        // Taken out of the store while loading, so that the rest of the store can be borrowed.
+        let mut vertices_map = std::mem::take(&mut global_store.vertex_ids);
+        let result = self.load_files(
             &mut vertices_map,
             global_store,
             &mut total_vertices_count,
             &mut total_edges_count,
             &mappings,
-            &self,
-        )?;
-
-        if self.randomize {
-            global_store.graph.randomize_edges();
+            &mut rejects,
+        );
+        global_store.vertex_ids = vertices_map;
+        let result = result.and_then(|counts| rejects.flush().map(|()| counts));
+        if result.is_ok() {
+            if self.randomize {
+                global_store.graph.randomize_edges();
+            }
+        } else {
+            // Roll back a failed load, so that an append leaves the graph as it was.
+            global_store.graph.truncate(previous_vertex_count, previous_edges_count);
+            global_store
+                .vertex_ids
+                .retain(|_, vertex_id| (*vertex_id as usize) < previous_vertex_count);
         }
+        global_store.index_store.rebuild(&global_store.graph);
+        let (rejected_vertices_count, rejected_edges_count) = result?;
 
         if let Some(dir) = &self.save_mappings_dir {
             let file_path = format!("{}/vertex_mappings.txt", dir);
             let mut file_buffer = GsWriter::new(file_path)?;
             file_buffer.write_file_lines(
-                vertices_map
+                global_store
+                    .vertex_ids
                     .iter()
                     .map(|(vertex_str, vertex_id)| format!("{},{}", vertex_str, vertex_id)),
             )?;
         }
 
         Ok(GraphSurgeResult::new(format!(
-            "{} vertices and {} edges loaded",
-            total_vertices_count, total_edges_count
+            "{} vertices and {} edges {}, {} vertex rows and {} edge rows rejected",
+            total_vertices_count,
+            total_edges_count,
+            if self.append { "appended" } else { "loaded" },
+            rejected_vertices_count,
+            rejected_edges_count
         )))
     }
 }
 
+impl LoadGraphAst {
+    /// Loads the vertex and edge files and returns the number of rejected vertex and edge rows.
+    fn load_files(
+        &self,
+        vertices_map: &mut HashMap<String, VertexId>,
+        global_store: &mut GlobalStore,
+        total_vertices_count: &mut usize,
+        total_edges_count: &mut usize,
+        mappings: &ClosureMappings,
+        rejects: &mut Rejects,
+    ) -> Result<(usize, usize), GSError> {
+        if self.format == GraphFormat::GraphMl {
+            info!("Loading graph from file '{}'", self.edge_file);
+            return graphml::load_graphml(
+                &self.edge_file,
+                vertices_map,
+                global_store,
+                total_vertices_count,
+                total_edges_count,
+                mappings,
+                rejects,
+            );
+        }
+
+        let mut rejected_vertices_count = 0;
+        if let Some(vertex_file) = &self.vertex_file {
+            info!("Loading vertices from file '{}'", vertex_file);
+            if self.format == GraphFormat::JsonLines {
+                jsonl::load_vertices(
+                    vertex_file,
+                    vertices_map,
+                    global_store,
+                    total_vertices_count,
+                    rejects,
+                    self,
+                )?;
+            } else {
+                load_vertices(
+                    vertex_file,
+                    vertices_map,
+                    global_store,
+                    total_vertices_count,
+                    mappings,
+                    rejects,
+                    self,
+                )?;
+            }
+            rejected_vertices_count = rejects.take_count(vertex_file);
+        }
+        info!("Loading edges from file '{}'", self.edge_file);
+        if self.format == GraphFormat::JsonLines {
+            jsonl::load_edges(
+                &self.edge_file,
+                vertices_map,
+                global_store,
+                total_vertices_count,
+                total_edges_count,
+                rejects,
+                self,
+            )?;
+        } else {
+            load_edges(
+                &self.edge_file,
+                vertices_map,
+                global_store,
+                total_vertices_count,
+                total_edges_count,
+                mappings,
+                rejects,
+                self,
+            )?;
+        }
+        Ok((rejected_vertices_count, rejects.take_count(&self.edge_file)))
+    }
+}
+
+/// A row that could not be loaded, with the 1-based column that caused it, if known.
+pub(super) struct RejectedRow {
+    column: Option<usize>,
+    reason: String,
+    /// Whether the row is an edge with an empty or unknown src or dst vertex id.
+    dangling_edge: bool,
+}
+
+impl RejectedRow {
+    pub(super) fn new(column: Option<usize>, reason: String) -> Self {
+        Self { column, reason, dangling_edge: false }
+    }
+
+    pub(super) fn dangling_edge(column: Option<usize>, reason: String) -> Self {
+        Self { column, reason, dangling_edge: true }
+    }
+}
+
+/// Handles rejected rows: fails the load unless the `RejectMode` skips them, and otherwise counts
+/// the rows and optionally writes them, along with the reason, to a rejects file.
+pub(super) struct Rejects {
+    mode: RejectMode,
+    writer: Option<GsWriter>,
+    count: usize,
+}
+
+impl Rejects {
+    fn new(mode: RejectMode, rejects_dir: Option<&str>) -> Result<Self, GSError> {
+        let writer = rejects_dir
+            .map(|dir| GsWriter::new(format!("{}/{}", dir, REJECTS_FILE_NAME)))
+            .transpose()?;
+        Ok(Self { mode, writer, count: 0 })
+    }
+
+    pub(super) fn reject(
+        &mut self,
+        file_path: &str,
+        line: u64,
+        row: &str,
+        rejected_row: RejectedRow,
+    ) -> Result<(), GSError> {
+        let location = match rejected_row.column {
+            Some(column) => format!("line {}, column {}", line, column),
+            None => format!("line {}", line),
+        };
+        let skip = match self.mode {
+            RejectMode::Default => rejected_row.dangling_edge,
+            RejectMode::Strict => false,
+            RejectMode::Lenient => true,
+        };
+        if !skip {
+            return Err(GSError::LoadGraph(format!(
+                "{} at {} in file '{}'",
+                rejected_row.reason, location, file_path
+            )));
+        }
+        debug!("{} at {} in file '{}'. Skipping", rejected_row.reason, location, file_path);
+        self.count += 1;
+        if let Some(writer) = &mut self.writer {
+            writer.write_file_line(&format!(
+                "{}\t{}\t{}\t{}\t{}",
+                file_path,
+                line,
+                rejected_row.column.map_or_else(String::new, |column| column.to_string()),
+                rejected_row.reason,
+                row
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows rejected since the last call.
+    pub(super) fn take_count(&mut self, file_path: &str) -> usize {
+        if self.count > 0 {
+            warn!("Skipped {} rows in file '{}'", self.count, file_path);
+        }
+        std::mem::take(&mut self.count)
+    }
+
+    fn flush(self) -> Result<(), GSError> {
+        if let Some(writer) = self.writer {
+            writer
+                .into_buf_writer()
+                .flush()
+                .map_err(|e| GSError::WriteFile(REJECTS_FILE_NAME.to_owned(), e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
 fn load_vertices(
     vertex_file: &str,
     vertices_map: &mut HashMap<String, VertexId>,
     global_store: &mut GlobalStore,
     total_vertices_count: &mut usize,
     mappings: &ClosureMappings,
+    rejects: &mut Rejects,
     load_graph_ast: &LoadGraphAst,
 ) -> Result<(), GSError> {
     let graph = &mut global_store.graph;
@@ -144,55 +333,72 @@ fn load_vertices(
     };
 
     let timer = GsTimer::now();
-    for (index, line) in reader.records().filter_map(Result::ok).enumerate() {
-        let mut line_parts = line.iter();
-
-        let vertex_id_string = line_parts.next().ok_or_else(|| {
-            GSError::LoadGraph(format!("Could not read vertex id from file '{}'", vertex_file))
-        })?;
-        if vertex_id_string.is_empty() {
-            return Err(GSError::LoadGraph(format!(
-                "Vertex id string is empty in line '{}' in file '{}'",
-                index, vertex_file
-            )));
-        }
-
-        if vertices_map.contains_key(vertex_id_string) {
-            return Err(GSError::LoadGraph(format!(
-                "Duplicate vertex id '{}' found in file '{}'",
-                vertex_id_string, vertex_file
-            )));
-        }
-
-        let properties = if let Some(property_types) = &property_types {
-            load_property_values(line_parts, vertex_id_string, vertex_file, property_types)?
-        } else {
-            Properties::default()
-        };
-
-        add_new_vertex(
-            vertex_id_string.to_owned(),
-            properties,
-            graph,
-            vertices_map,
-            total_vertices_count,
-        );
-
+    for (index, line) in reader.records().enumerate() {
         if index > 0 && index % 500_000 == 0 {
             info!("Processed {} vertices in {}", index, timer.elapsed().seconds_string());
         }
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                let line_number = e.position().map_or(0, Position::line);
+                let rejected_row = RejectedRow::new(None, e.to_string());
+                rejects.reject(vertex_file, line_number, "", rejected_row)?;
+                continue;
+            }
+        };
+        match parse_vertex(&line, vertices_map, &property_types) {
+            Ok((vertex_id_string, properties)) => {
+                add_new_vertex(
+                    vertex_id_string.to_owned(),
+                    properties,
+                    graph,
+                    vertices_map,
+                    total_vertices_count,
+                );
+            }
+            Err(rejected_row) => {
+                let line_number = line.position().map_or(0, Position::line);
+                rejects.reject(vertex_file, line_number, &line.iter().join(","), rejected_row)?;
+            }
+        }
     }
     Ok(())
 }
 
-fn add_new_vertex(
+fn parse_vertex<'a>(
+    line: &'a StringRecord,
+    vertices_map: &HashMap<String, VertexId>,
+    property_types: &Option<PropertyColumns>,
+) -> Result<(&'a str, Properties), RejectedRow> {
+    let mut line_parts = line.iter();
+
+    let vertex_id_string = line_parts.next().unwrap_or("");
+    if vertex_id_string.is_empty() {
+        return Err(RejectedRow::new(Some(1), "Vertex id is empty".to_owned()));
+    }
+    if vertices_map.contains_key(vertex_id_string) {
+        return Err(RejectedRow::new(
+            Some(1),
+            format!("Duplicate vertex id '{}'", vertex_id_string),
+        ));
+    }
+
+    let properties = if let Some(property_types) = property_types {
+        load_property_values(line_parts, 2, property_types)?
+    } else {
+        Properties::default()
+    };
+    Ok((vertex_id_string, properties))
+}
+
+pub(super) fn add_new_vertex(
     vertex_id_string: String,
     properties: Properties,
     graph: &mut Graph,
     vertices_map: &mut HashMap<String, VertexId>,
     total_vertices_count: &mut usize,
 ) -> VertexId {
-    let vertex_id = graph.append_vertex(Vertex::new(properties));
+    let vertex_id = graph.append_vertex(properties);
     vertices_map.insert(vertex_id_string, vertex_id);
     *total_vertices_count += 1;
     vertex_id
@@ -205,6 +411,7 @@ fn load_edges(
     total_vertices_count: &mut usize,
     total_edges_count: &mut usize,
     mappings: &ClosureMappings,
+    rejects: &mut Rejects,
     load_graph_ast: &LoadGraphAst,
 ) -> Result<(), GSError> {
     let graph = &mut global_store.graph;
@@ -238,120 +445,98 @@ fn load_edges(
     };
 
     let timer = GsTimer::now();
-    let mut src_empty_count = 0;
-    let mut dst_empty_count = 0;
-    let mut parsing_error_count = 0;
-    let mut src_id_missing_count = 0;
-    let mut dst_id_missing_count = 0;
-    for (index, line) in reader.records().filter_map(Result::ok).enumerate() {
+    for (index, line) in reader.records().enumerate() {
         if index > 0 && index % 1_000_000 == 0 {
             info!("Processed {} lines in {}", index, timer.elapsed().seconds_string());
         }
-        let mut line_parts = line.iter();
-
-        let from_id_string;
-        if let Some(part) = line_parts.next() {
-            from_id_string = part;
-        } else {
-            debug!(
-                "Could not read from_vertex at line '{}' in file '{}'. Ignoring",
-                index, edge_file
-            );
-            parsing_error_count += 1;
-            continue;
-        }
-        if from_id_string.is_empty() {
-            debug!("From id string is empty at line '{}' in file '{}'. Ignoring", index, edge_file);
-            src_empty_count += 1;
-            continue;
-        }
-        let from_id = if let Some(from_id) = vertices_map.get(from_id_string) {
-            *from_id
-        } else if load_graph_ast.only_edge_files {
-            add_new_vertex(
-                from_id_string.to_owned(),
-                Properties::default(),
-                graph,
-                vertices_map,
-                total_vertices_count,
-            )
-        } else {
-            src_id_missing_count += 1;
-            debug!(
-                "Src vertex id '{}' not found for at line '{}' in file '{}'. Adding.",
-                from_id_string, index, edge_file
-            );
-            continue;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                let line_number = e.position().map_or(0, Position::line);
+                let rejected_row = RejectedRow::new(None, e.to_string());
+                rejects.reject(edge_file, line_number, "", rejected_row)?;
+                continue;
+            }
         };
-
-        let to_id_string;
-        if let Some(part) = line_parts.next() {
-            to_id_string = part;
-        } else {
-            debug!(
-                "Could not read to_vertex at line '{}' in file '{}'. Ignoring",
-                index, edge_file
-            );
-            parsing_error_count += 1;
-            continue;
-        }
-        if to_id_string.is_empty() {
-            debug!("To id string is empty at line '{}' in file '{}'. Ignoring", index, edge_file);
-            dst_empty_count += 1;
-            continue;
-        }
-        let to_id = if let Some(to_id) = vertices_map.get(to_id_string) {
-            *to_id
-        } else if load_graph_ast.only_edge_files {
-            add_new_vertex(
-                to_id_string.to_owned(),
-                Properties::default(),
-                graph,
-                vertices_map,
-                total_vertices_count,
-            )
-        } else {
-            dst_id_missing_count += 1;
-            debug!(
-                "Dst vertex id '{}' not found for at line '{}' in file '{}'. Skipping.",
-                to_id_string, index, edge_file
-            );
-            continue;
+        let (from_id_string, to_id_string, properties) = match parse_edge(
+            &line,
+            vertices_map,
+            &property_types,
+            load_graph_ast.only_edge_files,
+        ) {
+            Ok(edge) => edge,
+            Err(rejected_row) => {
+                let line_number = line.position().map_or(0, Position::line);
+                rejects.reject(edge_file, line_number, &line.iter().join(","), rejected_row)?;
+                continue;
+            }
         };
 
-        let properties = if let Some(property_types) = &property_types {
-            load_property_values(line_parts, from_id_string, edge_file, &property_types)?
-        } else {
-            Properties::default()
+        let mut get_vertex_id = |id_string: &str| {
+            if let Some(vertex_id) = vertices_map.get(id_string) {
+                *vertex_id
+            } else {
+                add_new_vertex(
+                    id_string.to_owned(),
+                    Properties::default(),
+                    graph,
+                    vertices_map,
+                    total_vertices_count,
+                )
+            }
         };
+        let from_id = get_vertex_id(from_id_string);
+        let to_id = get_vertex_id(to_id_string);
 
-        graph.append_edge(Edge::new(properties, from_id, to_id));
+        graph.append_edge(Edge::new(from_id, to_id), properties);
         *total_edges_count += 1;
     }
-    if src_empty_count > 0_i32 {
-        warn!("Skipped {} lines with empty src ids", src_empty_count);
-    }
-    if parsing_error_count > 0_i32 {
-        warn!("Skipped {} lines which failed to parse", parsing_error_count);
-    }
-    if dst_empty_count > 0_i32 {
-        warn!("Skipped {} lines with empty dst ids", dst_empty_count);
-    }
-    if src_id_missing_count > 0_i32 {
-        warn!("Skipped {} lines with unmapped src ids to existing vertices", src_id_missing_count);
-    }
-    if dst_id_missing_count > 0_i32 {
-        warn!("Skipped {} lines with unmapped dst ids to existing vertices", dst_id_missing_count);
-    }
     Ok(())
 }
 
+/// Returns the src and dst id strings and the properties of the edge. Vertex ids that are not
+/// mapped are only allowed if the graph is loaded from edge files only.
+fn parse_edge<'a>(
+    line: &'a StringRecord,
+    vertices_map: &HashMap<String, VertexId>,
+    property_types: &Option<PropertyColumns>,
+    only_edge_files: bool,
+) -> Result<(&'a str, &'a str, Properties), RejectedRow> {
+    let mut line_parts = line.iter();
+
+    let mut parse_id = |column, name| {
+        let id_string = line_parts.next().unwrap_or("");
+        if id_string.is_empty() {
+            return Err(RejectedRow::dangling_edge(
+                Some(column),
+                format!("{} vertex id is empty", name),
+            ));
+        }
+        if !only_edge_files && !vertices_map.contains_key(id_string) {
+            return Err(RejectedRow::dangling_edge(
+                Some(column),
+                format!("{} vertex id '{}' not found", name, id_string),
+            ));
+        }
+        Ok(id_string)
+    };
+    let from_id_string = parse_id(1, "Src")?;
+    let to_id_string = parse_id(2, "Dst")?;
+
+    let properties = if let Some(property_types) = property_types {
+        load_property_values(line_parts, 3, property_types)?
+    } else {
+        Properties::default()
+    };
+    Ok((from_id_string, to_id_string, properties))
+}
+
 fn load_schema<'a, 'b>(
     schema_parts: impl Iterator<Item = &'a str>,
     file_path: &str,
     key_store: &mut KeyStore,
     mappings: &'b ClosureMappings,
-) -> Result<Vec<(PropertyKeyId, PropertyValueClosureIndex<'b>)>, GSError> {
+) -> Result<PropertyColumns<'b>, GSError> {
     let mut property_types = Vec::new();
     for schema in schema_parts {
         let mut parts = schema.split(':');
@@ -370,48 +555,119 @@ fn load_schema<'a, 'b>(
                 file_path
             )));
         }
-        let closure_index = *mappings.get(&column_type_string.to_lowercase()).ok_or_else(|| {
-            GSError::LoadGraph(format!(
-                "Unrecognized column type '{}' in file '{}'",
-                column_type_string, file_path,
-            ))
-        })?;
-        property_types.push((key_store.get_key_id_or_insert(column_name), closure_index));
+        let (type_name, closure_index) =
+            mappings.get_key_value(&column_type_string.to_lowercase()).ok_or_else(|| {
+                GSError::LoadGraph(format!(
+                    "Unrecognized column type '{}' in file '{}'",
+                    column_type_string, file_path,
+                ))
+            })?;
+        property_types.push((
+            key_store.get_key_id_or_insert(column_name),
+            type_name.as_str(),
+            *closure_index,
+        ));
     }
     Ok(property_types)
 }
 
+/// Parses the property values of a line, where `first_column` is the 1-based column of the first
+/// property value. Empty fields are missing values, and values of the types that can be empty are
+/// unescaped with `unescape_empty_value`.
 fn load_property_values<'a>(
     line_parts: impl Iterator<Item = &'a str>,
-    vertex_id_string: &str,
-    file_path: &str,
-    property_types: &[(PropertyKeyId, PropertyValueClosureIndex)],
-) -> Result<Properties, GSError> {
+    first_column: usize,
+    property_types: &[(PropertyKeyId, &str, PropertyValueClosureIndex)],
+) -> Result<Properties, RejectedRow> {
     let mut properties = Properties::default();
     let mut count = 0;
     for (index, property_string) in line_parts.enumerate() {
         count += 1;
+        let column = first_column + index;
         if property_string.is_empty() {
             continue;
         }
-        let (property_key_id, property_closure) = property_types.get(index).ok_or_else(|| {
-            GSError::LoadGraph(format!(
-                "No. of columns for vertex id '{}' does not match header in file '{}'",
-                vertex_id_string, file_path,
-            ))
+        let (property_key_id, type_name, property_closure) =
+            property_types.get(index).ok_or_else(|| {
+                RejectedRow::new(Some(column), "No. of columns does not match header".to_owned())
+            })?;
+        let property_string = if holds_empty_values(type_name) {
+            unescape_empty_value(property_string)
+        } else {
+            property_string
+        };
+        let property_value = property_closure(property_string).ok_or_else(|| {
+            RejectedRow::new(
+                Some(column),
+                format!("Could not parse property value '{}' as {}", property_string, type_name),
+            )
         })?;
-        let property_value = property_closure(property_string, vertex_id_string, file_path)?;
         properties.add_new_property(*property_key_id, property_value);
     }
     if property_types.len() != count {
-        return Err(GSError::LoadGraph(format!(
-            "Total number of columns for vertex id '{}' does not match header in file '{}'",
-            vertex_id_string, file_path,
-        )));
+        return Err(RejectedRow::new(
+            None,
+            "Total number of columns does not match header".to_owned(),
+        ));
     }
     Ok(properties)
 }
 
+/// Splits a list value at the separator. Elements that hold the separator or a quote, or are
+/// empty, are quoted as in RFC 4180, with their quotes doubled. Returns `None` if a quoted element
+/// is not terminated or is followed by anything but the separator.
+fn split_list(value: &str, separator: char) -> Option<Vec<String>> {
+    let mut elements = Vec::new();
+    if value.is_empty() {
+        return Some(elements);
+    }
+    let mut chars = value.chars().peekable();
+    loop {
+        let mut element = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next()? {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        element.push('"');
+                    }
+                    '"' => break,
+                    c => element.push(c),
+                }
+            }
+            if chars.peek().map_or(false, |&c| c != separator) {
+                return None;
+            }
+        } else {
+            while let Some(c) = chars.peek().filter(|&&c| c != separator) {
+                element.push(*c);
+                chars.next();
+            }
+        }
+        elements.push(element);
+        if chars.next().is_none() {
+            return Some(elements);
+        }
+    }
+}
+
+/// Delimited files cannot tell an empty value from a missing one, which is an empty field. So
+/// values of the types that can be empty have two quotes added when they only hold quotes,
+/// including when they are empty, and `""` is read as an empty value.
+fn unescape_empty_value(value: &str) -> &str {
+    if value.len() >= 2 && value.bytes().all(|byte| byte == b'"') {
+        &value[2..]
+    } else {
+        value
+    }
+}
+
+/// Whether the values of the type can be empty, as opposed to missing.
+pub(super) fn holds_empty_values(type_name: &str) -> bool {
+    matches!(type_name, "string" | "strings" | "ints")
+}
+
 fn get_csv_reader(
     file_path: &str,
     load_graph_ast: &LoadGraphAst,
@@ -419,7 +675,427 @@ fn get_csv_reader(
     Ok(csv::ReaderBuilder::new()
         .has_headers(load_graph_ast.has_headers)
         .delimiter(load_graph_ast.separator.unwrap_or(DEFAULT_SEPARATOR))
-        .double_quote(false)
         .comment(load_graph_ast.comment_char)
         .from_reader(get_buf_reader(file_path)?))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{split_list, unescape_empty_value};
+    use crate::global_store::GlobalStore;
+    use crate::graph::properties::property_value::{Float, PropertyValue};
+    use crate::process_query;
+    use crate::util::io::get_file_lines;
+    use gs_analytics_api::{EdgeId, VertexId};
+    use itertools::Itertools;
+
+    const LOAD_QUERY: &str = "load graph with vertices from 'data/small_malformed/vertices.txt' \
+                              and edges from 'data/small_malformed/edges.txt' comment '#'";
+
+    #[test]
+    fn test_load_graph_strict() {
+        // Malformed values fail the load unless it is lenient.
+        for mode in &[" strict", ""] {
+            let mut global_store = GlobalStore::default();
+            let mut query = format!("{}{};", LOAD_QUERY, mode);
+            let error = process_query(&mut global_store, &mut query).expect_err("Graph loaded");
+            assert_eq!(
+                error.to_string(),
+                "[LoadGraphError] Could not parse property value 'thirty' as int at line 4, \
+                 column 2 in file 'data/small_malformed/vertices.txt'"
+            );
+        }
+
+        let mut global_store = GlobalStore::default();
+        let mut query = format!("{} strict rejects to '/tmp';", LOAD_QUERY);
+        let error = process_query(&mut global_store, &mut query).expect_err("Graph loaded");
+        assert!(error.to_string().contains("Rejected rows cannot be saved in strict mode"));
+    }
+
+    #[test]
+    fn test_load_graph_dangling_edges() {
+        // All the edges refer to vertices missing from the vertex file.
+        let query = "load graph with vertices from 'data/small_properties/vertices.txt' \
+                     and edges from 'data/small_properties_append/edges.txt' comment '#'";
+        let mut global_store = GlobalStore::default();
+        let result =
+            process_query(&mut global_store, &mut format!("{};", query)).expect("Graph not loaded");
+        assert_eq!(result, "9 vertices and 0 edges loaded, 0 vertex rows and 3 edge rows rejected");
+
+        let error = process_query(&mut global_store, &mut format!("{} strict;", query))
+            .expect_err("Graph loaded");
+        assert_eq!(
+            error.to_string(),
+            "[LoadGraphError] Dst vertex id '9' not found at line 3, column 2 in file \
+             'data/small_properties_append/edges.txt'"
+        );
+    }
+
+    #[test]
+    fn test_load_graph_lenient_rejects() {
+        let rejects_dir = std::env::temp_dir().join("graphsurge_load_graph_rejects");
+        std::fs::create_dir_all(&rejects_dir).expect("Rejects dir not created");
+        let rejects_dir = rejects_dir.to_str().expect("Invalid path");
+
+        let mut global_store = GlobalStore::default();
+        let mut query = format!("{} lenient rejects to '{}';", LOAD_QUERY, rejects_dir);
+        let result = process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        assert_eq!(result, "3 vertices and 2 edges loaded, 2 vertex rows and 5 edge rows rejected");
+
+        let rejects = get_file_lines(&format!("{}/rejects.txt", rejects_dir))
+            .expect("Rejects not written")
+            .map(|line| line.split('\t').skip(1).take(3).join("|"))
+            .collect_vec();
+        assert_eq!(
+            rejects,
+            vec![
+                "4|2|Could not parse property value 'thirty' as int",
+                "6|1|Duplicate vertex id '2'",
+                "4|2|Dst vertex id '1' not found",
+                "5|3|Could not parse property value 'heavy' as float",
+                "6|1|Src vertex id is empty",
+                "7|2|Dst vertex id '9' not found",
+                "8||CSV error: record 6 (line: 8, byte: 114): found record with 2 fields, but \
+                 the previous record has 3 fields",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_graph() {
+        let mut global_store = GlobalStore::default();
+        let mut query = "load graph with vertices from 'data/small_properties/vertices.txt' \
+                         and edges from 'data/small_properties/edges.txt' comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        let mut query = "create view collection before where [year <= 2010];".to_owned();
+        process_query(&mut global_store, &mut query).expect("Collection not created");
+        let amount_key_id = global_store.key_store.get_key_id("amount");
+
+        let mut query =
+            "append graph with vertices from 'data/small_properties_append/vertices.txt' \
+                         and edges from 'data/small_properties_append/edges.txt' comment '#';"
+                .to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Graph not appended");
+        assert_eq!(
+            result,
+            "2 vertices and 3 edges appended, 0 vertex rows and 0 edge rows rejected"
+        );
+
+        assert_eq!(global_store.graph.vertex_count(), 11);
+        assert_eq!(global_store.graph.edges_count(), 10);
+        assert_eq!(global_store.key_store.get_key_id("amount"), amount_key_id);
+        assert!(global_store.key_store.get_key_id("verified").is_some());
+        assert!(global_store.filtered_cube_store.cubes.contains_key("before"));
+
+        // Edges of the appended file refer to both existing and new vertices.
+        let vertex_ids = &global_store.vertex_ids;
+        let appended_edges = global_store
+            .graph
+            .edge_iterator()
+            .skip(7)
+            .map(|(_, edge)| (edge.src_vertex_id, edge.dst_vertex_id))
+            .collect_vec();
+        assert_eq!(
+            appended_edges,
+            vec![
+                (vertex_ids["0"], vertex_ids["9"]),
+                (vertex_ids["9"], vertex_ids["10"]),
+                (vertex_ids["10"], vertex_ids["6"]),
+            ]
+        );
+        assert_eq!(vertex_ids["0"], 0);
+        assert_eq!(vertex_ids["9"], 9);
+
+        let mut query =
+            "create view collection after where [year > 2018 and u.city = 'ottawa'];".to_owned();
+        process_query(&mut global_store, &mut query).expect("Collection not created");
+    }
+
+    #[test]
+    fn test_append_graph_strict_rolls_back() {
+        let mut global_store = GlobalStore::default();
+        let mut query = "load graph with vertices from 'data/small_properties/vertices.txt' \
+                         and edges from 'data/small_properties/edges.txt' comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        let mut query = "create index on vertices(city);".to_owned();
+        process_query(&mut global_store, &mut query).expect("Index not created");
+        let index_len = global_store.index_store.indexes()[0].len();
+
+        // The vertices and the first two edges are appended before the third edge fails.
+        let mut query =
+            "append graph with vertices from 'data/small_properties_append/vertices.txt' \
+                         and edges from 'data/small_malformed/edges.txt' comment '#' strict;"
+                .to_owned();
+        process_query(&mut global_store, &mut query).expect_err("Graph appended");
+        assert_eq!(global_store.graph.vertex_count(), 9);
+        assert_eq!(global_store.graph.edges_count(), 7);
+        assert_eq!(global_store.vertex_ids.len(), 9);
+        assert!(!global_store.vertex_ids.contains_key("9"));
+        let weight_key_id = global_store.key_store.get_key_id("weight");
+        assert!((0..7).all(|edge_id| {
+            global_store
+                .graph
+                .edge_properties(edge_id)
+                .into_entries()
+                .into_iter()
+                .all(|(key_id, _)| Some(key_id) != weight_key_id)
+        }));
+        assert_eq!(global_store.index_store.indexes()[0].len(), index_len);
+
+        let mut query =
+            "append graph with vertices from 'data/small_properties_append/vertices.txt' \
+                         and edges from 'data/small_properties_append/edges.txt' comment '#';"
+                .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not appended");
+        assert_eq!(global_store.vertex_ids["9"], 9);
+        assert_eq!(global_store.index_store.indexes()[0].len(), index_len + 2);
+    }
+
+    #[test]
+    fn test_load_graphml() {
+        let mut global_store = GlobalStore::default();
+        let mut query = "load graph from 'data/small_graphml/graph.graphml' format graphml \
+                         lenient;"
+            .to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        assert_eq!(result, "3 vertices and 2 edges loaded, 1 vertex rows and 2 edge rows rejected");
+
+        assert_eq!(
+            get_named_vertices(&global_store),
+            vec![
+                vec![
+                    ("name".to_owned(), PropertyValue::String("A & B".to_owned())),
+                    ("color".to_owned(), PropertyValue::String("yellow".to_owned())),
+                ],
+                vec![("color".to_owned(), PropertyValue::String("green".to_owned()))],
+                vec![
+                    ("name".to_owned(), PropertyValue::String("<c>".to_owned())),
+                    ("color".to_owned(), PropertyValue::String("blue".to_owned())),
+                ],
+            ]
+        );
+        let edges = global_store
+            .graph
+            .edge_iterator()
+            .map(|(edge_id, edge)| {
+                (
+                    edge.src_vertex_id,
+                    edge.dst_vertex_id,
+                    global_store
+                        .graph
+                        .edge_properties(edge_id as EdgeId)
+                        .iter()
+                        .map(|(key_id, value)| {
+                            (global_store.key_store.key_string(*key_id).clone(), value.clone())
+                        })
+                        .collect_vec(),
+                )
+            })
+            .collect_vec();
+        assert_eq!(
+            edges,
+            vec![(0, 1, vec![("weight".to_owned(), PropertyValue::Isize(3))]), (2, 0, vec![]),]
+        );
+    }
+
+    fn get_rejects(rejects_dir: &str) -> Vec<String> {
+        get_file_lines(&format!("{}/rejects.txt", rejects_dir))
+            .expect("Rejects not written")
+            .map(|line| line.split('\t').skip(1).take(3).join("|"))
+            .collect_vec()
+    }
+
+    fn get_named_vertices(global_store: &GlobalStore) -> Vec<Vec<(String, PropertyValue)>> {
+        (0..global_store.graph.vertex_count() as VertexId)
+            .map(|vertex_id| {
+                global_store
+                    .graph
+                    .vertex_properties(vertex_id)
+                    .iter()
+                    .map(|(key_id, value)| {
+                        (global_store.key_store.key_string(*key_id).clone(), value.clone())
+                    })
+                    .collect_vec()
+            })
+            .collect_vec()
+    }
+
+    #[test]
+    fn test_load_graph_quoted_fields() {
+        let mut global_store = GlobalStore::default();
+        let mut query =
+            "load graph with vertices from 'data/small_quoted_properties/vertices.txt' \
+                         and edges from 'data/small_quoted_properties/edges.txt' comment '#';"
+                .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not loaded");
+
+        let strings = |values: &[&str]| {
+            PropertyValue::Strings(values.iter().map(|&value| value.to_owned()).collect())
+        };
+        assert_eq!(
+            get_named_vertices(&global_store),
+            vec![
+                vec![
+                    ("name".to_owned(), PropertyValue::String("say \"hi\"".to_owned())),
+                    ("aliases".to_owned(), strings(&["a", "b", "c;d"])),
+                ],
+                vec![
+                    ("name".to_owned(), PropertyValue::String("two\nlines".to_owned())),
+                    ("aliases".to_owned(), strings(&["", "x"])),
+                ],
+                vec![
+                    ("name".to_owned(), PropertyValue::String(String::new())),
+                    ("aliases".to_owned(), strings(&[])),
+                ],
+                vec![("name".to_owned(), PropertyValue::String("plain".to_owned()))],
+            ]
+        );
+        let labels = (0..global_store.graph.edges_count() as EdgeId)
+            .map(|edge_id| {
+                global_store
+                    .graph
+                    .edge_properties(edge_id)
+                    .iter()
+                    .next()
+                    .map(|(_, value)| value.clone())
+            })
+            .collect_vec();
+        assert_eq!(
+            labels,
+            vec![
+                Some(PropertyValue::String("a,b".to_owned())),
+                Some(PropertyValue::String(String::new())),
+                None,
+                Some(PropertyValue::String("\"".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_list() {
+        let split = |value| split_list(value, ';');
+        assert_eq!(split(""), Some(Vec::new()));
+        assert_eq!(split("a;b"), Some(vec!["a".to_owned(), "b".to_owned()]));
+        assert_eq!(split("a;"), Some(vec!["a".to_owned(), String::new()]));
+        assert_eq!(
+            split(r#""";"a;b";"say ""hi""""#),
+            Some(vec![String::new(), "a;b".to_owned(), "say \"hi\"".to_owned()])
+        );
+        assert_eq!(split(r#""unterminated"#), None);
+        assert_eq!(split(r#""a"b"#), None);
+    }
+
+    #[test]
+    fn test_unescape_empty_value() {
+        assert_eq!(unescape_empty_value(""), "");
+        assert_eq!(unescape_empty_value("\""), "\"");
+        assert_eq!(unescape_empty_value("\"\""), "");
+        assert_eq!(unescape_empty_value("\"\"\"\""), "\"\"");
+        assert_eq!(unescape_empty_value("\"\";x"), "\"\";x");
+    }
+
+    #[test]
+    fn test_load_graphml_text() {
+        let rejects_dir = std::env::temp_dir().join("graphsurge_load_graphml_text_rejects");
+        std::fs::create_dir_all(&rejects_dir).expect("Rejects dir not created");
+        let rejects_dir = rejects_dir.to_str().expect("Invalid path");
+
+        let mut global_store = GlobalStore::default();
+        let mut query = format!(
+            "load graph from 'data/small_graphml_text/graph.graphml' format graphml \
+             lenient rejects to '{}';",
+            rejects_dir
+        );
+        let result = process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        assert_eq!(result, "2 vertices and 1 edges loaded, 1 vertex rows and 0 edge rows rejected");
+
+        // Character references, entities and non-ASCII text, including in attributes.
+        assert_eq!(global_store.vertex_ids["ñé"], 0);
+        assert_eq!(
+            get_named_vertices(&global_store),
+            vec![
+                vec![("name".to_owned(), PropertyValue::String("中<文> \"q\"".to_owned()))],
+                vec![("name".to_owned(), PropertyValue::String("multi\nline".to_owned()))],
+            ]
+        );
+        // Elements are reported at the line their start tag ends on.
+        assert_eq!(get_rejects(rejects_dir), vec!["13||Key 'd9' is not declared for nodes"]);
+
+        let mut query =
+            "load graph from 'data/small_graphml_malformed/graph.graphml' format graphml;"
+                .to_owned();
+        let error = process_query(&mut global_store, &mut query).expect_err("Graph loaded");
+        assert!(
+            error.to_string().contains(
+                "Could not parse file 'data/small_graphml_malformed/graph.graphml' at line 6"
+            ),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_load_jsonl() {
+        let rejects_dir = std::env::temp_dir().join("graphsurge_load_jsonl_rejects");
+        std::fs::create_dir_all(&rejects_dir).expect("Rejects dir not created");
+        let rejects_dir = rejects_dir.to_str().expect("Invalid path");
+
+        let mut global_store = GlobalStore::default();
+        let mut query = format!(
+            "load graph with vertices from 'data/small_jsonl/vertices.jsonl' \
+             and edges from 'data/small_jsonl/edges.jsonl' format jsonl comment '#' \
+             lenient rejects to '{}';",
+            rejects_dir
+        );
+        let result = process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        assert_eq!(result, "3 vertices and 1 edges loaded, 5 vertex rows and 3 edge rows rejected");
+
+        assert_eq!(
+            get_named_vertices(&global_store),
+            vec![
+                vec![
+                    (
+                        "name".to_owned(),
+                        PropertyValue::String("Ä \"quoted\" \\ back\ttab".to_owned())
+                    ),
+                    (
+                        "tags".to_owned(),
+                        PropertyValue::Strings(vec!["x".to_owned(), "y".to_owned()])
+                    ),
+                    (
+                        "born".to_owned(),
+                        PropertyValue::parse_datetime("2015-06-30T10:15:00.000")
+                            .expect("Invalid datetime")
+                    ),
+                ],
+                vec![
+                    ("name".to_owned(), PropertyValue::String("été 😀".to_owned())),
+                    ("scores".to_owned(), PropertyValue::IntList(vec![1, -2, 3])),
+                    ("weight".to_owned(), PropertyValue::Float(Float(1.5))),
+                ],
+                vec![("empty".to_owned(), PropertyValue::Strings(Vec::new()))],
+            ]
+        );
+        assert_eq!(global_store.vertex_ids["2"], 1);
+
+        let rejects = get_rejects(rejects_dir);
+        assert_eq!(
+            rejects
+                .iter()
+                .map(|reject| reject.split(" at line").next().unwrap_or(""))
+                .collect_vec(),
+            vec![
+                "4||Nested objects are not supported for property 'nested'",
+                "5||Property name 'source' is reserved for ids",
+                "6||JSON error: EOF while parsing a string",
+                "7||Line is not a JSON object",
+                "8||Lists should have only strings or only integers for property 'mixed'",
+                "3||Property name 'id' is reserved for ids",
+                "4||Dst vertex id 'z' not found",
+                "5||Src vertex id should be a string or an integer",
+            ]
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::properties::{Properties, PropertyKeyId};
+use crate::graph::Edge;
+use crate::query_handler::load_graph::executor::{
+    add_new_vertex, holds_empty_values, ClosureMappings, PropertyValueClosureIndex, RejectedRow,
+    Rejects,
+};
+use crate::util::io::{get_buf_reader, LineCountingReader};
+use gs_analytics_api::VertexId;
+use hashbrown::HashMap;
+use itertools::Itertools;
+use log::warn;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::fs::File;
+use std::io::BufReader;
+
+/// Extension attribute of `<key>` elements holding the type of properties that GraphML has no
+/// type for, e.g., `datetime` or `strings`. It takes precedence over `attr.type`.
+pub const GS_TYPE_ATTRIBUTE: &str = "gs.type";
+
+type XmlAttributes = Vec<(String, String)>;
+
+enum XmlEvent {
+    Start(String, XmlAttributes),
+    End(String),
+    Text(String),
+}
+
+/// Streams the elements and the text of a GraphML file. Self-closing elements are returned as a
+/// start event followed by an end event.
+struct GraphMlReader {
+    reader: Reader<LineCountingReader<BufReader<File>>>,
+    buffer: Vec<u8>,
+    /// The name of a self-closing element, whose end event has not been returned yet.
+    pending_end: Option<String>,
+}
+
+impl GraphMlReader {
+    fn new(file_path: &str) -> Result<Self, GSError> {
+        Ok(Self {
+            reader: Reader::from_reader(LineCountingReader::new(get_buf_reader(file_path)?)),
+            buffer: Vec::new(),
+            pending_end: None,
+        })
+    }
+
+    /// The 1-based line the reader has reached, i.e., the line at which the last returned start
+    /// tag ends.
+    fn line(&self) -> u64 {
+        self.reader.get_ref().line()
+    }
+
+    fn next_event(&mut self) -> Result<Option<XmlEvent>, String> {
+        if let Some(name) = self.pending_end.take() {
+            return Ok(Some(XmlEvent::End(name)));
+        }
+        loop {
+            self.buffer.clear();
+            let event = match self.reader.read_event_into(&mut self.buffer) {
+                Ok(Event::Start(start)) => {
+                    XmlEvent::Start(get_name(&start)?, get_attributes(&start)?)
+                }
+                Ok(Event::Empty(start)) => {
+                    let name = get_name(&start)?;
+                    self.pending_end = Some(name.clone());
+                    XmlEvent::Start(name, get_attributes(&start)?)
+                }
+                Ok(Event::End(end)) => XmlEvent::End(
+                    String::from_utf8(end.name().as_ref().to_vec()).map_err(|e| e.to_string())?,
+                ),
+                Ok(Event::Text(text)) => {
+                    XmlEvent::Text(text.unescape().map_err(|e| e.to_string())?.into_owned())
+                }
+                Ok(Event::CData(text)) => XmlEvent::Text(
+                    String::from_utf8(text.into_inner().into_owned()).map_err(|e| e.to_string())?,
+                ),
+                Ok(Event::Eof) => return Ok(None),
+                Ok(_) => continue,
+                Err(e) => return Err(e.to_string()),
+            };
+            return Ok(Some(event));
+        }
+    }
+}
+
+fn get_name(start: &BytesStart) -> Result<String, String> {
+    String::from_utf8(start.name().as_ref().to_vec()).map_err(|e| e.to_string())
+}
+
+fn get_attributes(start: &BytesStart) -> Result<XmlAttributes, String> {
+    start
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.map_err(|e| e.to_string())?;
+            Ok((
+                String::from_utf8(attribute.key.as_ref().to_vec()).map_err(|e| e.to_string())?,
+                attribute.unescape_value().map_err(|e| e.to_string())?.into_owned(),
+            ))
+        })
+        .collect()
+}
+
+fn get_attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+/// A `<key>` declaration, which maps `<data>` elements to a property.
+struct GraphMlKey<'a> {
+    /// One of `node`, `edge` or `all`.
+    domain: String,
+    name: String,
+    key_id: PropertyKeyId,
+    type_name: &'a str,
+    closure: PropertyValueClosureIndex<'a>,
+    default: Option<String>,
+}
+
+/// A `<node>` or `<edge>` element, along with the key ids and the contents of its `<data>`
+/// children.
+struct GraphMlElement {
+    attributes: XmlAttributes,
+    line: u64,
+    data: Vec<(String, String)>,
+}
+
+impl GraphMlElement {
+    fn row(&self) -> String {
+        self.attributes.iter().map(|(name, value)| format!("{}={}", name, value)).join(",")
+    }
+}
+
+/// Loads the vertices and edges of a GraphML file and returns the number of rejected vertices
+/// and edges. Edges are loaded after all the vertices, so they can refer to later vertices.
+pub(super) fn load_graphml(
+    file_path: &str,
+    vertices_map: &mut HashMap<String, VertexId>,
+    global_store: &mut GlobalStore,
+    total_vertices_count: &mut usize,
+    total_edges_count: &mut usize,
+    mappings: &ClosureMappings,
+    rejects: &mut Rejects,
+) -> Result<(usize, usize), GSError> {
+    let mut reader = GraphMlReader::new(file_path)?;
+
+    let mut keys: HashMap<String, GraphMlKey> = HashMap::new();
+    // Keys in the order they are declared in, for adding default values.
+    let mut key_order = Vec::new();
+    let mut current_key = None;
+    let mut current_element: Option<(bool, GraphMlElement)> = None;
+    let mut current_data_key = None;
+    let mut text: Option<String> = None;
+    let mut edges = Vec::new();
+    let mut rejected_vertices_count = 0;
+
+    while let Some(event) = reader.next_event().map_err(|e| {
+        GSError::LoadGraph(format!(
+            "Could not parse file '{}' at line {}: {}",
+            file_path,
+            reader.line(),
+            e
+        ))
+    })? {
+        match event {
+            XmlEvent::Start(name, attributes) => match name.as_str() {
+                "key" => {
+                    let key = parse_key(&attributes, file_path, global_store, mappings)?;
+                    let id = get_attribute(&attributes, "id").unwrap_or("").to_owned();
+                    key_order.push(id.clone());
+                    keys.insert(id.clone(), key);
+                    current_key = Some(id);
+                }
+                "default" => text = Some(String::new()),
+                "graph" if get_attribute(&attributes, "edgedefault") == Some("undirected") => {
+                    warn!("Undirected edges in file '{}' are loaded as directed", file_path);
+                }
+                "node" | "edge" => {
+                    let line = reader.line();
+                    current_element = Some((
+                        name == "node",
+                        GraphMlElement { attributes, line, data: Vec::new() },
+                    ));
+                }
+                "data" => {
+                    current_data_key = get_attribute(&attributes, "key").map(ToOwned::to_owned);
+                    text = Some(String::new());
+                }
+                _ => {}
+            },
+            XmlEvent::Text(value) => {
+                if let Some(text) = &mut text {
+                    text.push_str(&value);
+                }
+            }
+            XmlEvent::End(name) => match name.as_str() {
+                "key" => current_key = None,
+                "default" => {
+                    if let Some(key) = current_key.as_ref().and_then(|id| keys.get_mut(id)) {
+                        key.default = text.take();
+                    }
+                }
+                "data" => {
+                    let value = text.take().unwrap_or_default();
+                    if let (Some((_, element)), Some(key)) =
+                        (&mut current_element, current_data_key.take())
+                    {
+                        element.data.push((key, value));
+                    }
+                }
+                "node" | "edge" => {
+                    if let Some((is_node, element)) = current_element.take() {
+                        if !is_node {
+                            edges.push(element);
+                            continue;
+                        }
+                        let result = get_vertex(&element, &keys, &key_order, vertices_map).map(
+                            |(vertex_id_string, properties)| {
+                                add_new_vertex(
+                                    vertex_id_string,
+                                    properties,
+                                    &mut global_store.graph,
+                                    vertices_map,
+                                    total_vertices_count,
+                                );
+                            },
+                        );
+                        if let Err(rejected_row) = result {
+                            rejects.reject(
+                                file_path,
+                                element.line,
+                                &element.row(),
+                                rejected_row,
+                            )?;
+                            rejected_vertices_count += 1;
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let mut rejected_edges_count = 0;
+    for element in edges {
+        let get_vertex_id = |attribute, name| {
+            let id_string = get_attribute(&element.attributes, attribute).unwrap_or("");
+            vertices_map.get(id_string).copied().ok_or_else(|| {
+                RejectedRow::dangling_edge(
+                    None,
+                    format!("{} vertex id '{}' not found", name, id_string),
+                )
+            })
+        };
+        let result = get_vertex_id("source", "Src").and_then(|src_vertex_id| {
+            let dst_vertex_id = get_vertex_id("target", "Dst")?;
+            let properties = get_properties(&element, "edge", &keys, &key_order)?;
+            Ok((Edge::new(src_vertex_id, dst_vertex_id), properties))
+        });
+        match result {
+            Ok((edge, properties)) => {
+                global_store.graph.append_edge(edge, properties);
+                *total_edges_count += 1;
+            }
+            Err(rejected_row) => {
+                rejects.reject(file_path, element.line, &element.row(), rejected_row)?;
+                rejected_edges_count += 1;
+            }
+        }
+    }
+    rejects.take_count(file_path);
+
+    Ok((rejected_vertices_count, rejected_edges_count))
+}
+
+fn parse_key<'a>(
+    attributes: &[(String, String)],
+    file_path: &str,
+    global_store: &mut GlobalStore,
+    mappings: &'a ClosureMappings,
+) -> Result<GraphMlKey<'a>, GSError> {
+    let id = get_attribute(attributes, "id").unwrap_or("");
+    if id.is_empty() {
+        return Err(GSError::LoadGraph(format!("Key without id in file '{}'", file_path)));
+    }
+    let name = get_attribute(attributes, "attr.name").unwrap_or(id).to_owned();
+    let type_string = match get_attribute(attributes, GS_TYPE_ATTRIBUTE) {
+        Some(type_string) => type_string.to_lowercase(),
+        None => match get_attribute(attributes, "attr.type").unwrap_or("string") {
+            "boolean" => "bool".to_owned(),
+            "int" | "long" => "int".to_owned(),
+            "float" | "double" => "float".to_owned(),
+            type_string => type_string.to_lowercase(),
+        },
+    };
+    let (type_name, closure) = mappings.get_key_value(&type_string).ok_or_else(|| {
+        GSError::LoadGraph(format!(
+            "Unrecognized type '{}' of key '{}' in file '{}'",
+            type_string, id, file_path
+        ))
+    })?;
+    Ok(GraphMlKey {
+        domain: get_attribute(attributes, "for").unwrap_or("all").to_owned(),
+        key_id: global_store.key_store.get_key_id_or_insert(&name),
+        name,
+        type_name: type_name.as_str(),
+        closure: *closure,
+        default: None,
+    })
+}
+
+fn get_vertex(
+    element: &GraphMlElement,
+    keys: &HashMap<String, GraphMlKey>,
+    key_order: &[String],
+    vertices_map: &HashMap<String, VertexId>,
+) -> Result<(String, Properties), RejectedRow> {
+    let vertex_id_string = get_attribute(&element.attributes, "id").unwrap_or("");
+    if vertex_id_string.is_empty() {
+        return Err(RejectedRow::new(None, "Vertex id is empty".to_owned()));
+    }
+    if vertices_map.contains_key(vertex_id_string) {
+        return Err(RejectedRow::new(None, format!("Duplicate vertex id '{}'", vertex_id_string)));
+    }
+    let properties = get_properties(element, "node", keys, key_order)?;
+    Ok((vertex_id_string.to_owned(), properties))
+}
+
+/// Parses the `<data>` values of the element, followed by the default values of the keys that
+/// have no `<data>`. Empty values are skipped, unless the type can hold empty values.
+fn get_properties(
+    element: &GraphMlElement,
+    domain: &str,
+    keys: &HashMap<String, GraphMlKey>,
+    key_order: &[String],
+) -> Result<Properties, RejectedRow> {
+    let mut properties = Properties::default();
+    let mut add_property = |key: &GraphMlKey, value: &str| {
+        if value.is_empty() && !holds_empty_values(key.type_name) {
+            return Ok(());
+        }
+        let property_value = (key.closure)(value).ok_or_else(|| {
+            RejectedRow::new(
+                None,
+                format!(
+                    "Could not parse property value '{}' of key '{}' as {}",
+                    value, key.name, key.type_name
+                ),
+            )
+        })?;
+        properties.add_new_property(key.key_id, property_value);
+        Ok(())
+    };
+
+    for (key_id, value) in &element.data {
+        let key = keys
+            .get(key_id)
+            .filter(|key| key.domain == domain || key.domain == "all")
+            .ok_or_else(|| {
+                RejectedRow::new(None, format!("Key '{}' is not declared for {}s", key_id, domain))
+            })?;
+        add_property(key, value)?;
+    }
+    for key_id in key_order {
+        let key = &keys[key_id];
+        if let Some(default) = &key.default {
+            if (key.domain == domain || key.domain == "all")
+                && element.data.iter().all(|(data_key_id, _)| data_key_id != key_id)
+            {
+                add_property(key, default)?;
+            }
+        }
+    }
+    Ok(properties)
+}
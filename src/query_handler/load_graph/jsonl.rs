@@ -0,0 +1,236 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::key_store::KeyStore;
+use crate::graph::properties::property_value::{Float, PropertyValue};
+use crate::graph::properties::Properties;
+use crate::graph::Edge;
+use crate::query_handler::load_graph::executor::{add_new_vertex, RejectedRow, Rejects};
+use crate::query_handler::load_graph::LoadGraphAst;
+use crate::util::io::get_buf_reader;
+use gs_analytics_api::VertexId;
+use hashbrown::HashMap;
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::io::BufRead;
+
+pub const ID_MEMBER: &str = "id";
+pub const SOURCE_MEMBER: &str = "source";
+pub const TARGET_MEMBER: &str = "target";
+/// Datetimes are written as `{"$datetime": "2015-06-30T10:15:00.000"}`, as JSON has no type for
+/// them.
+pub const DATETIME_MEMBER: &str = "$datetime";
+/// Member names that hold ids, and so cannot be used as property names.
+pub const RESERVED_MEMBERS: [&str; 3] = [ID_MEMBER, SOURCE_MEMBER, TARGET_MEMBER];
+
+/// The members of an object, in the order they appear in.
+type Members = Vec<(String, Value)>;
+
+pub(super) fn load_vertices(
+    vertex_file: &str,
+    vertices_map: &mut HashMap<String, VertexId>,
+    global_store: &mut GlobalStore,
+    total_vertices_count: &mut usize,
+    rejects: &mut Rejects,
+    load_graph_ast: &LoadGraphAst,
+) -> Result<(), GSError> {
+    for_each_object(vertex_file, rejects, load_graph_ast, |mut members| {
+        let vertex_id_string = take_id(&mut members, ID_MEMBER, "Vertex")?;
+        if vertex_id_string.is_empty() {
+            return Err(RejectedRow::new(None, "Vertex id is empty".to_owned()));
+        }
+        if vertices_map.contains_key(&vertex_id_string) {
+            return Err(RejectedRow::new(
+                None,
+                format!("Duplicate vertex id '{}'", vertex_id_string),
+            ));
+        }
+        let properties = get_properties(members, &mut global_store.key_store)?;
+        add_new_vertex(
+            vertex_id_string,
+            properties,
+            &mut global_store.graph,
+            vertices_map,
+            total_vertices_count,
+        );
+        Ok(())
+    })
+}
+
+pub(super) fn load_edges(
+    edge_file: &str,
+    vertices_map: &mut HashMap<String, VertexId>,
+    global_store: &mut GlobalStore,
+    total_vertices_count: &mut usize,
+    total_edges_count: &mut usize,
+    rejects: &mut Rejects,
+    load_graph_ast: &LoadGraphAst,
+) -> Result<(), GSError> {
+    for_each_object(edge_file, rejects, load_graph_ast, |mut members| {
+        let from_id_string = take_id(&mut members, SOURCE_MEMBER, "Src vertex")?;
+        let to_id_string = take_id(&mut members, TARGET_MEMBER, "Dst vertex")?;
+        for (id_string, name) in &[(&from_id_string, "Src"), (&to_id_string, "Dst")] {
+            if id_string.is_empty() {
+                return Err(RejectedRow::dangling_edge(
+                    None,
+                    format!("{} vertex id is empty", name),
+                ));
+            }
+            if !load_graph_ast.only_edge_files && !vertices_map.contains_key(*id_string) {
+                return Err(RejectedRow::dangling_edge(
+                    None,
+                    format!("{} vertex id '{}' not found", name, id_string),
+                ));
+            }
+        }
+        let properties = get_properties(members, &mut global_store.key_store)?;
+
+        let mut get_vertex_id = |id_string: String| {
+            if let Some(vertex_id) = vertices_map.get(&id_string) {
+                *vertex_id
+            } else {
+                add_new_vertex(
+                    id_string,
+                    Properties::default(),
+                    &mut global_store.graph,
+                    vertices_map,
+                    total_vertices_count,
+                )
+            }
+        };
+        let from_id = get_vertex_id(from_id_string);
+        let to_id = get_vertex_id(to_id_string);
+
+        global_store.graph.append_edge(Edge::new(from_id, to_id), properties);
+        *total_edges_count += 1;
+        Ok(())
+    })
+}
+
+/// Calls `f` with the members of the JSON object on each line. Empty lines and lines starting
+/// with the comment char are skipped.
+fn for_each_object(
+    file_path: &str,
+    rejects: &mut Rejects,
+    load_graph_ast: &LoadGraphAst,
+    mut f: impl FnMut(Members) -> Result<(), RejectedRow>,
+) -> Result<(), GSError> {
+    for (index, line) in get_buf_reader(file_path)?.lines().enumerate() {
+        let line = line.map_err(|e| GSError::ReadFile(file_path.to_owned(), e.to_string()))?;
+        let trimmed_line = line.trim();
+        if trimmed_line.is_empty()
+            || load_graph_ast.comment_char.map_or(false, |c| trimmed_line.as_bytes()[0] == c)
+        {
+            continue;
+        }
+        let result = match serde_json::from_str(trimmed_line) {
+            Ok(Value::Object(members)) => f(members.into_iter().collect()),
+            Ok(_) => Err(RejectedRow::new(None, "Line is not a JSON object".to_owned())),
+            Err(e) => Err(RejectedRow::new(None, format!("JSON error: {}", e))),
+        };
+        if let Err(rejected_row) = result {
+            rejects.reject(file_path, index as u64 + 1, trimmed_line, rejected_row)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the id member from `members`. Ids can be strings or integers, and are empty if the
+/// member is missing.
+fn take_id(members: &mut Members, member: &str, name: &str) -> Result<String, RejectedRow> {
+    let index = members.iter().position(|(member_name, _)| member_name == member);
+    let id_string = match index.map(|index| members.remove(index).1) {
+        Some(Value::String(id_string)) => id_string,
+        Some(Value::Number(id)) if !id.is_f64() => id.to_string(),
+        Some(_) => {
+            return Err(RejectedRow::new(
+                None,
+                format!("{} id should be a string or an integer", name),
+            ));
+        }
+        None => String::new(),
+    };
+    Ok(id_string)
+}
+
+/// Null values are skipped, like empty values in delimited files. The ids must have been taken
+/// out of `members` already, so any remaining reserved member is rejected.
+fn get_properties(members: Members, key_store: &mut KeyStore) -> Result<Properties, RejectedRow> {
+    let mut properties = Properties::default();
+    for (name, value) in members {
+        if RESERVED_MEMBERS.contains(&name.as_str()) {
+            return Err(RejectedRow::new(
+                None,
+                format!("Property name '{}' is reserved for ids", name),
+            ));
+        }
+        let property_value = match get_property_value(value) {
+            Ok(Some(property_value)) => property_value,
+            Ok(None) => continue,
+            Err(reason) => {
+                return Err(RejectedRow::new(None, format!("{} for property '{}'", reason, name)));
+            }
+        };
+        properties.add_new_property(key_store.get_key_id_or_insert(&name), property_value);
+    }
+    Ok(properties)
+}
+
+/// Maps a JSON value to a property value. Arrays become `strings` or `ints` lists, with empty
+/// arrays read as empty `strings` lists.
+fn get_property_value(value: Value) -> Result<Option<PropertyValue>, String> {
+    Ok(Some(match value {
+        Value::Null => return Ok(None),
+        Value::Bool(value) => PropertyValue::Bool(value),
+        Value::Number(number) => get_number(&number)?,
+        Value::String(value) => PropertyValue::String(value),
+        Value::Array(values) => {
+            if values.iter().all(|value| value.is_number() && !value.is_f64()) && !values.is_empty()
+            {
+                PropertyValue::IntList(
+                    values
+                        .iter()
+                        .map(|value| match value {
+                            Value::Number(number) => get_int(number),
+                            _ => unreachable!("Checked to be an integer"),
+                        })
+                        .collect::<Result<_, _>>()?,
+                )
+            } else if values.iter().all(Value::is_string) {
+                PropertyValue::Strings(
+                    values
+                        .into_iter()
+                        .filter_map(|value| match value {
+                            Value::String(value) => Some(value),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            } else {
+                return Err("Lists should have only strings or only integers".to_owned());
+            }
+        }
+        Value::Object(members) => match members.into_iter().collect::<Vec<_>>().as_slice() {
+            [(member, Value::String(value))] if member == DATETIME_MEMBER => {
+                PropertyValue::parse_datetime(value)
+                    .ok_or_else(|| format!("Could not parse '{}' as datetime", value))?
+            }
+            _ => return Err("Nested objects are not supported".to_owned()),
+        },
+    }))
+}
+
+/// Numbers written with a fraction or an exponent are floats, and the others are integers.
+fn get_number(number: &serde_json::Number) -> Result<PropertyValue, String> {
+    if number.is_f64() {
+        Ok(PropertyValue::Float(Float(number.as_f64().expect("Checked to be a float"))))
+    } else {
+        get_int(number).map(PropertyValue::Isize)
+    }
+}
+
+fn get_int(number: &serde_json::Number) -> Result<isize, String> {
+    number
+        .as_i64()
+        .and_then(|value| isize::try_from(value).ok())
+        .ok_or_else(|| format!("Integer '{}' is out of range", number))
+}
@@ -1,28 +1,81 @@
 pub mod executor;
+pub mod graphml;
+pub mod jsonl;
+
+/// The file formats that graphs can be loaded from and saved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Delimited vertex and edge files with typed headers.
+    Csv,
+    /// A single GraphML file with both the vertices and the edges.
+    GraphMl,
+    /// Vertex and edge files with one JSON object per line.
+    JsonLines,
+}
+
+/// How rows that cannot be loaded are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectMode {
+    /// Skip and count edges with an empty or unknown src or dst vertex id, as earlier versions
+    /// did, and fail on any other row that cannot be loaded.
+    Default,
+    /// Fail on the first row that cannot be loaded.
+    Strict,
+    /// Skip and count every row that cannot be loaded.
+    Lenient,
+}
+
+impl std::fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                GraphFormat::Csv => "csv",
+                GraphFormat::GraphMl => "graphml",
+                GraphFormat::JsonLines => "jsonl",
+            }
+        )
+    }
+}
 
 #[derive(new)]
 pub struct LoadGraphAst {
+    /// Extend the existing graph instead of replacing it.
+    append: bool,
     only_edge_files: bool,
     vertex_file: Option<String>,
+    /// The edge file, or the file with the whole graph for GraphML.
     edge_file: String,
+    format: GraphFormat,
     separator: Option<u8>,
+    /// Separates the values inside a cell of a list-typed column.
+    list_separator: Option<char>,
     comment_char: Option<u8>,
     has_headers: bool,
+    reject_mode: RejectMode,
+    rejects_dir: Option<String>,
     save_mappings_dir: Option<String>,
     randomize: bool,
 }
 
 impl std::fmt::Display for LoadGraphAst {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let action = if self.append { "append" } else { "load" };
+        if self.format == GraphFormat::GraphMl {
+            return write!(f, "{} graph from '{}' format {}", action, self.edge_file, self.format);
+        }
         write!(
             f,
-            "load graph with {}edges from '{}'",
+            "{} graph with {}edges from '{}' format {}",
+            action,
             if let Some(vf) = &self.vertex_file {
                 format!("vertices from '{}' and ", vf)
             } else {
                 String::new()
             },
             self.edge_file,
+            self.format,
         )
     }
 }
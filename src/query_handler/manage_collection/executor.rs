@@ -0,0 +1,71 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::query_handler::manage_collection::{CollectionOperation, ManageCollectionAst};
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+
+impl GraphSurgeQuery for ManageCollectionAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let cube_store = &mut global_store.filtered_cube_store;
+        let result = match &self.operation {
+            CollectionOperation::Drop(name) => {
+                cube_store.drop_cube(name)?;
+                format!("Collection '{}' dropped", name)
+            }
+            CollectionOperation::Rename(from, to) => {
+                cube_store.rename_cube(from, to)?;
+                format!("Collection '{}' renamed to '{}'", from, to)
+            }
+            CollectionOperation::Copy(from, to) => {
+                cube_store.copy_cube(from, to)?;
+                format!("Collection '{}' copied to '{}'", from, to)
+            }
+        };
+        Ok(GraphSurgeResult::new(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+
+    #[test]
+    fn test_manage_collections() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection first where [year <= 2000],[year <= 2008] materialized;",
+            "create view collection second where [year <= 2005] materialized;",
+            "copy collection first to copied;",
+            "drop collection second;",
+            "rename collection first to renamed;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+
+        let cubes = &global_store.filtered_cube_store.cubes;
+        let mut names = cubes.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["copied".to_owned(), "renamed".to_owned()]);
+        let copied = cubes.get("copied").expect("Cube not found");
+        let renamed = cubes.get("renamed").expect("Cube not found");
+        assert!(copied.differential_data.is_none());
+        assert!(renamed.differential_data.is_some());
+        assert_eq!(copied.get_full_data_string(), renamed.get_full_data_string());
+
+        for (query, expected) in &[
+            ("drop collection second;", "'second' has not been created yet"),
+            ("rename collection first to other;", "'first' has not been created yet"),
+            ("copy collection copied to renamed;", "'renamed' already exists"),
+            ("rename collection renamed to copied;", "'copied' already exists"),
+        ] {
+            let error = process_query(&mut global_store, &mut (*query).to_owned())
+                .expect_err("Query should fail");
+            assert!(error.to_string().contains(expected), "{}", error);
+        }
+    }
+}
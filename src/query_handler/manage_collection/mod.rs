@@ -0,0 +1,24 @@
+pub mod executor;
+
+#[derive(new)]
+pub struct ManageCollectionAst {
+    operation: CollectionOperation,
+}
+
+pub enum CollectionOperation {
+    Drop(String),
+    Rename(String, String),
+    Copy(String, String),
+}
+
+impl std::fmt::Display for ManageCollectionAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match &self.operation {
+            CollectionOperation::Drop(name) => write!(f, "drop collection {}", name),
+            CollectionOperation::Rename(from, to) => {
+                write!(f, "rename collection {} to {}", from, to)
+            }
+            CollectionOperation::Copy(from, to) => write!(f, "copy collection {} to {}", from, to),
+        }
+    }
+}
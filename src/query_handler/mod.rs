@@ -1,19 +1,27 @@
 use crate::error::GSError;
 use crate::global_store::GlobalStore;
 
+pub mod alter_collection;
 pub mod create_aggregated_cube;
 pub mod create_filtered_cube;
+pub mod create_index;
 pub mod create_view;
 pub mod delete_cubes;
+pub mod describe_collection;
+pub mod explain;
 pub mod generate_cube;
 pub mod load_cube;
 pub mod load_graph;
+pub mod manage_collection;
+pub mod modify_graph;
+pub mod reorder_collection;
 pub mod run_computation;
 pub mod serde;
 pub mod set_threads;
 pub mod show_computations;
 pub mod show_cube_data;
 pub mod show_cubes;
+pub mod show_indexes;
 pub mod window_cube;
 pub mod write_cube;
 pub mod write_graph;
@@ -0,0 +1,269 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::key_store::KeyStore;
+use crate::graph::properties::Properties;
+use crate::graph::stream_data::filter::test_where_conditions;
+use crate::graph::{Edge, Graph, GraphPointer, VertexOrEdge, VertexOrEdgeId};
+use crate::query_handler::create_view::WhereConditions;
+use crate::query_handler::modify_graph::{ModifyGraphAst, PropertyAssignment};
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+use gs_analytics_api::{EdgeId, VertexId};
+use std::convert::TryFrom;
+
+impl GraphSurgeQuery for ModifyGraphAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let (result, modified) = match self {
+            ModifyGraphAst::InsertVertex(vertex, assignments) => {
+                if global_store.vertex_ids.contains_key(vertex) {
+                    return Err(GSError::ModifyGraph(format!(
+                        "Vertex '{}' already exists",
+                        vertex
+                    )));
+                }
+                let properties = get_properties(assignments, &mut global_store.key_store);
+                let vertex_id = global_store.graph.append_vertex(properties);
+                global_store.index_store.insert(
+                    VertexOrEdge::Vertex,
+                    &[vertex_id],
+                    &global_store.graph,
+                );
+                global_store.vertex_ids.insert(vertex.clone(), vertex_id);
+                (format!("Vertex '{}' inserted with id {}", vertex, vertex_id), true)
+            }
+            ModifyGraphAst::InsertEdge(src_vertex, dst_vertex, assignments) => {
+                let src_vertex_id = get_vertex_id(global_store, src_vertex)?;
+                let dst_vertex_id = get_vertex_id(global_store, dst_vertex)?;
+                let properties = get_properties(assignments, &mut global_store.key_store);
+                let edge_id = global_store
+                    .graph
+                    .append_edge(Edge::new(src_vertex_id, dst_vertex_id), properties);
+                global_store.index_store.insert(
+                    VertexOrEdge::Edge,
+                    &[edge_id],
+                    &global_store.graph,
+                );
+                (
+                    format!(
+                        "Edge '{}' to '{}' inserted with id {}",
+                        src_vertex, dst_vertex, edge_id
+                    ),
+                    true,
+                )
+            }
+            ModifyGraphAst::DeleteEdges(where_conditions) => {
+                let edge_ids =
+                    get_matching_ids(&global_store.graph, VertexOrEdge::Edge, where_conditions);
+                global_store.index_store.remove(VertexOrEdge::Edge, &edge_ids, &global_store.graph);
+                for &edge_id in &edge_ids {
+                    global_store.graph.delete_edge(edge_id);
+                }
+                (format!("{} edges deleted", edge_ids.len()), !edge_ids.is_empty())
+            }
+            ModifyGraphAst::SetProperty(vertex_or_edge, (name, value), where_conditions) => {
+                let key_id = global_store.key_store.get_key_id_or_insert(name);
+                let ids = get_matching_ids(&global_store.graph, *vertex_or_edge, where_conditions);
+                global_store.index_store.remove(*vertex_or_edge, &ids, &global_store.graph);
+                for &id in &ids {
+                    match vertex_or_edge {
+                        VertexOrEdge::Vertex => {
+                            global_store.graph.set_vertex_property(id, key_id, value.clone())
+                        }
+                        VertexOrEdge::Edge => {
+                            global_store.graph.set_edge_property(id, key_id, value.clone())
+                        }
+                    }
+                }
+                global_store.index_store.insert(*vertex_or_edge, &ids, &global_store.graph);
+                (
+                    format!(
+                        "{} {} updated",
+                        ids.len(),
+                        if *vertex_or_edge == VertexOrEdge::Vertex { "vertices" } else { "edges" }
+                    ),
+                    !ids.is_empty(),
+                )
+            }
+        };
+        if modified {
+            global_store.filtered_cube_store.mark_all_stale();
+        }
+        Ok(GraphSurgeResult::new(result))
+    }
+}
+
+fn get_vertex_id(global_store: &GlobalStore, vertex: &str) -> Result<VertexId, GSError> {
+    global_store
+        .vertex_ids
+        .get(vertex)
+        .copied()
+        .ok_or_else(|| GSError::ModifyGraph(format!("Vertex '{}' does not exist", vertex)))
+}
+
+fn get_properties(assignments: &[PropertyAssignment], key_store: &mut KeyStore) -> Properties {
+    let mut properties = Properties::default();
+    for (name, value) in assignments {
+        properties.set_property(key_store.get_key_id_or_insert(name), value.clone());
+    }
+    properties
+}
+
+/// Returns the ids of the vertices or the non-deleted edges that satisfy `where_conditions`.
+fn get_matching_ids(
+    graph: &Graph,
+    vertex_or_edge: VertexOrEdge,
+    where_conditions: &WhereConditions,
+) -> Vec<VertexOrEdgeId> {
+    let graph_pointer = GraphPointer::new(graph);
+    let count = match vertex_or_edge {
+        VertexOrEdge::Vertex => graph.vertex_count(),
+        VertexOrEdge::Edge => graph.edges_count(),
+    };
+    (0..count)
+        .map(|id| VertexOrEdgeId::try_from(id).expect("Overflow"))
+        .filter(|&id| {
+            vertex_or_edge == VertexOrEdge::Vertex || !graph.is_edge_deleted(id as EdgeId)
+        })
+        .filter(|&id| test_where_conditions(id, where_conditions, graph_pointer))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::graph::indexes::IndexStore;
+    use crate::graph::properties::operations::{Operator, RightOperand};
+    use crate::graph::properties::property_value::PropertyValue;
+    use crate::graph::stream_data::get_timely_edgeid_stream;
+    use crate::process_query;
+    use itertools::Itertools;
+
+    fn run(global_store: &mut GlobalStore, query: &str) -> String {
+        process_query(global_store, &mut query.to_owned()).expect("Query failed")
+    }
+
+    #[test]
+    fn test_modify_graph() {
+        let mut global_store = GlobalStore::default();
+        run(
+            &mut global_store,
+            "load graph with vertices from 'data/small_properties/vertices.txt' \
+             and edges from 'data/small_properties/edges.txt' comment '#';",
+        );
+        run(&mut global_store, "create view collection before where [year <= 2010];");
+        assert!(!global_store.filtered_cube_store.cubes["before"].stale);
+
+        assert_eq!(
+            run(&mut global_store, "insert vertex '9' (city = 'ottawa', include = true);"),
+            "Vertex '9' inserted with id 9"
+        );
+        assert_eq!(
+            run(&mut global_store, "insert edge '9' to '0' (amount = 50, year = 2020);"),
+            "Edge '9' to '0' inserted with id 7"
+        );
+        assert!(global_store.filtered_cube_store.cubes["before"].stale);
+        assert!(process_query(&mut global_store, &mut "insert vertex '9';".to_owned()).is_err());
+        assert!(
+            process_query(&mut global_store, &mut "insert edge '9' to '10';".to_owned()).is_err()
+        );
+
+        assert_eq!(
+            run(&mut global_store, "delete edges where amount >= 600 and year < 2010;"),
+            "2 edges deleted"
+        );
+        assert_eq!(global_store.graph.edges_count(), 8);
+        assert_eq!(global_store.graph.deleted_edges_count(), 2);
+        // Remaining edges keep their ids.
+        assert_eq!(
+            get_timely_edgeid_stream(&global_store.graph, 0, 1).collect_vec(),
+            vec![0, 1, 2, 3, 4, 7]
+        );
+        // Edges inserted after a delete are not deleted.
+        assert_eq!(
+            run(&mut global_store, "insert edge '0' to '9';"),
+            "Edge '0' to '9' inserted with id 8"
+        );
+        assert!(!global_store.graph.is_edge_deleted(8));
+        assert_eq!(global_store.graph.edge_iterator().map(|(edge_id, _)| edge_id).last(), Some(8));
+
+        assert_eq!(
+            run(&mut global_store, "set property year = 2021 on edges where u.city = 'ottawa';"),
+            "1 edges updated"
+        );
+        assert_eq!(
+            run(
+                &mut global_store,
+                "set property country = 'ca' on vertices where state = 'ontario';"
+            ),
+            "6 vertices updated"
+        );
+        run(&mut global_store, "create view collection after where [year > 2020];");
+        assert!(!global_store.filtered_cube_store.cubes["after"].stale);
+    }
+
+    #[test]
+    fn test_modify_graph_updates_indexes() {
+        let mut global_store = GlobalStore::default();
+        run(
+            &mut global_store,
+            "load graph with vertices from 'data/small_properties/vertices.txt' \
+             and edges from 'data/small_properties/edges.txt' comment '#';",
+        );
+        for query in &[
+            "create index on edges(year);",
+            "create index on edges(amount) using hash;",
+            "create index on vertices(country);",
+            "create index on vertices(city) using hash;",
+        ] {
+            run(&mut global_store, query);
+        }
+
+        for query in &[
+            "insert vertex '9' (city = 'ottawa', country = 'canada');",
+            "insert edge '9' to '0' (amount = 50, year = 2020);",
+            "delete edges where amount >= 600 and year < 2010;",
+            "set property year = 2021 on edges where u.city = 'ottawa';",
+            "set property amount = 200 on edges where year = 2008;",
+            "set property country = 'ca' on vertices where state = 'ontario';",
+            "set property city = 'ottawa' on vertices where city = 'toronto';",
+        ] {
+            run(&mut global_store, query);
+        }
+
+        // The updated indexes answer every lookup as indexes built from scratch do.
+        let values = vec![
+            PropertyValue::Isize(200),
+            PropertyValue::Isize(2010),
+            PropertyValue::Isize(2021),
+            PropertyValue::String("ca".to_owned()),
+            PropertyValue::String("india".to_owned()),
+            PropertyValue::String("ottawa".to_owned()),
+            PropertyValue::String("toronto".to_owned()),
+        ];
+        let mut rebuilt = IndexStore::default();
+        for index in global_store.index_store.indexes() {
+            let rebuilt_index = rebuilt.create_index(
+                index.vertex_or_edge,
+                index.key_id,
+                index.index_type,
+                &global_store.graph,
+            );
+            assert_eq!(index.len(), rebuilt_index.len());
+            for value in &values {
+                for &operator in
+                    &[Operator::Less, Operator::GreaterEqual, Operator::Equal, Operator::NotEqual]
+                {
+                    let right_operand = RightOperand::Value(value.clone());
+                    assert_eq!(
+                        index
+                            .lookup(operator, &right_operand)
+                            .map(|ids| ids.into_iter().sorted().collect_vec()),
+                        rebuilt_index
+                            .lookup(operator, &right_operand)
+                            .map(|ids| ids.into_iter().sorted().collect_vec()),
+                    );
+                }
+            }
+        }
+    }
+}
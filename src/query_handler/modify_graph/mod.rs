@@ -0,0 +1,58 @@
+use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::VertexOrEdge;
+use crate::query_handler::create_view::{fmt_condition_clauses, PropertyName, WhereConditions};
+
+pub mod executor;
+
+/// Statements that modify the loaded graph in place.
+pub enum ModifyGraphAst {
+    InsertVertex(String, PropertyAssignments),
+    InsertEdge(String, String, PropertyAssignments),
+    DeleteEdges(WhereConditions),
+    SetProperty(VertexOrEdge, PropertyAssignment, WhereConditions),
+}
+pub type PropertyAssignments = Vec<PropertyAssignment>;
+pub type PropertyAssignment = (PropertyName, PropertyValue);
+
+impl std::fmt::Display for ModifyGraphAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ModifyGraphAst::InsertVertex(vertex, assignments) => {
+                write!(f, "insert vertex '{}'{}", vertex, fmt_assignments(assignments))
+            }
+            ModifyGraphAst::InsertEdge(src_vertex, dst_vertex, assignments) => write!(
+                f,
+                "insert edge '{}' to '{}'{}",
+                src_vertex,
+                dst_vertex,
+                fmt_assignments(assignments)
+            ),
+            ModifyGraphAst::DeleteEdges(where_conditions) => {
+                write!(f, "delete edges where {}", fmt_condition_clauses(where_conditions))
+            }
+            ModifyGraphAst::SetProperty(vertex_or_edge, (name, value), where_conditions) => write!(
+                f,
+                "set property {} = {} on {} where {}",
+                name,
+                value,
+                if *vertex_or_edge == VertexOrEdge::Vertex { "vertices" } else { "edges" },
+                fmt_condition_clauses(where_conditions)
+            ),
+        }
+    }
+}
+
+fn fmt_assignments(assignments: &[PropertyAssignment]) -> String {
+    if assignments.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " ({})",
+            assignments
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
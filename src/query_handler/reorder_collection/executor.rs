@@ -0,0 +1,454 @@
+use crate::computations::filtered_cubes::optimal_orders::{get_greedy_order, get_optimal_order};
+use crate::computations::filtered_cubes::{DimensionOrder, DimensionOrders, Matrices};
+use crate::error::GSError;
+use crate::filtered_cubes::timestamp::timestamp_mappings::{
+    accumulate_diffs, get_diff, TimestampMappings,
+};
+use crate::filtered_cubes::timestamp::{DimensionId, GSTimestamp};
+use crate::filtered_cubes::FilteredCube;
+use crate::global_store::GlobalStore;
+use crate::query_handler::create_filtered_cube::executor::print_totals;
+use crate::query_handler::reorder_collection::{CollectionOrdering, ReorderCollectionAst};
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+use gs_analytics_api::{DiffCount, EdgePayload, GsTimestampIndex, SimpleEdge, VertexId};
+use hashbrown::HashMap;
+use itertools::Itertools;
+use log::info;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::convert::TryFrom;
+
+type EdgeKey = (SimpleEdge, EdgePayload);
+/// The diffs of a key, as (timestamp index, change) pairs.
+type KeyDiffs = Vec<(GsTimestampIndex, DiffCount)>;
+type Diffs<K> = Vec<(K, DiffCount)>;
+
+impl GraphSurgeQuery for ReorderCollectionAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let cube = global_store
+            .filtered_cube_store
+            .cubes
+            .get_mut(&self.name)
+            .ok_or_else(|| GSError::CollectionMissing(self.name.clone()))?;
+
+        let old_totals = cube.get_totals();
+        let (edge_diffs, vertex_diffs) = get_key_diffs(cube);
+        // The view at each timestamp id of each dimension. Collections that were not created from
+        // view predicates keep their views in the order they were stored.
+        let current_orders = match &cube.definition {
+            Some(definition) => definition.orders.clone(),
+            None => cube
+                .dimension_lengths
+                .iter()
+                .map(|&length| (0..length as usize).collect_vec())
+                .collect_vec(),
+        };
+        // The current timestamp id to move to each new timestamp id of each dimension.
+        let positions: DimensionOrders = match self.ordering {
+            CollectionOrdering::Manual => current_orders
+                .iter()
+                .zip(&cube.dimension_lengths)
+                .map(|(order, &length)| {
+                    (0..length).sorted_by_key(|&id| order[id as usize]).collect()
+                })
+                .collect(),
+            CollectionOrdering::Optimal | CollectionOrdering::Greedy => {
+                get_ordering_matrices(cube, &edge_diffs)
+                    .into_iter()
+                    .map(|matrix| {
+                        // With less than 3 views, the greedy order is also the optimal one.
+                        let order =
+                            if self.ordering == CollectionOrdering::Optimal && matrix.len() > 3 {
+                                get_optimal_order(&[matrix]).remove(0)
+                            } else {
+                                get_greedy_order(&matrix)
+                            };
+                        // Skip the empty view.
+                        order.into_iter().map(|id| id - 1).collect()
+                    })
+                    .collect()
+            }
+            CollectionOrdering::Random(seed) => {
+                let mut rng = StdRng::seed_from_u64(seed as u64);
+                cube.dimension_lengths
+                    .iter()
+                    .map(|&length| {
+                        let mut order = (0..length).collect::<DimensionOrder>();
+                        order.shuffle(&mut rng);
+                        order
+                    })
+                    .collect()
+            }
+        };
+
+        let total_dimensions = cube.dimension_lengths.len();
+        // The current timestamp index of the view at each new timestamp index.
+        let sources = cube
+            .timestamp_mappings
+            .0
+            .iter()
+            .map(|(_, timestamp)| {
+                let mut source = *timestamp;
+                for (dimension, order) in positions.iter().enumerate() {
+                    let id = timestamp.get_value_at(dimension, total_dimensions);
+                    source.set_value_at(dimension, total_dimensions, order[id as usize]);
+                }
+                cube.timestamp_mappings.1[&source]
+            })
+            .collect_vec();
+
+        let store_total_data = cube.data.has_full_views;
+        let (edge_diffs, full_edges) =
+            reorder_diffs(&cube.timestamp_mappings, &edge_diffs, &sources, store_total_data);
+        let (vertex_diffs, full_vertices) =
+            reorder_diffs(&cube.timestamp_mappings, &vertex_diffs, &sources, store_total_data);
+        let has_payloads = cube.data.has_payloads();
+        let mut entries = Vec::new();
+        let mut payloads = Vec::new();
+        for (timestamp_index, (((diff_edges, full_edges), diff_vertices), full_vertices)) in
+            edge_diffs.into_iter().zip(full_edges).zip(vertex_diffs).zip(full_vertices).enumerate()
+        {
+            let additions = diff_edges.iter().filter(|(_, change)| *change > 0).count();
+            let counts = (additions, diff_edges.len() - additions);
+            let (full_edges, full_payloads): (Vec<_>, Vec<_>) = full_edges.into_iter().unzip();
+            let (diff_edges, diff_payloads): (Vec<_>, Vec<_>) = diff_edges
+                .into_iter()
+                .map(|((edge, payload), change)| ((edge, change), payload))
+                .unzip();
+            if has_payloads {
+                payloads.push((full_payloads, diff_payloads));
+            }
+            entries.push((
+                timestamp_index,
+                cube.timestamp_mappings.0[timestamp_index].1,
+                (full_edges, diff_edges),
+                counts,
+                (full_vertices, diff_vertices),
+            ));
+        }
+
+        let new_orders = current_orders
+            .iter()
+            .zip(&positions)
+            .map(|(order, position)| position.iter().map(|&id| order[id as usize]).collect_vec())
+            .collect_vec();
+        if let Some(definition) = &mut cube.definition {
+            definition.orders.clone_from(&new_orders);
+            definition.manual_order = self.ordering == CollectionOrdering::Manual;
+        }
+        cube.data.entries = entries;
+        cube.data.payloads = payloads;
+        cube.differential_data = None;
+        info!("Reordered '{}' using {} order", self.name, self.ordering);
+        print_totals(cube);
+
+        let new_totals = cube.get_totals();
+        let mut lines =
+            vec![format!("Reordered collection '{}' using {} order", self.name, self.ordering)];
+        for (dimension_index, order) in new_orders.iter().enumerate() {
+            lines.push(format!("Dimension {}: views {:?}", dimension_index + 1, order));
+        }
+        lines.push(format!("Total diffs: {} -> {}", old_totals.diffs, new_totals.diffs));
+        lines.push(format!(
+            "Partial diffs: {} -> {}",
+            old_totals.partial_diffs, new_totals.partial_diffs
+        ));
+        Ok(GraphSurgeResult::new(lines.join("\n")))
+    }
+}
+
+/// Returns the diffs of each edge, along with its payload, and of each vertex of the cube, as
+/// (timestamp index, change) pairs. Keeping the diffs of each key together lets its count in every
+/// view be computed one key at a time, without keeping all the views of the cube.
+fn get_key_diffs(cube: &FilteredCube) -> (HashMap<EdgeKey, KeyDiffs>, HashMap<VertexId, KeyDiffs>) {
+    let mut edge_diffs: HashMap<EdgeKey, KeyDiffs> = HashMap::new();
+    let mut vertex_diffs: HashMap<VertexId, KeyDiffs> = HashMap::new();
+    for (entry_index, (timestamp_index, _, (_, diff_edges), _, (_, diff_vertices))) in
+        cube.data.entries.iter().enumerate()
+    {
+        for (edge_index, &(edge, change)) in diff_edges.iter().enumerate() {
+            let key = (edge, cube.data.diff_payload(entry_index, edge_index));
+            edge_diffs.entry(key).or_default().push((*timestamp_index, change));
+        }
+        for &(vertex_id, change) in diff_vertices {
+            vertex_diffs.entry(vertex_id).or_default().push((*timestamp_index, change));
+        }
+    }
+    (edge_diffs, vertex_diffs)
+}
+
+/// Sets `counts` to the count of a key in each view, given its diffs.
+fn get_counts(
+    timestamp_mappings: &TimestampMappings,
+    diffs: &[(GsTimestampIndex, DiffCount)],
+    counts: &mut [DiffCount],
+) {
+    for count in counts.iter_mut() {
+        *count = 0;
+    }
+    for &(timestamp_index, change) in diffs {
+        counts[timestamp_index] += change;
+    }
+    accumulate_diffs(timestamp_mappings, counts);
+}
+
+/// Returns the diffs, and the full views if `store_total_data` is set, at each new timestamp index,
+/// where the view at new timestamp index `i` is the current view at `sources[i]`.
+fn reorder_diffs<K: Clone + Ord>(
+    timestamp_mappings: &TimestampMappings,
+    key_diffs: &HashMap<K, KeyDiffs>,
+    sources: &[GsTimestampIndex],
+    store_total_data: bool,
+) -> (Vec<Diffs<K>>, Vec<Vec<K>>) {
+    let total_timestamps = timestamp_mappings.0.len();
+    let mut diffs = vec![Vec::new(); total_timestamps];
+    let mut full_views = vec![Vec::new(); total_timestamps];
+    let mut counts = vec![0; total_timestamps];
+    let mut new_counts = vec![0; total_timestamps];
+    for (key, key_diffs) in key_diffs {
+        get_counts(timestamp_mappings, key_diffs, &mut counts);
+        for (new_count, &source) in new_counts.iter_mut().zip(sources) {
+            *new_count = counts[source];
+        }
+        for timestamp_index in 0..total_timestamps {
+            let change = get_diff(timestamp_mappings, &new_counts, timestamp_index);
+            if change != 0 {
+                diffs[timestamp_index].push((key.clone(), change));
+            }
+            if store_total_data {
+                let count = usize::try_from(new_counts[timestamp_index]).unwrap_or(0);
+                full_views[timestamp_index].extend(std::iter::repeat(key.clone()).take(count));
+            }
+        }
+    }
+    for (diffs, view) in diffs.iter_mut().zip(&mut full_views) {
+        diffs.sort_unstable();
+        view.sort_unstable();
+    }
+    (diffs, full_views)
+}
+
+/// Returns the ordering matrices of the cube, with the same layout as the ones computed when
+/// creating a collection. The diffs between two views of a dimension are summed over all the
+/// combinations of the views of the other dimensions.
+fn get_ordering_matrices(cube: &FilteredCube, edge_diffs: &HashMap<EdgeKey, KeyDiffs>) -> Matrices {
+    let total_dimensions = cube.dimension_lengths.len();
+    // The timestamps of each dimension, grouped by their ids in the other dimensions.
+    let groups = (0..total_dimensions)
+        .map(|dimension| {
+            let mut groups: HashMap<GSTimestamp, Vec<(DimensionId, GsTimestampIndex)>> =
+                HashMap::new();
+            for (timestamp_index, (_, timestamp)) in cube.timestamp_mappings.0.iter().enumerate() {
+                let mut others = *timestamp;
+                others.set_value_at(dimension, total_dimensions, 0);
+                groups
+                    .entry(others)
+                    .or_default()
+                    .push((timestamp.get_value_at(dimension, total_dimensions), timestamp_index));
+            }
+            groups.into_iter().map(|(_, group)| group).collect_vec()
+        })
+        .collect_vec();
+    let mut matrices: Matrices = cube
+        .dimension_lengths
+        .iter()
+        .map(|&length| vec![vec![0; length as usize + 1]; length as usize + 1])
+        .collect();
+    let mut counts = vec![0; cube.timestamp_mappings.0.len()];
+    for key_diffs in edge_diffs.values() {
+        get_counts(&cube.timestamp_mappings, key_diffs, &mut counts);
+        for (matrix, groups) in matrices.iter_mut().zip(&groups) {
+            for group in groups {
+                for &(id, timestamp_index) in group {
+                    let size = counts[timestamp_index].unsigned_abs();
+                    let row = id as usize + 1;
+                    matrix[0][row] += size;
+                    matrix[row][0] += size;
+                }
+                for (&(left_id, left), &(right_id, right)) in group.iter().tuple_combinations() {
+                    let diffs = (counts[left] - counts[right]).unsigned_abs();
+                    let (left_id, right_id) = (left_id as usize + 1, right_id as usize + 1);
+                    matrix[left_id][right_id] += diffs;
+                    matrix[right_id][left_id] += diffs;
+                }
+            }
+        }
+    }
+    matrices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filtered_cubes::get_dimension_length;
+    use crate::filtered_cubes::timestamp::GSTimestamp;
+    use crate::global_store::GlobalStore;
+    use crate::process_query;
+    use gs_analytics_api::{DiffCount, EdgePayload, SimpleEdge};
+    use hashbrown::HashMap;
+    use itertools::Itertools;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_reorder_collection() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection years
+            where [year <= 2018],[year <= 2000],[year <= 2012],[year <= 2006]
+            manually_ordered materialize_full_view;",
+            "copy collection years to optimal_years;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+        let full_views = get_full_views(&global_store, "years");
+        let manual_diffs = global_store.filtered_cube_store.cubes["years"].get_totals().diffs;
+
+        for (ordering, expected_order) in &[
+            ("greedy", Some(vec![1, 3, 2, 0])),
+            ("random seed 5", None),
+            ("optimal", None),
+            ("manual", Some(vec![0, 1, 2, 3])),
+        ] {
+            let mut query = format!("reorder collection years using {};", ordering);
+            let result = process_query(&mut global_store, &mut query).expect("Query failed");
+            let cube = &global_store.filtered_cube_store.cubes["years"];
+            let order = cube.definition.as_ref().expect("Definition missing").orders[0].clone();
+            if let Some(expected_order) = expected_order {
+                assert_eq!(&order, expected_order, "{}", ordering);
+            }
+            assert_eq!(order.iter().sorted().copied().collect_vec(), vec![0, 1, 2, 3]);
+            assert!(
+                result.ends_with(&format!("-> {}", cube.get_totals().partial_diffs)),
+                "{}",
+                result
+            );
+
+            // The views are the same, in the new order, and their diffs add up to them.
+            let views = get_full_views(&global_store, "years");
+            let mut accumulated: HashMap<SimpleEdge, DiffCount> = HashMap::new();
+            for (timestamp_id, &view) in order.iter().enumerate() {
+                assert_eq!(views[timestamp_id], full_views[view], "{} at {}", ordering, view);
+                let (_, _, _, (adds, dels), _) = cube.data.entries[timestamp_id];
+                let (_, diffs) = &cube.data.entries[timestamp_id].2;
+                assert_eq!(adds + dels, diffs.len());
+                for (edge, change) in diffs {
+                    *accumulated.entry(*edge).or_insert(0) += change;
+                }
+                accumulated.retain(|_, count| *count != 0);
+                let accumulated_view = accumulated
+                    .iter()
+                    .flat_map(|(&edge, &count)| {
+                        std::iter::repeat(edge)
+                            .take(usize::try_from(count).expect("Negative count"))
+                    })
+                    .sorted()
+                    .collect_vec();
+                assert_eq!(accumulated_view, full_views[view], "{} at {}", ordering, view);
+            }
+        }
+        let cube = &global_store.filtered_cube_store.cubes["years"];
+        assert_eq!(cube.get_totals().diffs, manual_diffs);
+        assert!(cube.definition.as_ref().expect("Definition missing").manual_order);
+
+        // The optimal tour over cumulative views follows their sizes, in either direction.
+        let mut query = "reorder collection optimal_years using optimal;".to_owned();
+        let result = process_query(&mut global_store, &mut query).expect("Query failed");
+        assert!(
+            result.contains("Dimension 1: views [1, 3, 2, 0]")
+                || result.contains("Dimension 1: views [0, 2, 3, 1]"),
+            "{}",
+            result
+        );
+        let optimal_totals = global_store.filtered_cube_store.cubes["optimal_years"].get_totals();
+        assert!(optimal_totals.diffs < manual_diffs);
+        assert!(
+            result.contains(&format!("Total diffs: {} -> {}", manual_diffs, optimal_totals.diffs))
+        );
+
+        let mut query = "reorder collection missing using manual;".to_owned();
+        let error =
+            process_query(&mut global_store, &mut query).expect_err("Collection should be missing");
+        assert!(error.to_string().contains("has not been created yet"));
+    }
+
+    #[test]
+    fn test_reorder_multiple_dimensions() {
+        let mut global_store = GlobalStore::default();
+
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';",
+            "create view collection years_included
+            where [year <= 2018],[year <= 2000],[year <= 2008]
+            by [include = true],[include = false]
+            with edge properties (amount)
+            manually_ordered
+            materialize_full_view;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+        let created = get_sorted_entries(&global_store, "years_included");
+
+        let mut query = "reorder collection years_included using random seed 3;".to_owned();
+        process_query(&mut global_store, &mut query).expect("Query failed");
+        let cube = &global_store.filtered_cube_store.cubes["years_included"];
+        let orders = cube.definition.as_ref().expect("Definition missing").orders.clone();
+        let reordered = get_sorted_entries(&global_store, "years_included");
+        for (timestamp_index, (_, timestamp)) in cube.timestamp_mappings.0.iter().enumerate() {
+            let source = GSTimestamp::new(&[
+                get_dimension_length(orders[0][timestamp.get_value_at(0, 2) as usize])
+                    .expect("Dimension length overflow"),
+                get_dimension_length(orders[1][timestamp.get_value_at(1, 2) as usize])
+                    .expect("Dimension length overflow"),
+            ]);
+            let source_index = cube.timestamp_mappings.1[&source];
+            assert_eq!(reordered[timestamp_index].1, created[source_index].1);
+        }
+
+        for query in &[
+            "reorder collection years_included using greedy;",
+            "reorder collection years_included using manual;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+        assert_eq!(get_sorted_entries(&global_store, "years_included"), created);
+    }
+
+    type SortedEntry = (
+        GSTimestamp,
+        Vec<(SimpleEdge, EdgePayload)>,
+        Vec<((SimpleEdge, DiffCount), EdgePayload)>,
+        (usize, usize),
+    );
+
+    fn get_sorted_entries(global_store: &GlobalStore, name: &str) -> Vec<SortedEntry> {
+        let data = &global_store.filtered_cube_store.cubes[name].data;
+        data.entries
+            .iter()
+            .zip(&data.payloads)
+            .map(|((_, timestamp, (full_edges, diff_edges), counts, _), payloads)| {
+                (
+                    *timestamp,
+                    full_edges.iter().copied().zip(payloads.0.iter().cloned()).sorted().collect(),
+                    diff_edges.iter().copied().zip(payloads.1.iter().cloned()).sorted().collect(),
+                    *counts,
+                )
+            })
+            .collect()
+    }
+
+    fn get_full_views(global_store: &GlobalStore, name: &str) -> Vec<Vec<SimpleEdge>> {
+        global_store.filtered_cube_store.cubes[name]
+            .data
+            .entries
+            .iter()
+            .map(|(_, _, (full_edges, _), _, _)| full_edges.iter().copied().sorted().collect_vec())
+            .collect_vec()
+    }
+}
@@ -0,0 +1,37 @@
+pub mod executor;
+
+#[derive(new)]
+pub struct ReorderCollectionAst {
+    name: String,
+    ordering: CollectionOrdering,
+}
+
+/// How the views of each dimension of a collection are ordered.
+#[derive(Clone, Copy, PartialEq)]
+pub enum CollectionOrdering {
+    /// The order computed when creating the collection, which minimizes the diffs.
+    Optimal,
+    /// The order in which the views were written.
+    Manual,
+    /// Each view is followed by the remaining view with the fewest diffs from it.
+    Greedy,
+    /// A random order, generated from the given seed.
+    Random(usize),
+}
+
+impl std::fmt::Display for CollectionOrdering {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            CollectionOrdering::Optimal => write!(f, "optimal"),
+            CollectionOrdering::Manual => write!(f, "manual"),
+            CollectionOrdering::Greedy => write!(f, "greedy"),
+            CollectionOrdering::Random(seed) => write!(f, "random seed {}", seed),
+        }
+    }
+}
+
+impl std::fmt::Display for ReorderCollectionAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "reorder collection {} using {}", self.name, self.ordering)
+    }
+}
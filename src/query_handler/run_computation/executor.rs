@@ -1,5 +1,6 @@
 //use crate::computations::CType;
 //use crate::computations::ComputationProperties;
+use crate::computations::dataflow_arranged_adaptive::{AdaptiveModel, AdaptiveSettings};
 use crate::error::GSError;
 use crate::filtered_cubes::FilteredCube;
 use crate::global_store::GlobalStore;
@@ -8,11 +9,14 @@ use crate::query_handler::run_computation::RunComputationAst;
 use crate::query_handler::GraphSurgeQuery;
 use crate::query_handler::GraphSurgeResult;
 //use hashbrown::HashMap;
-use gs_analytics_api::ComputationRuntimeData;
+use gs_analytics_api::{ComputationRuntimeData, ComputationType};
+use itertools::Itertools;
 use log::info;
+use std::convert::TryFrom;
 
 impl GraphSurgeQuery for RunComputationAst {
     fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let runtime_data = self.get_runtime_data(global_store);
         let cube: &mut FilteredCube = global_store
             .filtered_cube_store
             .cubes
@@ -31,25 +35,207 @@ impl GraphSurgeQuery for RunComputationAst {
                     ))
                 })?;
 
-            computation.execute(
-                &self.properties,
-                cube,
-                ComputationRuntimeData::new(
-                    self.c_type,
-                    global_store.graph.vertex_count(),
-                    self.materialize_results,
-                    self.save_to.clone(),
-                    global_store.threads.get(),
-                    global_store.process_id,
-                    self.hosts.clone(),
-                    self.splits.clone(),
-                    self.batch_size,
-                    self.comp_multipler,
-                    self.diff_multipler,
-                    self.limit,
-                    self.use_lr,
-                ),
-            )
+            let (result, adaptive_model) =
+                computation.execute(&self.properties, cube, runtime_data)?;
+            if let Some(adaptive_model) = adaptive_model {
+                cube.adaptive_models.insert(self.computation.clone(), adaptive_model);
+            }
+            Ok(result)
         }
     }
 }
+
+impl RunComputationAst {
+    fn get_runtime_data(&self, global_store: &GlobalStore) -> ComputationRuntimeData {
+        ComputationRuntimeData::new(
+            self.c_type,
+            global_store.graph.vertex_count(),
+            self.materialize_results,
+            self.save_to.clone(),
+            global_store.threads.get(),
+            global_store.process_id,
+            self.hosts.clone(),
+            self.splits.clone(),
+            self.batch_size,
+            self.comp_multipler,
+            self.diff_multipler,
+            self.limit,
+            self.use_lr,
+        )
+    }
+
+    /// Reports how the computation would be run on the collection, without running it.
+    pub fn explain(&self, global_store: &GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let cube = global_store
+            .filtered_cube_store
+            .cubes
+            .get(&self.cube)
+            .ok_or_else(|| GSError::CollectionMissing(self.cube.clone()))?;
+        if !global_store.computations.contains_key(&self.computation) {
+            return Err(GSError::Computation(format!(
+                "Computation '{}' is not defined yet",
+                self.computation
+            )));
+        }
+
+        let mut lines = vec![
+            format!("Runner: {} ({})", self.c_type.description(), get_runner_details(self.c_type)),
+            format!("Arrangements: {}", get_arrangements(self.c_type, cube).join(", ")),
+        ];
+        let (edge_diffs, vertex_diffs) =
+            cube.data.entries.iter().fold((0, 0), |(edges, vertices), (_, _, (_, diffs), _, v)| {
+                (edges + diffs.len(), vertices + v.1.len())
+            });
+        let (full_edges, full_vertices) = get_full_view_sizes(cube);
+        lines.push(format!(
+            "Diffs: {} edge diffs and {} vertex diffs over {} views",
+            edge_diffs,
+            vertex_diffs,
+            cube.data.entries.len()
+        ));
+        lines.push(format!(
+            "Full views: {} edges{}{}",
+            full_edges,
+            if cube.data.has_vertex_views {
+                format!(" and {} vertices", full_vertices)
+            } else {
+                String::new()
+            },
+            match self.c_type {
+                ComputationType::Individual
+                | ComputationType::IndividualBasic
+                | ComputationType::Timely => " (inserted by this runner)",
+                ComputationType::CompareDifferential => " (inserted along with the diffs)",
+                ComputationType::Basic
+                | ComputationType::OneStageDifferential
+                | ComputationType::TwoStageDifferential
+                | ComputationType::Adaptive => "",
+            }
+        ));
+        if cube.stale {
+            lines.push("Warning: the collection is stale".to_owned());
+        }
+
+        if self.c_type == ComputationType::Adaptive {
+            let settings = AdaptiveSettings::new(&self.get_runtime_data(global_store));
+            if let Some(splits) = &self.splits {
+                lines.push(format!("Splits: given at {:?}", splits.iter().sorted().collect_vec()));
+            } else if let Some(model) =
+                cube.adaptive_models.get(&self.computation).filter(|model| model.is_fitted())
+            {
+                lines.push(format!(
+                    "Splits: {:?}, predicted by the model fitted by the last run of '{}' \
+                    ({} individual and {} differential runs)",
+                    get_adaptive_splits(cube, Some(model), &settings),
+                    self.computation,
+                    model.individual_runs.0.len(),
+                    model.diff_runs.0.len()
+                ));
+            } else {
+                lines.push(format!(
+                    "Splits: no fitted model for '{}' on '{}' yet, so splits are decided during \
+                    the run. Splits on diff counts alone: {:?}",
+                    self.computation,
+                    self.cube,
+                    get_adaptive_splits(cube, None, &settings)
+                ));
+            }
+        }
+        Ok(GraphSurgeResult::new(lines.join("\n")))
+    }
+}
+
+fn get_runner_details(c_type: ComputationType) -> &'static str {
+    match c_type {
+        ComputationType::Basic => {
+            "high level operators, inserting diffs with incrementing timely timestamps"
+        }
+        ComputationType::OneStageDifferential => {
+            "arranged operators, inserting diffs with incrementing timely timestamps"
+        }
+        ComputationType::TwoStageDifferential => {
+            "arranged operators, inserting all diffs before computing"
+        }
+        ComputationType::Adaptive => {
+            "arranged operators, splitting views to run individually when predicted to be faster"
+        }
+        ComputationType::Individual => "arranged operators, running each full view separately",
+        ComputationType::IndividualBasic => {
+            "high level operators, running each full view separately"
+        }
+        ComputationType::CompareDifferential => {
+            "runs differentially and individually, and compares the results"
+        }
+        ComputationType::Timely => "timely dataflow over the full views",
+    }
+}
+
+fn get_arrangements(c_type: ComputationType, cube: &FilteredCube) -> Vec<String> {
+    match c_type {
+        ComputationType::OneStageDifferential
+        | ComputationType::TwoStageDifferential
+        | ComputationType::Adaptive
+        | ComputationType::Individual => {
+            let mut arrangements = vec![
+                "forward edges by source".to_owned(),
+                "reverse edges by destination".to_owned(),
+                if cube.data.has_vertex_views {
+                    "vertex views".to_owned()
+                } else {
+                    "distinct edge endpoints".to_owned()
+                },
+            ];
+            if cube.data.has_payloads() {
+                arrangements.push(format!(
+                    "weighted edges by source ({})",
+                    cube.data.edge_properties.join(", ")
+                ));
+            }
+            arrangements
+        }
+        ComputationType::Basic
+        | ComputationType::IndividualBasic
+        | ComputationType::CompareDifferential
+        | ComputationType::Timely => {
+            vec!["none, the computation arranges its own inputs".to_owned()]
+        }
+    }
+}
+
+/// Returns the total edges and vertices of all the views of the collection.
+fn get_full_view_sizes(cube: &FilteredCube) -> (isize, isize) {
+    cube.get_full_view_sizes().into_iter().fold(
+        (0, 0),
+        |(edges, vertices), (view_edges, view_vertices)| {
+            (edges + view_edges, vertices + view_vertices)
+        },
+    )
+}
+
+/// Returns the indices of the views the adaptive runner would run individually. Without a fitted
+/// model, only the decisions based on the diff counts can be made.
+fn get_adaptive_splits(
+    cube: &FilteredCube,
+    model: Option<&AdaptiveModel>,
+    settings: &AdaptiveSettings,
+) -> Vec<usize> {
+    let mut indvc_total: isize = 0;
+    let mut splits = Vec::new();
+    for (index, (_, _, _, (adds, dels), _)) in cube.data.entries.iter().enumerate() {
+        let diffc_total = isize::try_from(adds + dels).expect("Overflow");
+        indvc_total +=
+            isize::try_from(*adds).expect("Overflow") - isize::try_from(*dels).expect("Overflow");
+        // The first view is always run individually and the second one differentially.
+        if index < 2 {
+            continue;
+        }
+        let should_split = match model {
+            Some(model) => model.predict_split(diffc_total, indvc_total, settings).should_split,
+            None => diffc_total as f64 > indvc_total as f64 * settings.diff_multipler,
+        };
+        if should_split {
+            splits.push(index);
+        }
+    }
+    splits
+}
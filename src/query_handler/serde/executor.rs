@@ -35,3 +35,99 @@ impl GraphSurgeQuery for Serde {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::{serialize_object, GlobalStore};
+    use crate::process_query;
+
+    #[test]
+    fn test_deserialize_format_version() {
+        let mut global_store = GlobalStore::default();
+        let mut query = "
+            load graph with vertices from 'data/small_properties/vertices.txt'
+            and edges from 'data/small_properties/edges.txt'
+            comment '#';"
+            .to_owned();
+        process_query(&mut global_store, &mut query).expect("Graph not loaded");
+        let mut query = "
+            create view collection amounts
+            where [year <= 2000],[year <= 2008]
+            manually_ordered;"
+            .to_owned();
+        process_query(&mut global_store, &mut query).expect("Cube not created");
+
+        let bin_dir = std::env::temp_dir().join("graphsurge_serde_format_version");
+        let graph_dir = bin_dir.join("graph");
+        let cube_dir = bin_dir.join("cube");
+        for dir in &[&graph_dir, &cube_dir] {
+            std::fs::create_dir_all(dir).expect("Bin dir not created");
+        }
+        let graph_dir = graph_dir.to_str().expect("Invalid path");
+        let cube_dir = cube_dir.to_str().expect("Invalid path");
+        let mut query = format!("serialize to '{}';", graph_dir);
+        process_query(&mut global_store, &mut query).expect("Graph not serialized");
+        let mut query = format!("serialize cube amounts to '{}';", cube_dir);
+        process_query(&mut global_store, &mut query).expect("Cube not serialized");
+
+        let mut deserialized_store = GlobalStore::default();
+        let mut query = format!("deserialize from '{}';", graph_dir);
+        process_query(&mut deserialized_store, &mut query).expect("Graph not deserialized");
+        assert_eq!(deserialized_store.graph.edges_count(), global_store.graph.edges_count());
+
+        // Data serialized before the version was recorded has version 0.
+        std::fs::remove_file(format!("{}/graph_format_version.bin", graph_dir))
+            .expect("File not removed");
+        let mut query = format!("deserialize from '{}';", graph_dir);
+        let error = process_query(&mut deserialized_store, &mut query).expect_err("Deserialized");
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "[SerdeError] Unsupported format version 0 of the data in '{}', this build only \
+                 reads version 1",
+                graph_dir
+            )
+        );
+
+        serialize_object(cube_dir, "cube_format_version", &2_u32).expect("Version not written");
+        deserialized_store.filtered_cube_store.cubes.clear();
+        let mut query = format!("deserialize cube from '{}';", cube_dir);
+        let error = process_query(&mut deserialized_store, &mut query).expect_err("Deserialized");
+        assert!(error.to_string().contains("Unsupported format version 2"));
+    }
+
+    #[test]
+    fn test_serialize_stale_collection() {
+        let mut global_store = GlobalStore::default();
+        for query in &[
+            "load graph with vertices from 'data/small_properties/vertices.txt' \
+             and edges from 'data/small_properties/edges.txt' comment '#';",
+            "create view collection before where [year <= 2000],[year <= 2008] manually_ordered;",
+            "delete edges where amount >= 600;",
+        ] {
+            process_query(&mut global_store, &mut (*query).to_owned()).expect("Query failed");
+        }
+        assert!(global_store.filtered_cube_store.cubes["before"].stale);
+
+        let cube_dir = std::env::temp_dir().join("graphsurge_serde_stale_collection");
+        std::fs::create_dir_all(&cube_dir).expect("Bin dir not created");
+        let cube_dir = cube_dir.to_str().expect("Invalid path");
+        let mut query = format!("serialize cube before to '{}';", cube_dir);
+        process_query(&mut global_store, &mut query).expect("Cube not serialized");
+        global_store.filtered_cube_store.cubes.clear();
+        let mut query = format!("deserialize cube from '{}';", cube_dir);
+        process_query(&mut global_store, &mut query).expect("Cube not deserialized");
+
+        assert!(global_store.filtered_cube_store.cubes["before"].stale);
+        let description =
+            process_query(&mut global_store, &mut "describe collection before;".to_owned())
+                .expect("Collection not described");
+        assert!(description.starts_with("Collection 'before' (stale)"));
+        let error = process_query(
+            &mut global_store,
+            &mut "alter view collection before add [year <= 2010];".to_owned(),
+        )
+        .expect_err("Stale collection altered");
+        assert!(error.to_string().contains("is stale"));
+    }
+}
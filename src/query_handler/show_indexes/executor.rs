@@ -0,0 +1,31 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::VertexOrEdge;
+use crate::query_handler::show_indexes::ShowIndexesAst;
+use crate::query_handler::GraphSurgeQuery;
+use crate::query_handler::GraphSurgeResult;
+
+impl GraphSurgeQuery for ShowIndexesAst {
+    fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
+        let indexes = global_store.index_store.indexes();
+        if indexes.is_empty() {
+            return Ok(GraphSurgeResult::new("No indexes registered".to_owned()));
+        }
+        let result = indexes
+            .iter()
+            .enumerate()
+            .map(|(index_number, index)| {
+                format!(
+                    "({}) index on {}({}) using {}, {} entries",
+                    index_number + 1,
+                    if index.vertex_or_edge == VertexOrEdge::Vertex { "vertices" } else { "edges" },
+                    global_store.key_store.key_string(index.key_id),
+                    index.index_type,
+                    index.len()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        Ok(GraphSurgeResult::new(format!("Indexes:\n{}", result)))
+    }
+}
@@ -0,0 +1,9 @@
+pub mod executor;
+
+pub struct ShowIndexesAst;
+
+impl std::fmt::Display for ShowIndexesAst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "show indexes")
+    }
+}
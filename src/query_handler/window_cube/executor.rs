@@ -1,7 +1,7 @@
 use crate::error::GSError;
 use crate::filtered_cubes::timestamp::timestamp_mappings::get_timestamp_mappings;
 use crate::filtered_cubes::timestamp::GSTimestamp;
-use crate::filtered_cubes::{DimensionLength, FilteredCube};
+use crate::filtered_cubes::{get_dimension_length, FilteredCube};
 use crate::global_store::GlobalStore;
 use crate::query_handler::create_filtered_cube::executor::print_totals;
 use crate::query_handler::generate_cube::executor::partial_shuffle;
@@ -27,10 +27,9 @@ impl GraphSurgeQuery for WindowCubeAst {
         let graph = GraphMMap::new(&self.graph_filename);
         let mut edges = (0..graph.nodes())
             .flat_map(|node| {
-                graph
-                    .edges(node)
-                    .iter()
-                    .map(move |neighbor| (VertexId::try_from(node).expect("Overflow"), *neighbor))
+                graph.edges(node).iter().map(move |&neighbor| {
+                    (VertexId::try_from(node).expect("Overflow"), VertexId::from(neighbor))
+                })
             })
             .collect::<Vec<_>>();
         info!("Loaded {} edges", edges.len(),);
@@ -91,9 +90,10 @@ impl GraphSurgeQuery for WindowCubeAst {
 
                 cube_data.push((
                     ts,
-                    GSTimestamp::new(&[DimensionLength::try_from(ts).expect("Overflow")]),
+                    GSTimestamp::new(&[get_dimension_length(ts)?]),
                     (Vec::new(), data),
                     (adds_len, dels_len),
+                    (Vec::new(), Vec::new()),
                 ));
                 ts += 1;
                 del_start_index += dels;
@@ -101,7 +101,7 @@ impl GraphSurgeQuery for WindowCubeAst {
             }
         }
 
-        let dimension_lengths = vec![DimensionLength::try_from(ts).expect("Overflow")];
+        let dimension_lengths = vec![get_dimension_length(ts)?];
         let timestamp_mappings = get_timestamp_mappings(&dimension_lengths);
         let cube = FilteredCube::new(
             timestamp_mappings,
@@ -18,7 +18,7 @@ impl GraphSurgeQuery for WriteCubeAst {
                 let data = &cube.data.entries;
                 for chunk in data.chunks(std::cmp::max(1, data.len() / self.threads)) {
                     let thread: ScopedJoinHandle<Result<(), GSError>> = s.spawn(move |_| {
-                        for (_, timestamp, (full_edges, diff_edges), _) in chunk {
+                        for (_, timestamp, (full_edges, diff_edges), _, _) in chunk {
                             info!("Writing {}...", timestamp);
                             let full_edges_path = format!(
                                 "{}/fcube-{}-full-{}.txt",
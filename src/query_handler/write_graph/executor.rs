@@ -1,24 +1,345 @@
 use crate::error::GSError;
 use crate::global_store::GlobalStore;
-use crate::query_handler::write_graph::WriteGraphAst;
+use crate::graph::key_store::KeyStore;
+use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::{Properties, PropertyKeyId};
+use crate::query_handler::load_graph::executor::DEFAULT_LIST_SEPARATOR;
+use crate::query_handler::load_graph::GraphFormat;
+use crate::query_handler::write_graph::{graphml, jsonl, WriteGraphAst};
 use crate::query_handler::GraphSurgeQuery;
 use crate::util::io::GsWriter;
 use crate::GraphSurgeResult;
+use chrono::NaiveDateTime;
+use gs_analytics_api::{EdgeId, VertexId};
+use itertools::Itertools;
+use std::convert::TryFrom;
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+const CSV_SEPARATOR: char = ',';
+
+/// The key and the `LOAD GRAPH` type name of each property column.
+pub(super) type PropertyColumns = Vec<(PropertyKeyId, &'static str)>;
 
 impl GraphSurgeQuery for WriteGraphAst {
     fn execute(&self, global_store: &mut GlobalStore) -> Result<GraphSurgeResult, GSError> {
-        let vertex_file_path = format!("{}/vertices.txt", self.dir);
-        let mut vertex_file_buffer = GsWriter::new(vertex_file_path)?;
-        for (vertex_id, _) in global_store.graph.vertices().iter().enumerate() {
-            vertex_file_buffer.write_file_line(&format!("{}", vertex_id,))?;
+        match self.format {
+            GraphFormat::Csv => write_csv(global_store, &self.dir)?,
+            GraphFormat::GraphMl => graphml::write_graphml(global_store, &self.dir)?,
+            GraphFormat::JsonLines => jsonl::write_jsonl(global_store, &self.dir)?,
         }
+        Ok(GraphSurgeResult::new(format!("Graph written to '{}'", self.dir)))
+    }
+}
+
+fn write_csv(global_store: &GlobalStore, dir: &str) -> Result<(), GSError> {
+    let graph = &global_store.graph;
+    let vertex_id_strings = get_vertex_id_strings(global_store)
+        .into_iter()
+        .map(|vertex_id_string| quote_if_needed(&vertex_id_string, CSV_SEPARATOR))
+        .collect_vec();
+
+    let vertex_columns = get_property_columns(
+        (0..graph.vertex_count()).map(|vertex_id| graph.vertex_properties(vertex_id as VertexId)),
+        &global_store.key_store,
+    )?;
+    let vertex_file_path = format!("{}/vertices.txt", dir);
+    let mut vertex_file_buffer = GsWriter::new(vertex_file_path)?;
+    vertex_file_buffer.write_file_line(&get_header(
+        &[":id"],
+        &vertex_columns,
+        &global_store.key_store,
+    ))?;
+    for (vertex_id, vertex_id_string) in vertex_id_strings.iter().enumerate() {
+        vertex_file_buffer.write_file_line(&format!(
+            "{}{}",
+            vertex_id_string,
+            get_property_fields(&graph.vertex_properties(vertex_id as VertexId), &vertex_columns)
+        ))?;
+    }
+
+    let edge_columns = get_property_columns(
+        graph.edge_iterator().map(|(edge_id, _)| graph.edge_properties(edge_id as EdgeId)),
+        &global_store.key_store,
+    )?;
+    let edge_file_path = format!("{}/edges.txt", dir);
+    let mut edge_file_buffer = GsWriter::new(edge_file_path)?;
+    edge_file_buffer.write_file_line(&get_header(
+        &[":start_id", ":end_id"],
+        &edge_columns,
+        &global_store.key_store,
+    ))?;
+    for (edge_id, edge) in graph.edge_iterator() {
+        edge_file_buffer.write_file_line(&format!(
+            "{},{}{}",
+            vertex_id_strings[edge.src_vertex_id as usize],
+            vertex_id_strings[edge.dst_vertex_id as usize],
+            get_property_fields(&graph.edge_properties(edge_id as EdgeId), &edge_columns)
+        ))?;
+    }
+    Ok(())
+}
 
-        let edge_file_path = format!("{}/edges.txt", self.dir);
-        let mut edge_file_buffer = GsWriter::new(edge_file_path)?;
-        for edge in global_store.graph.edges() {
-            edge_file_buffer
-                .write_file_line(&format!("{},{}", edge.src_vertex_id, edge.dst_vertex_id,))?;
+/// Returns the original vertex id strings, indexed by `VertexId`. Falls back to the numeric ids
+/// if the id strings of some vertices are not known.
+pub(super) fn get_vertex_id_strings(global_store: &GlobalStore) -> Vec<String> {
+    let vertex_count = global_store.graph.vertex_count();
+    let mut vertex_id_strings = vec![None; vertex_count];
+    for (vertex_id_string, &vertex_id) in &global_store.vertex_ids {
+        vertex_id_strings[vertex_id as usize] = Some(vertex_id_string);
+    }
+    if vertex_id_strings.iter().all(Option::is_some) {
+        vertex_id_strings.into_iter().flatten().cloned().collect()
+    } else {
+        (0..vertex_count).map(|vertex_id| vertex_id.to_string()).collect()
+    }
+}
+
+/// Returns the property keys that are present in `properties`, along with their types. The
+/// columns keep the order in which the properties are stored, so that a reloaded graph assigns
+/// the keys in the same order.
+pub(super) fn get_property_columns(
+    properties: impl Iterator<Item = Properties>,
+    key_store: &KeyStore,
+) -> Result<PropertyColumns, GSError> {
+    let mut columns: PropertyColumns = Vec::new();
+    for properties in properties {
+        // Index of the column of the previous property, so that new keys are placed after it.
+        let mut previous_index = None;
+        for (key_id, value) in properties.iter() {
+            let type_name = get_type_name(value)?;
+            match columns.iter().position(|(column_key_id, _)| column_key_id == key_id) {
+                Some(index) => {
+                    let column_type_name = columns[index].1;
+                    if column_type_name != type_name {
+                        return Err(GSError::Generic(format!(
+                            "Property '{}' has values of both '{}' and '{}' types",
+                            key_store.key_string(*key_id),
+                            column_type_name,
+                            type_name
+                        )));
+                    }
+                    previous_index = Some(index);
+                }
+                None => {
+                    let index = previous_index.map_or(0, |index| index + 1);
+                    columns.insert(index, (*key_id, type_name));
+                    previous_index = Some(index);
+                }
+            }
+        }
+    }
+    Ok(columns)
+}
+
+pub(super) fn get_type_name(value: &PropertyValue) -> Result<&'static str, GSError> {
+    Ok(match value {
+        PropertyValue::Isize(_) => "int",
+        PropertyValue::String(_) => "string",
+        PropertyValue::Strings(_) => "strings",
+        PropertyValue::IntList(_) => "ints",
+        PropertyValue::Bool(_) => "bool",
+        PropertyValue::Float(_) => "float",
+        PropertyValue::DateTime(_) => "datetime",
+        PropertyValue::KeyId(_) | PropertyValue::Pair(_, _) | PropertyValue::FloatPair(_, _) => {
+            return Err(GSError::Generic(format!(
+                "Property type '{}' cannot be written to a graph file",
+                value.value_type()
+            )));
+        }
+    })
+}
+
+fn get_header(id_columns: &[&str], columns: &PropertyColumns, key_store: &KeyStore) -> String {
+    id_columns
+        .iter()
+        .map(|&id_column| id_column.to_owned())
+        .chain(
+            columns.iter().map(|(key_id, type_name)| {
+                format!("{}:{}", key_store.key_string(*key_id), type_name)
+            }),
+        )
+        .join(",")
+}
+
+/// Returns the comma prefixed property values, where missing properties are left empty.
+fn get_property_fields(properties: &Properties, columns: &PropertyColumns) -> String {
+    columns
+        .iter()
+        .map(|(key_id, _)| {
+            let field = properties
+                .iter()
+                .find(|(property_key_id, _)| property_key_id == key_id)
+                .map_or_else(String::new, |(_, value)| get_csv_field(value));
+            format!(",{}", field)
+        })
+        .collect()
+}
+
+fn get_csv_field(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::String(_) | PropertyValue::Strings(_) | PropertyValue::IntList(_) => {
+            quote_if_needed(&escape_empty_value(get_field(value)), CSV_SEPARATOR)
+        }
+        value => get_field(value),
+    }
+}
+
+/// Adds two quotes to a value that only holds quotes, including an empty value, so that it is not
+/// read back as missing. The inverse of `unescape_empty_value`.
+fn escape_empty_value(value: String) -> String {
+    if value.len() != 1 && value.bytes().all(|byte| byte == b'"') {
+        format!("\"\"{}", value)
+    } else {
+        value
+    }
+}
+
+/// Formats the value the way it is parsed by `LOAD GRAPH` for its type.
+pub(super) fn get_field(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::String(value) => value.clone(),
+        PropertyValue::Strings(values) => values
+            .iter()
+            .map(|value| quote_if_needed(value, DEFAULT_LIST_SEPARATOR))
+            .join(&DEFAULT_LIST_SEPARATOR.to_string()),
+        PropertyValue::IntList(values) => values.iter().join(&DEFAULT_LIST_SEPARATOR.to_string()),
+        PropertyValue::Float(value) => format!("{:?}", value.0),
+        PropertyValue::DateTime(millis) => format_datetime(*millis),
+        value => value.to_string(),
+    }
+}
+
+pub(super) fn format_datetime(millis: i64) -> String {
+    NaiveDateTime::from_timestamp(
+        millis.div_euclid(1000),
+        u32::try_from(millis.rem_euclid(1000) * 1_000_000).expect("Nanoseconds overflow"),
+    )
+    .format(DATETIME_FORMAT)
+    .to_string()
+}
+
+/// Quotes the value as in RFC 4180 if it holds the separator, a quote or a line break, so that it
+/// is read back as a single field, or if it is empty, so that an empty list element is not dropped.
+/// Quotes inside the value are doubled.
+fn quote_if_needed(value: &str, separator: char) -> String {
+    if value.is_empty() || value.contains(&[separator, '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::global_store::GlobalStore;
+    use crate::graph::properties::property_value::PropertyValue;
+    use crate::graph::properties::Properties;
+    use crate::process_query;
+    use gs_analytics_api::{EdgeId, VertexId};
+
+    fn load_graph(query: String) -> GlobalStore {
+        let mut global_store = GlobalStore::default();
+        process_query(&mut global_store, &mut { query }).expect("Graph not loaded");
+        global_store
+    }
+
+    /// Properties with key names instead of key ids, which depend on the order keys are seen in.
+    fn named_properties(
+        properties: &Properties,
+        global_store: &GlobalStore,
+    ) -> Vec<(String, PropertyValue)> {
+        properties
+            .iter()
+            .map(|(key_id, value)| {
+                (global_store.key_store.key_string(*key_id).clone(), value.clone())
+            })
+            .collect()
+    }
+
+    fn assert_same_graph(left: &GlobalStore, right: &GlobalStore, message: &str) {
+        assert_eq!(left.vertex_ids, right.vertex_ids, "{}", message);
+        for global_store in &[left, right] {
+            assert_eq!(global_store.graph.vertex_count(), left.graph.vertex_count(), "{}", message);
+            assert_eq!(global_store.graph.edges_count(), left.graph.edges_count(), "{}", message);
+        }
+        for vertex_id in 0..left.graph.vertex_count() as VertexId {
+            assert_eq!(
+                named_properties(&left.graph.vertex_properties(vertex_id), left),
+                named_properties(&right.graph.vertex_properties(vertex_id), right),
+                "{}",
+                message
+            );
+        }
+        for (edge_id, (left_edge, right_edge)) in
+            left.graph.edges().iter().zip(right.graph.edges()).enumerate()
+        {
+            let edge_id = edge_id as EdgeId;
+            assert_eq!(
+                (left_edge.src_vertex_id, left_edge.dst_vertex_id),
+                (right_edge.src_vertex_id, right_edge.dst_vertex_id),
+                "{}",
+                message
+            );
+            assert_eq!(
+                named_properties(&left.graph.edge_properties(edge_id), left),
+                named_properties(&right.graph.edge_properties(edge_id), right),
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_graph_round_trip() {
+        for (data_dir, options) in &[
+            ("small_properties", ""),
+            ("small_missing_properties", ""),
+            ("small_float_properties", ""),
+            ("small_date_properties", ""),
+            ("small_list_properties", "list separator '|'"),
+            ("small_quoted_properties", ""),
+        ] {
+            let mut global_store = load_graph(format!(
+                "load graph with vertices from 'data/{0}/vertices.txt' \
+                 and edges from 'data/{0}/edges.txt' {1} comment '#';",
+                data_dir, options
+            ));
+
+            for (format, load_query) in &[
+                (
+                    "csv",
+                    "load graph with vertices from '{0}/vertices.txt' \
+                     and edges from '{0}/edges.txt';",
+                ),
+                ("graphml", "load graph from '{0}/graph.graphml' format graphml;"),
+                (
+                    "jsonl",
+                    "load graph with vertices from '{0}/vertices.jsonl' \
+                     and edges from '{0}/edges.jsonl' format jsonl;",
+                ),
+            ] {
+                let save_dir = std::env::temp_dir()
+                    .join(format!("graphsurge_write_graph_{}_{}", data_dir, format));
+                std::fs::create_dir_all(&save_dir).expect("Save dir not created");
+                let save_dir = save_dir.to_str().expect("Invalid path");
+                let mut query = format!("save graph to '{}' format {};", save_dir, format);
+                process_query(&mut global_store, &mut query).expect("Graph not written");
+
+                let reloaded_store = load_graph(load_query.replace("{0}", save_dir));
+                let message = format!("{} {}", data_dir, format);
+                assert_same_graph(&global_store, &reloaded_store, &message);
+                if *format == "csv" {
+                    // Delimited files also keep the key order.
+                    for edge_id in 0..global_store.graph.edges_count() as EdgeId {
+                        assert_eq!(
+                            format!("{:?}", reloaded_store.graph.edge_properties(edge_id)),
+                            format!("{:?}", global_store.graph.edge_properties(edge_id)),
+                            "{}",
+                            message
+                        );
+                    }
+                }
+            }
         }
-        Ok(GraphSurgeResult::new(format!("Graph written to '{}'", self.dir)))
     }
 }
@@ -0,0 +1,98 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::properties::Properties;
+use crate::query_handler::load_graph::graphml::GS_TYPE_ATTRIBUTE;
+use crate::query_handler::write_graph::executor::{
+    get_field, get_property_columns, get_vertex_id_strings, PropertyColumns,
+};
+use crate::util::io::GsWriter;
+use gs_analytics_api::{EdgeId, VertexId};
+use quick_xml::escape::escape as escape_xml;
+
+pub const GRAPHML_FILE_NAME: &str = "graph.graphml";
+
+/// Writes the graph to a single GraphML file. Property types that GraphML has no type for are
+/// written as strings, with their actual type in a `gs.type` attribute.
+pub(super) fn write_graphml(global_store: &GlobalStore, dir: &str) -> Result<(), GSError> {
+    let graph = &global_store.graph;
+    let vertex_id_strings = get_vertex_id_strings(global_store);
+    let vertex_columns = get_property_columns(
+        (0..graph.vertex_count()).map(|vertex_id| graph.vertex_properties(vertex_id as VertexId)),
+        &global_store.key_store,
+    )?;
+    let edge_columns = get_property_columns(
+        graph.edge_iterator().map(|(edge_id, _)| graph.edge_properties(edge_id as EdgeId)),
+        &global_store.key_store,
+    )?;
+
+    let mut file_buffer = GsWriter::new(format!("{}/{}", dir, GRAPHML_FILE_NAME))?;
+    file_buffer.write_file_line(r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    file_buffer.write_file_line(r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    for (domain, prefix, columns) in &[("node", "v", &vertex_columns), ("edge", "e", &edge_columns)]
+    {
+        for (index, (key_id, type_name)) in columns.iter().enumerate() {
+            let (graphml_type, gs_type) = match *type_name {
+                "int" => ("long", None),
+                "bool" => ("boolean", None),
+                "float" => ("double", None),
+                "string" => ("string", None),
+                type_name => ("string", Some(type_name)),
+            };
+            file_buffer.write_file_line(&format!(
+                r#"  <key id="{}{}" for="{}" attr.name="{}" attr.type="{}"{}/>"#,
+                prefix,
+                index,
+                domain,
+                escape_xml(global_store.key_store.key_string(*key_id)),
+                graphml_type,
+                gs_type.map_or_else(String::new, |gs_type| format!(
+                    r#" {}="{}""#,
+                    GS_TYPE_ATTRIBUTE, gs_type
+                )),
+            ))?;
+        }
+    }
+
+    file_buffer.write_file_line(r#"  <graph id="G" edgedefault="directed">"#)?;
+    for (vertex_id, vertex_id_string) in vertex_id_strings.iter().enumerate() {
+        file_buffer.write_file_line(&format!(
+            r#"    <node id="{}">{}</node>"#,
+            escape_xml(vertex_id_string),
+            get_data_elements(
+                &graph.vertex_properties(vertex_id as VertexId),
+                &vertex_columns,
+                "v"
+            ),
+        ))?;
+    }
+    for (edge_id, edge) in graph.edge_iterator() {
+        file_buffer.write_file_line(&format!(
+            r#"    <edge source="{}" target="{}">{}</edge>"#,
+            escape_xml(&vertex_id_strings[edge.src_vertex_id as usize]),
+            escape_xml(&vertex_id_strings[edge.dst_vertex_id as usize]),
+            get_data_elements(&graph.edge_properties(edge_id as EdgeId), &edge_columns, "e"),
+        ))?;
+    }
+    file_buffer.write_file_line("  </graph>")?;
+    file_buffer.write_file_line("</graphml>")?;
+    Ok(())
+}
+
+fn get_data_elements(properties: &Properties, columns: &PropertyColumns, prefix: &str) -> String {
+    columns
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (key_id, _))| {
+            properties.iter().find(|(property_key_id, _)| property_key_id == key_id).map(
+                |(_, value)| {
+                    format!(
+                        r#"<data key="{}{}">{}</data>"#,
+                        prefix,
+                        index,
+                        escape_xml(&get_field(value))
+                    )
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,86 @@
+use crate::error::GSError;
+use crate::global_store::GlobalStore;
+use crate::graph::key_store::KeyStore;
+use crate::graph::properties::property_value::PropertyValue;
+use crate::graph::properties::Properties;
+use crate::query_handler::load_graph::jsonl::{
+    DATETIME_MEMBER, ID_MEMBER, RESERVED_MEMBERS, SOURCE_MEMBER, TARGET_MEMBER,
+};
+use crate::query_handler::write_graph::executor::{
+    format_datetime, get_type_name, get_vertex_id_strings,
+};
+use crate::util::io::GsWriter;
+use gs_analytics_api::{EdgeId, VertexId};
+use serde_json::{Map, Value};
+
+/// Writes the vertices and edges to `vertices.jsonl` and `edges.jsonl`, with one JSON object per
+/// line.
+pub(super) fn write_jsonl(global_store: &GlobalStore, dir: &str) -> Result<(), GSError> {
+    let graph = &global_store.graph;
+    let key_store = &global_store.key_store;
+    let vertex_id_strings = get_vertex_id_strings(global_store);
+
+    let mut vertex_file_buffer = GsWriter::new(format!("{}/vertices.jsonl", dir))?;
+    for (vertex_id, vertex_id_string) in vertex_id_strings.iter().enumerate() {
+        let mut members = Map::new();
+        members.insert(ID_MEMBER.to_owned(), Value::from(vertex_id_string.as_str()));
+        add_members(&mut members, &graph.vertex_properties(vertex_id as VertexId), key_store)?;
+        vertex_file_buffer.write_file_line(&Value::Object(members).to_string())?;
+    }
+
+    let mut edge_file_buffer = GsWriter::new(format!("{}/edges.jsonl", dir))?;
+    for (edge_id, edge) in graph.edge_iterator() {
+        let mut members = Map::new();
+        members.insert(
+            SOURCE_MEMBER.to_owned(),
+            Value::from(vertex_id_strings[edge.src_vertex_id as usize].as_str()),
+        );
+        members.insert(
+            TARGET_MEMBER.to_owned(),
+            Value::from(vertex_id_strings[edge.dst_vertex_id as usize].as_str()),
+        );
+        add_members(&mut members, &graph.edge_properties(edge_id as EdgeId), key_store)?;
+        edge_file_buffer.write_file_line(&Value::Object(members).to_string())?;
+    }
+    Ok(())
+}
+
+/// Adds the properties as members, after the id members.
+fn add_members(
+    members: &mut Map<String, Value>,
+    properties: &Properties,
+    key_store: &KeyStore,
+) -> Result<(), GSError> {
+    for (key_id, value) in properties.iter() {
+        let name = key_store.key_string(*key_id);
+        if RESERVED_MEMBERS.contains(&name.as_str()) {
+            return Err(GSError::Generic(format!(
+                "Property '{}' cannot be written to a JSON-lines file, as the name is reserved for \
+                ids",
+                name
+            )));
+        }
+        members.insert(name.to_owned(), get_json_value(value)?);
+    }
+    Ok(())
+}
+
+/// Non-finite floats have no JSON representation and are written as `null`.
+fn get_json_value(value: &PropertyValue) -> Result<Value, GSError> {
+    // Fails for the types that cannot be loaded back.
+    get_type_name(value)?;
+    Ok(match value {
+        PropertyValue::Isize(value) => Value::from(*value),
+        PropertyValue::Bool(value) => Value::from(*value),
+        PropertyValue::String(value) => Value::from(value.as_str()),
+        PropertyValue::Strings(values) => Value::from(values.clone()),
+        PropertyValue::IntList(values) => Value::from(values.clone()),
+        PropertyValue::Float(value) => Value::from(value.0),
+        PropertyValue::DateTime(millis) => {
+            let mut members = Map::new();
+            members.insert(DATETIME_MEMBER.to_owned(), Value::from(format_datetime(*millis)));
+            Value::Object(members)
+        }
+        value => unreachable!("Type '{}' is rejected by `get_type_name`", value.value_type()),
+    })
+}
@@ -1,12 +1,17 @@
+use crate::query_handler::load_graph::GraphFormat;
+
 pub mod executor;
+pub mod graphml;
+pub mod jsonl;
 
 #[derive(new)]
 pub struct WriteGraphAst {
     dir: String,
+    format: GraphFormat,
 }
 
 impl std::fmt::Display for WriteGraphAst {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "write graph to '{}'", self.dir)
+        write!(f, "write graph to '{}' format {}", self.dir, self.format)
     }
 }
@@ -0,0 +1,67 @@
+/// A growable set of bits. Bits past the end are unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    /// Returns a bitmap of length `len` with the bits at `indices` set.
+    pub fn from_indices(len: usize, indices: impl Iterator<Item = usize>) -> Self {
+        let mut bitmap = Self { words: vec![0; len.div_ceil(64)], len };
+        for index in indices {
+            bitmap.set(index, true);
+        }
+        bitmap
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, bit: bool) {
+        if index >= self.len {
+            self.len = index + 1;
+            self.words.resize(self.len.div_ceil(64), 0);
+        }
+        if bit {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// Shortens the bitmap to `len` bits. Has no effect if it is already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.len = len;
+        self.words.truncate(len.div_ceil(64));
+        if len % 64 != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (len % 64)) - 1;
+            }
+        }
+    }
+
+    /// Unsets the bits that are not set in `other`.
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (index, word) in self.words.iter_mut().enumerate() {
+            *word &= other.words.get(index).copied().unwrap_or(0);
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
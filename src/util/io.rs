@@ -1,6 +1,6 @@
 use crate::error::GSError;
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 
 pub fn get_buf_reader(file_path: &str) -> Result<BufReader<File>, GSError> {
     Ok(BufReader::new(
@@ -13,6 +13,49 @@ pub fn get_file_lines(file_path: &str) -> Result<impl Iterator<Item = String>, G
     Ok(get_buf_reader(file_path)?.lines().filter_map(Result::ok))
 }
 
+/// A `BufRead` that counts the lines consumed from the wrapped reader as it advances.
+pub struct LineCountingReader<R> {
+    inner: R,
+    newlines: u64,
+}
+
+impl<R> LineCountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, newlines: 0 }
+    }
+
+    /// The 1-based line of the next byte to be consumed.
+    pub fn line(&self) -> u64 {
+        self.newlines + 1
+    }
+}
+
+fn count_newlines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|&&byte| byte == b'\n').count() as u64
+}
+
+impl<R: BufRead> Read for LineCountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.newlines += count_newlines(&buf[..count]);
+        Ok(count)
+    }
+}
+
+impl<R: BufRead> BufRead for LineCountingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // The buffer is already filled, so this does not read from the wrapped reader again.
+        if let Ok(buffer) = self.inner.fill_buf() {
+            self.newlines += count_newlines(&buffer[..amount.min(buffer.len())]);
+        }
+        self.inner.consume(amount);
+    }
+}
+
 pub struct GsWriter {
     buf_writer: BufWriter<File>,
     file_path: String,
@@ -1,3 +1,4 @@
+pub mod bitmap;
 pub mod data_pointer;
 pub mod id_generator;
 pub mod io;